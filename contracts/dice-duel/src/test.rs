@@ -3,7 +3,10 @@
 // Unit tests for the dice-duel contract using a simple mock GameHub.
 // These tests verify game logic independently of the full GameHub system.
 
-use crate::{DiceDuelContract, DiceDuelContractClient, Error};
+use crate::{
+    DiceDuelContract, DiceDuelContractClient, Error, GameOutcome, LegacyGame, TtlConfig,
+    STORAGE_VERSION,
+};
 use soroban_sdk::testutils::{Address as _, Ledger as _};
 use soroban_sdk::{contract, contractimpl, Address, BytesN, Env};
 
@@ -28,10 +31,23 @@ impl MockGameHub {
         // Mock implementation - does nothing
     }
 
-    pub fn end_game(_env: Env, _session_id: u32, _player1_won: bool) {
+    pub fn end_game(_env: Env, _session_id: u32, _outcome: GameOutcome) {
         // Mock implementation - does nothing
     }
 
+    pub fn cancel_session(env: Env, session_id: u32) {
+        // Like the real hub, refuse a session that was already closed
+        if env.storage().instance().has(&session_id) {
+            env.panic_with_error(game_common::HubError::SessionAlreadyEnded);
+        }
+    }
+
+    /// Close a session the way the real hub does when anyone cancels it
+    /// after its deadline
+    pub fn close_session(env: Env, session_id: u32) {
+        env.storage().instance().set(&session_id, &true);
+    }
+
     pub fn add_game(_env: Env, _game_address: Address) {
         // Mock implementation - does nothing
     }
@@ -134,6 +150,7 @@ fn test_complete_game() {
     // Verify initial state
     let game = client.get_game(&session_id);
     assert!(game.winner.is_none());
+    assert!(!game.is_draw);
     assert_eq!(game.player1, player1);
     assert_eq!(game.player2, player2);
     assert_eq!(game.player1_points, points);
@@ -150,13 +167,15 @@ fn test_complete_game() {
     client.roll(&session_id, &player2);
 
     // Reveal winner
-    let winner = client.reveal_winner(&session_id);
-    assert!(winner == player1 || winner == player2);
+    let outcome = client.reveal_winner(&session_id);
 
-    // Verify dice values and winner stored
+    // Verify dice values and outcome stored
     let final_game = client.get_game(&session_id);
-    assert!(final_game.winner.is_some());
-    assert_eq!(final_game.winner.unwrap(), winner);
+    match outcome {
+        GameOutcome::Player1 => assert_eq!(final_game.winner, Some(player1)),
+        GameOutcome::Player2 => assert_eq!(final_game.winner, Some(player2)),
+        _ => assert!(final_game.is_draw),
+    }
 
     let p1d1 = final_game.player1_die1.unwrap();
     let p1d2 = final_game.player1_die2.unwrap();
@@ -172,6 +191,48 @@ fn test_complete_game() {
     let total2 = p2d1 + p2d2;
     assert!((2..=12).contains(&total1));
     assert!((2..=12).contains(&total2));
+    assert_eq!(outcome, expected_outcome(total1, total2));
+}
+
+/// Outcome implied by the two dice totals
+fn expected_outcome(total1: u32, total2: u32) -> GameOutcome {
+    if total1 > total2 {
+        GameOutcome::Player1
+    } else if total2 > total1 {
+        GameOutcome::Player2
+    } else {
+        GameOutcome::Draw
+    }
+}
+
+#[test]
+fn test_equal_totals_are_a_draw() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    // Dice are seeded by session ID, so some of these sessions must tie
+    let mut draws = 0;
+    for session_id in 100u32..160 {
+        client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+        client.roll(&session_id, &player1);
+        client.roll(&session_id, &player2);
+        let outcome = client.reveal_winner(&session_id);
+
+        let game = client.get_game(&session_id);
+        let total1 = game.player1_die1.unwrap() + game.player1_die2.unwrap();
+        let total2 = game.player2_die1.unwrap() + game.player2_die2.unwrap();
+        assert_eq!(outcome, expected_outcome(total1, total2));
+
+        match outcome {
+            GameOutcome::Player1 => assert_eq!(game.winner, Some(player1.clone())),
+            GameOutcome::Player2 => assert_eq!(game.winner, Some(player2.clone())),
+            _ => {
+                assert!(game.winner.is_none());
+                assert!(game.is_draw);
+                draws += 1;
+            }
+        }
+    }
+    assert!(draws > 0, "expected at least one tied roll");
 }
 
 #[test]
@@ -232,6 +293,205 @@ fn test_cannot_roll_after_game_ended() {
     assert_dice_duel_error(&result, Error::GameAlreadyEnded);
 }
 
+#[test]
+fn test_reused_session_cannot_overwrite_live_game() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let player3 = Address::generate(&env);
+    let player4 = Address::generate(&env);
+
+    let session_id = 6u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.roll(&session_id, &player1);
+
+    // A second start with the same session ID must not replace the live game
+    let result = client.try_start_game(&session_id, &player3, &player4, &1, &1);
+    assert_dice_duel_error(&result, Error::SessionAlreadyExists);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.player1, player1);
+    assert_eq!(game.player2, player2);
+    assert!(game.player1_rolled);
+}
+
+#[test]
+fn test_ended_session_cannot_be_restarted() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 7u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.roll(&session_id, &player1);
+    client.roll(&session_id, &player2);
+    let outcome = client.reveal_winner(&session_id);
+
+    let result = client.try_start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    assert_dice_duel_error(&result, Error::SessionAlreadyExists);
+    assert_eq!(client.reveal_winner(&session_id), outcome);
+}
+
+#[test]
+fn test_abort_needs_both_players() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 8u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    // One player alone cannot end the game
+    assert!(!client.abort_game(&session_id, &player1));
+    client.roll(&session_id, &player1);
+
+    // Once both agree the game is cancelled
+    assert!(client.abort_game(&session_id, &player2));
+    let game = client.get_game(&session_id);
+    assert!(game.winner.is_none());
+    assert!(!game.is_draw);
+
+    let result = client.try_roll(&session_id, &player2);
+    assert_dice_duel_error(&result, Error::GameAlreadyEnded);
+    assert_eq!(client.reveal_winner(&session_id), GameOutcome::Cancelled);
+}
+
+#[test]
+fn test_abort_rejected_for_non_player_and_finished_game() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 9u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    let result = client.try_abort_game(&session_id, &Address::generate(&env));
+    assert_dice_duel_error(&result, Error::NotPlayer);
+
+    client.roll(&session_id, &player1);
+    client.roll(&session_id, &player2);
+    client.reveal_winner(&session_id);
+
+    let result = client.try_abort_game(&session_id, &player1);
+    assert_dice_duel_error(&result, Error::GameAlreadyEnded);
+}
+
+#[test]
+fn test_abort_after_hub_closed_session() {
+    let (_env, client, hub, player1, player2) = setup_test();
+
+    let session_id = 40u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    // The session expired and the hub already refunded it
+    hub.close_session(&session_id);
+
+    // The players can still end the game
+    assert!(!client.abort_game(&session_id, &player1));
+    assert!(client.abort_game(&session_id, &player2));
+    let result = client.try_abort_game(&session_id, &player1);
+    assert_dice_duel_error(&result, Error::GameAlreadyEnded);
+    assert_eq!(client.reveal_winner(&session_id), GameOutcome::Cancelled);
+}
+
+#[test]
+fn test_pause_blocks_new_games_and_moves() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    client.start_game(&10, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&11, &player1, &player2, &100_0000000, &100_0000000);
+    client.roll(&10, &player1);
+
+    client.pause();
+    assert_eq!(env.auths()[0].0, client.get_admin());
+    assert!(client.is_paused());
+
+    // Every entry point that starts a game or moves is blocked
+    let result = client.try_start_game(&12, &player1, &player2, &100_0000000, &100_0000000);
+    assert_dice_duel_error(&result, Error::ContractPaused);
+    let result = client.try_roll(&10, &player2);
+    assert_dice_duel_error(&result, Error::ContractPaused);
+    let result = client.try_reveal_winner(&10);
+    assert_dice_duel_error(&result, Error::ContractPaused);
+
+    // Queries and the mutual abort (which cancels on the hub) still work
+    assert!(client.get_game(&10).player1_rolled);
+    assert!(!client.abort_game(&11, &player1));
+    assert!(client.abort_game(&11, &player2));
+
+    // Play resumes where it stopped
+    client.unpause();
+    assert!(!client.is_paused());
+    client.roll(&10, &player2);
+    client.reveal_winner(&10);
+    client.start_game(&12, &player1, &player2, &100_0000000, &100_0000000);
+}
+
+#[test]
+fn test_live_sessions_survive_storage_migration() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    // A session saved by this release, and one saved in the layout used before
+    // storage versioning (no draw or abort flags), on a contract with no
+    // version recorded
+    client.start_game(&13, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&14, &player1, &player2, &100_0000000, &100_0000000);
+    client.roll(&13, &player1);
+    let game = client.get_game(&14);
+    let legacy_game = LegacyGame {
+        player1: game.player1.clone(),
+        player2: game.player2.clone(),
+        player1_points: game.player1_points,
+        player2_points: game.player2_points,
+        player1_rolled: game.player1_rolled,
+        player2_rolled: game.player2_rolled,
+        player1_die1: game.player1_die1,
+        player1_die2: game.player1_die2,
+        player2_die1: game.player2_die1,
+        player2_die2: game.player2_die2,
+        winner: game.winner.clone(),
+    };
+    env.as_contract(&client.address, || {
+        game_common::save_game(&env, 14, &legacy_game);
+        env.storage()
+            .instance()
+            .remove(&game_common::DataKey::StorageVersion);
+    });
+    assert_eq!(client.get_storage_version(), 0);
+
+    // Legacy games load with the new flags cleared and keep playing before
+    // the migration...
+    assert_eq!(client.get_game(&14), game);
+    client.roll(&14, &player1);
+
+    assert_eq!(client.migrate(), 0);
+    assert_eq!(client.get_storage_version(), STORAGE_VERSION);
+    assert_eq!(client.migrate(), STORAGE_VERSION);
+
+    // ...and after it
+    client.roll(&14, &player2);
+    client.reveal_winner(&14);
+    client.roll(&13, &player2);
+    client.reveal_winner(&13);
+}
+
+#[test]
+fn test_bump_game_and_ttl_config() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    client.start_game(&15, &player1, &player2, &100_0000000, &100_0000000);
+
+    // Anyone can keep a game alive; no authorization is needed
+    client.bump_game(&15);
+    assert!(env.auths().is_empty());
+    let result = client.try_bump_game(&999);
+    assert_dice_duel_error(&result, Error::GameNotFound);
+
+    let config = TtlConfig {
+        game_ledgers: 17_280,
+        instance_ledgers: 34_560,
+    };
+    client.set_ttl_config(&config);
+    assert_eq!(client.get_ttl_config(), config);
+    let result = client.try_set_ttl_config(&TtlConfig {
+        game_ledgers: 0,
+        instance_ledgers: 34_560,
+    });
+    assert_dice_duel_error(&result, Error::InvalidTtl);
+    assert_eq!(client.get_ttl_config(), config);
+}
+
 #[test]
 fn test_upgrade_function_exists() {
     let (_env, client, _hub, _player1, _player2) = setup_test();
@@ -0,0 +1,98 @@
+use soroban_sdk::{Address, BytesN, Env};
+
+use crate::events::{AdminChanged, AdminProposalCancelled, AdminProposed, WasmUpgraded};
+use crate::storage::DataKey;
+
+/// Store the admin and GameHub address (call from the contract constructor)
+pub fn init(env: &Env, admin: &Address, game_hub: &Address) {
+    init_admin(env, admin);
+    env.storage()
+        .instance()
+        .set(&DataKey::GameHubAddress, game_hub);
+}
+
+/// Store only the admin, for contracts that do not report to a GameHub
+pub fn init_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&DataKey::Admin, admin);
+}
+
+/// Get the current admin address
+pub fn get_admin(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .expect("Admin not set")
+}
+
+/// Require authorization from the current admin and return it
+pub fn require_admin(env: &Env) -> Address {
+    let admin = get_admin(env);
+    admin.require_auth();
+    admin
+}
+
+/// Get the admin proposed by `propose_admin`, if it has not accepted yet
+pub fn get_pending_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::PendingAdmin)
+}
+
+/// Propose a new admin (admin only)
+///
+/// Nothing changes until the proposed address calls `accept_admin`, so a
+/// mistyped address can never take over. Proposing again replaces any
+/// earlier proposal.
+pub fn propose_admin(env: &Env, new_admin: &Address) {
+    let admin = require_admin(env);
+
+    env.storage()
+        .instance()
+        .set(&DataKey::PendingAdmin, new_admin);
+
+    AdminProposed {
+        admin,
+        pending_admin: new_admin.clone(),
+    }
+    .publish(env);
+}
+
+/// Accept a pending admin proposal (pending admin only)
+pub fn accept_admin(env: &Env) {
+    let pending_admin = get_pending_admin(env).expect("No pending admin");
+    pending_admin.require_auth();
+
+    let previous_admin = get_admin(env);
+    env.storage()
+        .instance()
+        .set(&DataKey::Admin, &pending_admin);
+    env.storage().instance().remove(&DataKey::PendingAdmin);
+
+    AdminChanged {
+        previous_admin,
+        new_admin: pending_admin,
+    }
+    .publish(env);
+}
+
+/// Withdraw a pending admin proposal (admin only)
+pub fn cancel_admin_proposal(env: &Env) {
+    let admin = require_admin(env);
+    let pending_admin = get_pending_admin(env).expect("No pending admin");
+
+    env.storage().instance().remove(&DataKey::PendingAdmin);
+
+    AdminProposalCancelled {
+        admin,
+        pending_admin,
+    }
+    .publish(env);
+}
+
+/// Update the contract WASM hash (admin only)
+pub fn upgrade(env: &Env, new_wasm_hash: BytesN<32>) {
+    require_admin(env);
+
+    env.deployer()
+        .update_current_contract_wasm(new_wasm_hash.clone());
+
+    WasmUpgraded { new_wasm_hash }.publish(env);
+}
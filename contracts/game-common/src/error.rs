@@ -0,0 +1,17 @@
+/// Failures shared by every game contract.
+///
+/// Each contract maps these onto its own `Error` enum with a `From` impl, so
+/// `?` works on the helpers and each contract keeps its existing error codes.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum GameError {
+    /// No game is stored under the session id
+    GameNotFound,
+    /// A game is already stored under the session id
+    SessionAlreadyExists,
+    /// The contract is paused
+    ContractPaused,
+    /// Stored data uses a schema version this release cannot read
+    UnsupportedStorageVersion,
+    /// A TTL is zero or longer than the network allows
+    InvalidTtl,
+}
@@ -0,0 +1,71 @@
+use soroban_sdk::{contractevent, Address, BytesN};
+
+// Audit events for admin-controlled configuration. Operators can follow every
+// change to who controls a contract and what it points at from the event
+// stream alone.
+
+/// A new admin was proposed and must call `accept_admin` to take over
+#[contractevent]
+pub struct AdminProposed {
+    pub admin: Address,
+    pub pending_admin: Address,
+}
+
+/// A pending admin proposal was withdrawn before it was accepted
+#[contractevent]
+pub struct AdminProposalCancelled {
+    pub admin: Address,
+    pub pending_admin: Address,
+}
+
+/// The pending admin accepted and is now the admin
+#[contractevent]
+pub struct AdminChanged {
+    pub previous_admin: Address,
+    pub new_admin: Address,
+}
+
+/// The GameHub address was changed
+#[contractevent]
+pub struct HubChanged {
+    pub previous_hub: Address,
+    pub new_hub: Address,
+}
+
+/// The verification key was replaced (keccak256 of the new key bytes)
+#[contractevent]
+pub struct VkChanged {
+    pub vk_hash: BytesN<32>,
+}
+
+/// New games and moves were blocked by the admin
+#[contractevent]
+pub struct Paused {
+    pub admin: Address,
+}
+
+/// New games and moves were allowed again
+#[contractevent]
+pub struct Unpaused {
+    pub admin: Address,
+}
+
+/// Stored data was migrated to a new schema version after an upgrade
+#[contractevent]
+pub struct StorageMigrated {
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+/// The storage TTL policy was changed
+#[contractevent]
+pub struct TtlConfigChanged {
+    pub game_ledgers: u32,
+    pub instance_ledgers: u32,
+}
+
+/// The contract WASM was upgraded
+#[contractevent]
+pub struct WasmUpgraded {
+    pub new_wasm_hash: BytesN<32>,
+}
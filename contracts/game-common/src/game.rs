@@ -0,0 +1,69 @@
+use soroban_sdk::{contractclient, Address, BytesN, Env};
+
+// `start_game` as exposed by every hub-aware game contract, for contracts
+// that open sessions on games (tournaments, matchmaking)
+#[contractclient(name = "GameClient")]
+pub trait Game {
+    fn start_game(
+        env: Env,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+    );
+}
+
+// `start_game` of games whose players commit to hidden state when the session
+// starts, like The Resistance's base commitments
+#[contractclient(name = "CommitmentGameClient")]
+pub trait CommitmentGame {
+    #[allow(clippy::too_many_arguments)]
+    fn start_game(
+        env: Env,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+        player1_commitment: BytesN<32>,
+        player2_commitment: BytesN<32>,
+    );
+}
+
+/// Start a session on a game contract with both players staking `points`.
+///
+/// With `commitments`, the game's `start_game` is called with the players'
+/// commitments appended (player 1's first); without, with the plain
+/// interface. Games only accept the call matching their own interface, and
+/// they check each player's authorization, which covers their commitment.
+pub fn start_game(
+    env: &Env,
+    game_id: &Address,
+    session_id: u32,
+    player1: &Address,
+    player2: &Address,
+    points: i128,
+    commitments: &Option<(BytesN<32>, BytesN<32>)>,
+) {
+    match commitments {
+        Some((player1_commitment, player2_commitment)) => {
+            CommitmentGameClient::new(env, game_id).start_game(
+                &session_id,
+                player1,
+                player2,
+                &points,
+                &points,
+                player1_commitment,
+                player2_commitment,
+            )
+        }
+        None => GameClient::new(env, game_id).start_game(
+            &session_id,
+            player1,
+            player2,
+            &points,
+            &points,
+        ),
+    }
+}
@@ -0,0 +1,140 @@
+use soroban_sdk::{contractclient, contracterror, contracttype, Address, Env};
+
+use crate::{admin::require_admin, events::HubChanged, storage::DataKey};
+
+// Import GameHub contract interface
+// This allows us to call into the GameHub contract
+#[contractclient(name = "GameHubClient")]
+pub trait GameHub {
+    fn start_game(
+        env: Env,
+        game_id: Address,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+    ) -> Result<(), HubError>;
+
+    fn end_game(env: Env, session_id: u32, outcome: GameOutcome) -> Result<(), HubError>;
+
+    fn cancel_session(env: Env, session_id: u32) -> Result<(), HubError>;
+
+    fn get_session(env: Env, session_id: u32) -> Result<Session, HubError>;
+
+    fn is_game(env: Env, game_id: Address) -> bool;
+
+    fn get_token(env: Env) -> Address;
+
+    fn get_stats(env: Env, player: Address) -> PlayerStats;
+}
+
+/// Errors returned by the `game-hub` contract
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum HubError {
+    SessionNotFound = 1,
+    SessionAlreadyExists = 2,
+    InvalidPoints = 3,
+    InsufficientBalance = 4,
+    InvalidFee = 5,
+    GameNotRegistered = 6,
+    SessionAlreadyEnded = 7,
+}
+
+/// How a session ended, as reported by the game contract
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum GameOutcome {
+    /// Player 1 takes the pot
+    Player1,
+    /// Player 2 takes the pot
+    Player2,
+    /// Each player gets their own stake back, less their share of the fee
+    Draw,
+    /// The game was abandoned: both stakes are refunded and no stats change
+    Cancelled,
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum SessionStatus {
+    /// Stakes are in escrow and the game is in progress
+    Active,
+    /// The game contract reported a result and the pot was settled
+    Ended(GameOutcome),
+}
+
+/// A session, the game contract that owns it and the stakes escrowed for it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Session {
+    pub game_id: Address,
+    pub player1: Address,
+    pub player2: Address,
+    pub player1_points: i128,
+    pub player2_points: i128,
+    pub status: SessionStatus,
+    /// Ledger sequence after which anyone may cancel the session
+    pub deadline: u32,
+}
+
+/// A player's record across every game played through the hub
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlayerStats {
+    pub player: Address,
+    pub games_played: u32,
+    pub wins: u32,
+    pub losses: u32,
+    pub draws: u32,
+    /// Opponents' stakes collected, before the treasury fee
+    pub points_won: i128,
+    /// Own stakes lost
+    pub points_lost: i128,
+    pub rating: u32,
+}
+
+/// Get the current GameHub contract address
+pub fn get_hub(env: &Env) -> Address {
+    env.storage()
+        .instance()
+        .get(&DataKey::GameHubAddress)
+        .expect("GameHub address not set")
+}
+
+/// Set a new GameHub contract address (admin only)
+pub fn set_hub(env: &Env, new_hub: &Address) {
+    require_admin(env);
+
+    let previous_hub = get_hub(env);
+    env.storage()
+        .instance()
+        .set(&DataKey::GameHubAddress, new_hub);
+
+    HubChanged {
+        previous_hub,
+        new_hub: new_hub.clone(),
+    }
+    .publish(env);
+}
+
+/// Client for the configured GameHub contract
+pub fn hub_client(env: &Env) -> GameHubClient<'_> {
+    GameHubClient::new(env, &get_hub(env))
+}
+
+/// Cancel a session at the hub once both players abort.
+///
+/// Anyone may cancel a session at the hub after its deadline, so the stakes
+/// may already be refunded. That leaves nothing to cancel and the game still
+/// ends; any other hub error is raised as usual.
+pub fn cancel_session(env: &Env, session_id: u32) {
+    match hub_client(env).try_cancel_session(&session_id) {
+        Ok(_) => {}
+        Err(Ok(HubError::SessionAlreadyEnded)) => {}
+        Err(Ok(e)) => env.panic_with_error(e),
+        Err(Err(_)) => panic!("GameHub cancel_session failed"),
+    }
+}
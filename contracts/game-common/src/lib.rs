@@ -0,0 +1,72 @@
+#![no_std]
+
+//! # Game Common
+//!
+//! Boilerplate shared by the Game Hub-aware game contracts: the GameHub
+//! client interface with the session, stats and error types the hub returns
+//! (so tournaments and matchmaking read the hub through the same
+//! definitions), admin / hub / upgrade helpers backed by instance
+//! storage, game session storage with an admin-configurable TTL (30 days by
+//! default), and the error cases every game reports.
+//!
+//! Admin changes are two-step: the admin proposes a successor, which only
+//! takes over once it authorizes `accept_admin`. Admin, hub, VK and WASM
+//! changes each publish an event so operators can audit them.
+//!
+//! The admin can pause a game as an emergency stop. Entry points that start
+//! games or make moves call `ensure_not_paused` first; queries and aborts do
+//! not, and the hub can still cancel and refund sessions.
+//!
+//! Games are stored as a versioned enum and the instance keeps a storage
+//! schema version, so live sessions survive an `upgrade` that changes the
+//! `Game` layout; the admin calls `migrate` after upgrading.
+//!
+//! Tournaments and matchmaking open sessions through `start_game`, which
+//! forwards the players' commitments to games that take them at the start
+//! of a session.
+//!
+//! Games that check ZK proofs can call a deployed `zk-verifier` contract
+//! through `VerifierClient` instead of linking the verifier themselves. Its
+//! `VerifierError` is also what a local `VerifyError` converts to, so both
+//! paths fail the same way.
+//!
+//! Contracts keep their own `#[contractimpl]` entry points and `Error` enum,
+//! so their interfaces and error codes are unchanged, and delegate to these
+//! helpers.
+
+mod admin;
+mod error;
+mod events;
+mod game;
+mod hub;
+mod pause;
+mod storage;
+mod ttl;
+mod verifier;
+mod version;
+
+pub use admin::{
+    accept_admin, cancel_admin_proposal, get_admin, get_pending_admin, init, init_admin,
+    propose_admin, require_admin, upgrade,
+};
+pub use error::GameError;
+pub use events::{
+    AdminChanged, AdminProposalCancelled, AdminProposed, HubChanged, Paused, StorageMigrated,
+    TtlConfigChanged, Unpaused, VkChanged, WasmUpgraded,
+};
+pub use game::{start_game, CommitmentGame, CommitmentGameClient, Game, GameClient};
+pub use hub::{
+    cancel_session, get_hub, hub_client, set_hub, GameHub, GameHubClient, GameOutcome, HubError,
+    PlayerStats, Session, SessionStatus,
+};
+pub use pause::{ensure_not_paused, is_paused, pause, unpause};
+pub use storage::{ensure_new_game, load_game, save_game, DataKey};
+pub use ttl::{
+    bump_game, extend_instance_ttl, get_ttl_config, set_ttl_config, TtlConfig,
+    DEFAULT_GAME_TTL_LEDGERS, DEFAULT_INSTANCE_TTL_LEDGERS,
+};
+pub use verifier::{verifier_client, Verifier, VerifierClient, VerifierConfig, VerifierError};
+pub use version::{get_storage_version, load_versioned_game, migrate, set_storage_version};
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,44 @@
+use soroban_sdk::Env;
+
+use crate::admin::require_admin;
+use crate::error::GameError;
+use crate::events::{Paused, Unpaused};
+use crate::storage::DataKey;
+
+/// Whether the contract is paused
+pub fn is_paused(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::Paused)
+        .unwrap_or(false)
+}
+
+/// Fail while the contract is paused (call first in entry points that start
+/// games or make moves)
+pub fn ensure_not_paused(env: &Env) -> Result<(), GameError> {
+    if is_paused(env) {
+        return Err(GameError::ContractPaused);
+    }
+    Ok(())
+}
+
+/// Emergency stop: block new games and moves (admin only)
+///
+/// Queries, admin functions and aborts keep working, and the GameHub can
+/// still cancel and refund sessions, so stakes are never locked in.
+pub fn pause(env: &Env) {
+    let admin = require_admin(env);
+
+    env.storage().instance().set(&DataKey::Paused, &true);
+
+    Paused { admin }.publish(env);
+}
+
+/// Lift the emergency stop (admin only)
+pub fn unpause(env: &Env) {
+    let admin = require_admin(env);
+
+    env.storage().instance().remove(&DataKey::Paused);
+
+    Unpaused { admin }.publish(env);
+}
@@ -0,0 +1,47 @@
+use soroban_sdk::{contracttype, Env, IntoVal, TryFromVal, Val};
+
+use crate::error::GameError;
+use crate::ttl::{extend_instance_ttl, get_ttl_config};
+
+/// Storage keys shared by every game contract. Contracts that need more keep
+/// them in their own `DataKey`; variant names must not overlap with these.
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Game(u32),
+    GameHubAddress,
+    Admin,
+    PendingAdmin,
+    Paused,
+    StorageVersion,
+    TtlConfig,
+}
+
+/// Fail if a game is already stored under `session_id`, so a live game can
+/// never be overwritten
+pub fn ensure_new_game(env: &Env, session_id: u32) -> Result<(), GameError> {
+    if env.storage().temporary().has(&DataKey::Game(session_id)) {
+        return Err(GameError::SessionAlreadyExists);
+    }
+    Ok(())
+}
+
+/// Load a game from temporary storage
+pub fn load_game<T: TryFromVal<Env, Val>>(env: &Env, session_id: u32) -> Result<T, GameError> {
+    env.storage()
+        .temporary()
+        .get(&DataKey::Game(session_id))
+        .ok_or(GameError::GameNotFound)
+}
+
+/// Store a game in temporary storage and extend it, and the contract
+/// instance, to the configured TTLs
+pub fn save_game<T: IntoVal<Env, Val>>(env: &Env, session_id: u32, game: &T) {
+    let key = DataKey::Game(session_id);
+    let game_ledgers = get_ttl_config(env).game_ledgers;
+    env.storage().temporary().set(&key, game);
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, game_ledgers, game_ledgers);
+    extend_instance_ttl(env);
+}
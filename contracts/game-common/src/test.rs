@@ -0,0 +1,524 @@
+#![cfg(test)]
+
+// Unit tests for the shared helpers, run inside a minimal contract that
+// delegates to them the way the game contracts do.
+
+use crate::{
+    AdminChanged, AdminProposalCancelled, AdminProposed, DataKey, GameError, HubChanged, Paused,
+    StorageMigrated, TtlConfig, TtlConfigChanged, Unpaused, DEFAULT_GAME_TTL_LEDGERS,
+    DEFAULT_INSTANCE_TTL_LEDGERS,
+};
+use soroban_sdk::testutils::{
+    storage::Instance as _, storage::Temporary as _, Address as _, Events as _, Ledger as _,
+    MockAuth, MockAuthInvoke,
+};
+use soroban_sdk::{contract, contractimpl, contracttype, vec, Address, Env, Event as _};
+
+// ============================================================================
+// Test Contract
+// ============================================================================
+
+#[contract]
+pub struct TestGame;
+
+#[contractimpl]
+impl TestGame {
+    pub fn __constructor(env: Env, admin: Address, game_hub: Address) {
+        crate::init(&env, &admin, &game_hub);
+    }
+
+    pub fn create(env: Env, session_id: u32, value: u32) -> bool {
+        if crate::ensure_not_paused(&env).is_err()
+            || crate::ensure_new_game(&env, session_id).is_err()
+        {
+            return false;
+        }
+        crate::save_game(&env, session_id, &value);
+        true
+    }
+
+    pub fn read(env: Env, session_id: u32) -> Option<u32> {
+        crate::load_game::<u32>(&env, session_id).ok()
+    }
+
+    pub fn bump(env: Env, session_id: u32) -> bool {
+        crate::bump_game(&env, session_id).is_ok()
+    }
+
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        crate::get_ttl_config(&env)
+    }
+
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> bool {
+        crate::set_ttl_config(&env, &config).is_ok()
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        crate::get_admin(&env)
+    }
+
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        crate::get_pending_admin(&env)
+    }
+
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        crate::propose_admin(&env, &new_admin);
+    }
+
+    pub fn accept_admin(env: Env) {
+        crate::accept_admin(&env);
+    }
+
+    pub fn cancel_admin_proposal(env: Env) {
+        crate::cancel_admin_proposal(&env);
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        crate::is_paused(&env)
+    }
+
+    pub fn pause(env: Env) {
+        crate::pause(&env);
+    }
+
+    pub fn unpause(env: Env) {
+        crate::unpause(&env);
+    }
+
+    pub fn get_hub(env: Env) -> Address {
+        crate::get_hub(&env)
+    }
+
+    pub fn set_hub(env: Env, new_hub: Address) {
+        crate::set_hub(&env, &new_hub);
+    }
+}
+
+// ============================================================================
+// Schema Upgrade Test Contracts
+// ============================================================================
+// Two releases of the same game: release 1 stores `RecordV1`, release 2 adds a
+// field. Re-registering release 2 at release 1's address stands in for
+// `upgrade`, with release 1's sessions still in storage.
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordV1 {
+    pub value: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecordV2 {
+    pub value: u32,
+    pub bonus: u32,
+}
+
+/// What release 1 writes
+#[contracttype]
+#[derive(Clone)]
+pub enum StoredRecordV1 {
+    V1(RecordV1),
+}
+
+/// What release 2 writes, and reads back from either release
+#[contracttype]
+#[derive(Clone)]
+pub enum StoredRecord {
+    V1(RecordV1),
+    V2(RecordV2),
+}
+
+impl From<RecordV1> for StoredRecord {
+    fn from(record: RecordV1) -> Self {
+        StoredRecord::V1(record)
+    }
+}
+
+impl StoredRecord {
+    fn into_current(self) -> RecordV2 {
+        match self {
+            StoredRecord::V1(record) => RecordV2 {
+                value: record.value,
+                bonus: 0,
+            },
+            StoredRecord::V2(record) => record,
+        }
+    }
+}
+
+#[contract]
+pub struct ReleaseOne;
+
+#[contractimpl]
+impl ReleaseOne {
+    pub fn __constructor(env: Env, admin: Address, game_hub: Address) {
+        crate::init(&env, &admin, &game_hub);
+        crate::set_storage_version(&env, 1);
+    }
+
+    pub fn create(env: Env, session_id: u32, value: u32) {
+        crate::save_game(&env, session_id, &StoredRecordV1::V1(RecordV1 { value }));
+    }
+}
+
+#[contract]
+pub struct ReleaseTwo;
+
+#[contractimpl]
+impl ReleaseTwo {
+    pub fn create(env: Env, session_id: u32, value: u32, bonus: u32) {
+        crate::save_game(
+            &env,
+            session_id,
+            &StoredRecord::V2(RecordV2 { value, bonus }),
+        );
+    }
+
+    pub fn read(env: Env, session_id: u32) -> RecordV2 {
+        crate::load_versioned_game::<StoredRecord, RecordV1>(&env, session_id)
+            .unwrap()
+            .into_current()
+    }
+
+    pub fn get_storage_version(env: Env) -> u32 {
+        crate::get_storage_version(&env)
+    }
+
+    pub fn migrate(env: Env) -> u32 {
+        crate::migrate(&env, 2).unwrap()
+    }
+}
+
+fn setup_test() -> (Env, TestGameClient<'static>, Address, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let hub = Address::generate(&env);
+    let contract_id = env.register(TestGame, (&admin, &hub));
+    let client = TestGameClient::new(&env, &contract_id);
+    (env, client, admin, hub)
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[test]
+fn test_game_storage() {
+    let (env, client, _admin, _hub) = setup_test();
+
+    assert_eq!(client.read(&1), None);
+    assert!(client.create(&1, &42));
+    assert_eq!(client.read(&1), Some(42));
+
+    // Reusing a session id is rejected
+    assert!(!client.create(&1, &7));
+    assert_eq!(client.read(&1), Some(42));
+
+    env.as_contract(&client.address, || {
+        let ttl = env.storage().temporary().get_ttl(&DataKey::Game(1));
+        assert_eq!(ttl, DEFAULT_GAME_TTL_LEDGERS);
+        assert_eq!(
+            crate::ensure_new_game(&env, 1),
+            Err(GameError::SessionAlreadyExists)
+        );
+        assert_eq!(
+            crate::load_game::<u32>(&env, 2),
+            Err(GameError::GameNotFound)
+        );
+    });
+}
+
+#[test]
+fn test_admin_and_hub() {
+    let (env, client, admin, hub) = setup_test();
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_hub(), hub);
+
+    let new_hub = Address::generate(&env);
+    client.set_hub(&new_hub);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        env.events().all(),
+        [HubChanged {
+            previous_hub: hub,
+            new_hub: new_hub.clone(),
+        }
+        .to_xdr(&env, &client.address)]
+    );
+    assert_eq!(client.get_hub(), new_hub);
+}
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let (env, client, admin, _hub) = setup_test();
+    let new_admin = Address::generate(&env);
+
+    client.propose_admin(&new_admin);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        env.events().all(),
+        [AdminProposed {
+            admin: admin.clone(),
+            pending_admin: new_admin.clone(),
+        }
+        .to_xdr(&env, &client.address)]
+    );
+
+    // Proposing alone does not hand over control
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+
+    // The proposed admin must authorize the acceptance
+    client.accept_admin();
+    assert_eq!(env.auths()[0].0, new_admin);
+    assert_eq!(
+        env.events().all(),
+        [AdminChanged {
+            previous_admin: admin,
+            new_admin: new_admin.clone(),
+        }
+        .to_xdr(&env, &client.address)]
+    );
+    assert_eq!(client.get_admin(), new_admin);
+    assert_eq!(client.get_pending_admin(), None);
+}
+
+#[test]
+fn test_cancel_admin_proposal() {
+    let (env, client, admin, _hub) = setup_test();
+    let typo = Address::generate(&env);
+
+    client.propose_admin(&typo);
+    client.cancel_admin_proposal();
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        env.events().all(),
+        [AdminProposalCancelled {
+            admin: admin.clone(),
+            pending_admin: typo,
+        }
+        .to_xdr(&env, &client.address)]
+    );
+    assert_eq!(client.get_pending_admin(), None);
+
+    // Nothing left to accept
+    assert!(client.try_accept_admin().is_err());
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+fn test_accept_admin_requires_pending_admin_auth() {
+    let env = Env::default();
+    let admin = Address::generate(&env);
+    let hub = Address::generate(&env);
+    let contract_id = env.register(TestGame, (&admin, &hub));
+    let client = TestGameClient::new(&env, &contract_id);
+    let new_admin = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.propose_admin(&new_admin);
+
+    // Only the current admin signs: acceptance must fail
+    env.mock_auths(&[MockAuth {
+        address: &admin,
+        invoke: &MockAuthInvoke {
+            contract: &contract_id,
+            fn_name: "accept_admin",
+            args: vec![&env],
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(client.try_accept_admin().is_err());
+    assert_eq!(client.get_admin(), admin);
+}
+
+#[test]
+fn test_pause_blocks_new_games() {
+    let (env, client, admin, _hub) = setup_test();
+    assert!(client.create(&1, &42));
+    assert!(!client.is_paused());
+
+    client.pause();
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        env.events().all(),
+        [Paused {
+            admin: admin.clone()
+        }
+        .to_xdr(&env, &client.address)]
+    );
+    assert!(client.is_paused());
+    assert!(!client.create(&2, &7));
+    env.as_contract(&client.address, || {
+        assert_eq!(
+            crate::ensure_not_paused(&env),
+            Err(GameError::ContractPaused)
+        );
+    });
+
+    // Reads still work while paused
+    assert_eq!(client.read(&1), Some(42));
+
+    client.unpause();
+    assert_eq!(
+        env.events().all(),
+        [Unpaused { admin }.to_xdr(&env, &client.address)]
+    );
+    assert!(!client.is_paused());
+    assert!(client.create(&2, &7));
+}
+
+#[test]
+fn test_upgrade_across_schema_change_with_live_sessions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let hub = Address::generate(&env);
+    let contract_id = env.register(ReleaseOne, (&admin, &hub));
+    let release_one = ReleaseOneClient::new(&env, &contract_id);
+
+    // Live sessions from release 1, plus one saved before versioning existed
+    release_one.create(&1, &10);
+    release_one.create(&2, &20);
+    env.as_contract(&contract_id, || {
+        crate::save_game(&env, 3, &RecordV1 { value: 30 });
+    });
+
+    env.register_at(&contract_id, ReleaseTwo, ());
+    let release_two = ReleaseTwoClient::new(&env, &contract_id);
+
+    // Old records decode into the new layout, before and after `migrate`
+    assert_eq!(
+        release_two.read(&1),
+        RecordV2 {
+            value: 10,
+            bonus: 0
+        }
+    );
+    assert_eq!(release_two.get_storage_version(), 1);
+    assert_eq!(release_two.migrate(), 1);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        env.events().all(),
+        [StorageMigrated {
+            from_version: 1,
+            to_version: 2,
+        }
+        .to_xdr(&env, &contract_id)]
+    );
+    assert_eq!(release_two.get_storage_version(), 2);
+    assert_eq!(release_two.read(&2).value, 20);
+    assert_eq!(release_two.read(&3).value, 30);
+
+    // New sessions use the new layout
+    release_two.create(&4, &40, &5);
+    assert_eq!(
+        release_two.read(&4),
+        RecordV2 {
+            value: 40,
+            bonus: 5
+        }
+    );
+
+    // Migrating twice is a no-op
+    assert_eq!(release_two.migrate(), 2);
+    assert_eq!(env.events().all().events().len(), 0);
+}
+
+#[test]
+fn test_migrate_rejects_newer_data() {
+    let (env, client, _admin, _hub) = setup_test();
+
+    env.as_contract(&client.address, || {
+        assert_eq!(crate::get_storage_version(&env), 0);
+        assert_eq!(crate::migrate(&env, 3), Ok(0));
+    });
+
+    env.as_contract(&client.address, || {
+        // Older WASM must not run against data from a newer release
+        assert_eq!(
+            crate::migrate(&env, 2),
+            Err(GameError::UnsupportedStorageVersion)
+        );
+
+        // Records that match no known layout are reported, not misread
+        crate::save_game(&env, 5, &7u32);
+        assert_eq!(
+            crate::load_versioned_game::<StoredRecord, RecordV1>(&env, 5).err(),
+            Some(GameError::UnsupportedStorageVersion)
+        );
+    });
+}
+
+#[test]
+fn test_ttl_config_and_bump() {
+    let (env, client, admin, _hub) = setup_test();
+    assert_eq!(
+        client.get_ttl_config(),
+        TtlConfig {
+            game_ledgers: DEFAULT_GAME_TTL_LEDGERS,
+            instance_ledgers: DEFAULT_INSTANCE_TTL_LEDGERS,
+        }
+    );
+
+    let config = TtlConfig {
+        game_ledgers: 6_000,
+        instance_ledgers: 10_000,
+    };
+    assert!(client.set_ttl_config(&config));
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        env.events().all(),
+        [TtlConfigChanged {
+            game_ledgers: 6_000,
+            instance_ledgers: 10_000,
+        }
+        .to_xdr(&env, &client.address)]
+    );
+    assert_eq!(client.get_ttl_config(), config);
+
+    // Zero and beyond the network maximum are refused
+    let max_ttl = env.as_contract(&client.address, || env.storage().max_ttl());
+    assert!(!client.set_ttl_config(&TtlConfig {
+        game_ledgers: 0,
+        instance_ledgers: 10_000,
+    }));
+    assert!(!client.set_ttl_config(&TtlConfig {
+        game_ledgers: 6_000,
+        instance_ledgers: max_ttl + 1,
+    }));
+    assert_eq!(client.get_ttl_config(), config);
+
+    // Saving a game applies both lifetimes
+    assert!(client.create(&1, &42));
+    let ttls = || {
+        env.as_contract(&client.address, || {
+            (
+                env.storage().temporary().get_ttl(&DataKey::Game(1)),
+                env.storage().instance().get_ttl(),
+            )
+        })
+    };
+    assert_eq!(ttls(), (6_000, 10_000));
+
+    // Anyone can bump a game back to the full TTL
+    env.ledger().with_mut(|li| li.sequence_number += 400);
+    assert_eq!(ttls(), (5_600, 9_600));
+    assert!(client.bump(&1));
+    assert_eq!(ttls(), (6_000, 9_600));
+
+    // Instance storage is extended once half its TTL has passed
+    env.ledger().with_mut(|li| li.sequence_number += 4_700);
+    assert_eq!(ttls(), (1_300, 4_900));
+    assert!(client.bump(&1));
+    assert_eq!(ttls(), (6_000, 10_000));
+
+    assert!(!client.bump(&2));
+    env.as_contract(&client.address, || {
+        assert_eq!(crate::bump_game(&env, 2), Err(GameError::GameNotFound));
+    });
+}
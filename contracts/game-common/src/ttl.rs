@@ -0,0 +1,89 @@
+use soroban_sdk::{contracttype, Env};
+
+use crate::admin::require_admin;
+use crate::error::GameError;
+use crate::events::TtlConfigChanged;
+use crate::storage::DataKey;
+
+// ============================================================================
+// Storage TTL Management
+// ============================================================================
+// TTL (Time To Live) ensures game data doesn't expire unexpectedly. Games sit
+// in temporary storage and the contract's own config in instance storage;
+// both lifetimes are set by the admin, defaulting to 30 days.
+
+/// Default TTL for game storage (30 days in ledgers, ~5 seconds per ledger)
+/// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
+pub const DEFAULT_GAME_TTL_LEDGERS: u32 = 518_400;
+
+/// Default TTL for instance storage (admin, hub and config), also 30 days
+pub const DEFAULT_INSTANCE_TTL_LEDGERS: u32 = 518_400;
+
+/// Storage lifetimes, in ledgers
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfig {
+    /// Each game is kept alive this long after its last move or bump
+    pub game_ledgers: u32,
+    /// Instance storage is extended back to this once half of it has passed
+    pub instance_ledgers: u32,
+}
+
+/// Get the configured storage lifetimes
+pub fn get_ttl_config(env: &Env) -> TtlConfig {
+    env.storage()
+        .instance()
+        .get(&DataKey::TtlConfig)
+        .unwrap_or(TtlConfig {
+            game_ledgers: DEFAULT_GAME_TTL_LEDGERS,
+            instance_ledgers: DEFAULT_INSTANCE_TTL_LEDGERS,
+        })
+}
+
+/// Set the storage lifetimes (admin only)
+///
+/// Both must be non-zero and no longer than the network's maximum TTL. New
+/// values apply from the next write or bump; existing TTLs are not shortened.
+pub fn set_ttl_config(env: &Env, config: &TtlConfig) -> Result<(), GameError> {
+    require_admin(env);
+
+    let max_ttl = env.storage().max_ttl();
+    let valid = |ledgers: u32| ledgers > 0 && ledgers <= max_ttl;
+    if !valid(config.game_ledgers) || !valid(config.instance_ledgers) {
+        return Err(GameError::InvalidTtl);
+    }
+
+    env.storage().instance().set(&DataKey::TtlConfig, config);
+
+    TtlConfigChanged {
+        game_ledgers: config.game_ledgers,
+        instance_ledgers: config.instance_ledgers,
+    }
+    .publish(env);
+    Ok(())
+}
+
+/// Extend instance storage once less than half its configured TTL is left
+pub fn extend_instance_ttl(env: &Env) {
+    let instance_ledgers = get_ttl_config(env).instance_ledgers;
+    env.storage()
+        .instance()
+        .extend_ttl(instance_ledgers / 2, instance_ledgers);
+}
+
+/// Extend a game to the full configured game TTL
+///
+/// Needs no authorization, so spectators can keep interesting games alive.
+pub fn bump_game(env: &Env, session_id: u32) -> Result<(), GameError> {
+    let key = DataKey::Game(session_id);
+    if !env.storage().temporary().has(&key) {
+        return Err(GameError::GameNotFound);
+    }
+
+    let game_ledgers = get_ttl_config(env).game_ledgers;
+    env.storage()
+        .temporary()
+        .extend_ttl(&key, game_ledgers, game_ledgers);
+    extend_instance_ttl(env);
+    Ok(())
+}
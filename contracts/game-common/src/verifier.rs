@@ -0,0 +1,75 @@
+use soroban_sdk::{contractclient, contracterror, contracttype, Address, Bytes, BytesN, Env};
+use ultrahonk_soroban_verifier::VerifyError;
+
+// Interface of the standalone `zk-verifier` contract, so games can verify
+// proofs without linking the verifier into their own WASM
+#[contractclient(name = "VerifierClient")]
+pub trait Verifier {
+    fn verify(
+        env: Env,
+        vk_hash: BytesN<32>,
+        proof: Bytes,
+        public_inputs: Bytes,
+    ) -> Result<(), VerifierError>;
+
+    fn verify_zk(
+        env: Env,
+        vk_hash: BytesN<32>,
+        proof: Bytes,
+        public_inputs: Bytes,
+    ) -> Result<(), VerifierError>;
+}
+
+/// Failures reported by the verifier contract's `verify` and `verify_zk`
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum VerifierError {
+    VkNotFound = 1,
+    VkParseError = 2,
+    InvalidProof = 3,
+    PublicInputsMismatch = 4,
+    SumcheckRoundFailed = 5,
+    SumcheckFinalMismatch = 6,
+    ShplonkDenominatorZero = 7,
+    PairingCheckFailed = 8,
+    ProofVerificationFailed = 9,
+}
+
+/// The one mapping from verifier failures to contract errors, shared by the
+/// verifier contract and games that verify locally
+impl From<VerifyError> for VerifierError {
+    fn from(error: VerifyError) -> Self {
+        match error {
+            VerifyError::ProofLength { .. } | VerifyError::InvalidPoint => Self::InvalidProof,
+            VerifyError::VkLength { .. } | VerifyError::InvalidVkHeader => Self::VkParseError,
+            VerifyError::PublicInputsNotAligned { .. } | VerifyError::PublicInputsCount { .. } => {
+                Self::PublicInputsMismatch
+            }
+            VerifyError::SumcheckRound { .. } | VerifyError::SumcheckDenominatorZero { .. } => {
+                Self::SumcheckRoundFailed
+            }
+            VerifyError::SumcheckFinalMismatch => Self::SumcheckFinalMismatch,
+            VerifyError::ShplonkDenominatorZero => Self::ShplonkDenominatorZero,
+            VerifyError::PairingFailed => Self::PairingCheckFailed,
+            VerifyError::PublicInputDeltaDenominatorZero
+            | VerifyError::GeminiDenominatorZero
+            | VerifyError::LibraInconsistent
+            | VerifyError::MsmLength => Self::ProofVerificationFailed,
+        }
+    }
+}
+
+/// A verifier contract and the registered VK (keccak256 of its bytes) to
+/// check proofs against
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifierConfig {
+    pub verifier: Address,
+    pub vk_hash: BytesN<32>,
+}
+
+/// Client for the verifier contract in `config`
+pub fn verifier_client<'a>(env: &Env, config: &VerifierConfig) -> VerifierClient<'a> {
+    VerifierClient::new(env, &config.verifier)
+}
@@ -0,0 +1,79 @@
+use soroban_sdk::{Env, TryFromVal, Val};
+
+use crate::admin::require_admin;
+use crate::error::GameError;
+use crate::events::StorageMigrated;
+use crate::storage::DataKey;
+
+// ============================================================================
+// Storage Schema Versioning
+// ============================================================================
+// `upgrade` swaps the WASM but leaves stored data as it was. Each contract
+// stores its games as a versioned enum (a schema change adds a variant and
+// converts older ones on read), and keeps the schema version of its data in
+// instance storage so `migrate` knows which one-off steps still have to run.
+
+/// Schema version of the contract's stored data. Contracts deployed before
+/// storage versioning have no version key and report 0.
+pub fn get_storage_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&DataKey::StorageVersion)
+        .unwrap_or(0)
+}
+
+/// Record the schema version (call from the constructor with the contract's
+/// current version, so fresh deployments need no migration)
+pub fn set_storage_version(env: &Env, version: u32) {
+    env.storage()
+        .instance()
+        .set(&DataKey::StorageVersion, &version);
+}
+
+/// Move the stored schema version up to `to_version` (admin only)
+///
+/// Call from the contract's `migrate` entry point after `upgrade`. Returns
+/// the version the data was at, so the contract can run the steps for each
+/// version it crossed. Calling it again once migrated changes nothing. Fails
+/// if the data is newer than `to_version`, which means older WASM is running
+/// against data written by a newer release.
+pub fn migrate(env: &Env, to_version: u32) -> Result<u32, GameError> {
+    require_admin(env);
+
+    let from_version = get_storage_version(env);
+    if from_version > to_version {
+        return Err(GameError::UnsupportedStorageVersion);
+    }
+    if from_version < to_version {
+        set_storage_version(env, to_version);
+        StorageMigrated {
+            from_version,
+            to_version,
+        }
+        .publish(env);
+    }
+    Ok(from_version)
+}
+
+/// Load a game record written by any schema version
+///
+/// `V` is the contract's versioned game enum. Records saved before storage
+/// versioning hold a bare `L` and are converted with `From`.
+pub fn load_versioned_game<V, L>(env: &Env, session_id: u32) -> Result<V, GameError>
+where
+    V: TryFromVal<Env, Val>,
+    L: TryFromVal<Env, Val> + Into<V>,
+{
+    let stored: Val = env
+        .storage()
+        .temporary()
+        .get(&DataKey::Game(session_id))
+        .ok_or(GameError::GameNotFound)?;
+
+    if let Ok(game) = V::try_from_val(env, &stored) {
+        return Ok(game);
+    }
+    L::try_from_val(env, &stored)
+        .map(Into::into)
+        .map_err(|_| GameError::UnsupportedStorageVersion)
+}
@@ -0,0 +1,62 @@
+//! Integer Elo rating updates.
+//!
+//! Contracts have no floating point, so the expected score is read from a
+//! table of `1 / (1 + 10^(-d / 400))` in basis points, sampled every 50
+//! rating points and linearly interpolated in between. Differences beyond
+//! 800 points are clamped.
+
+/// Rating given to a player the first time they finish a game
+pub const INITIAL_RATING: u32 = 1200;
+
+/// Maximum rating change from a single game
+pub const K_FACTOR: u32 = 32;
+
+const SCALE: u32 = 10_000;
+const STEP: u32 = 50;
+const MAX_DIFF: u32 = 800;
+
+/// Expected score (bps) of the higher-rated player, for d = 0, 50, ..., 800
+const EXPECTED_BPS: [u32; 17] = [
+    5000, 5715, 6401, 7034, 7597, 8083, 8490, 8823, 9091, 9302, 9468, 9595, 9693, 9768, 9825, 9868,
+    9901,
+];
+
+/// Expected score of a player rated `rating` against `opponent`, in basis points.
+pub fn expected_score_bps(rating: u32, opponent: u32) -> u32 {
+    let diff = rating.abs_diff(opponent).min(MAX_DIFF);
+    let i = (diff / STEP) as usize;
+    let frac = diff % STEP;
+    let favourite = if frac == 0 {
+        EXPECTED_BPS[i]
+    } else {
+        EXPECTED_BPS[i] + (EXPECTED_BPS[i + 1] - EXPECTED_BPS[i]) * frac / STEP
+    };
+
+    if rating >= opponent {
+        favourite
+    } else {
+        SCALE - favourite
+    }
+}
+
+/// New ratings for `(winner, loser)` after a decisive game.
+///
+/// The exchange is zero-sum except where the loser's rating is floored at 0.
+pub fn rate_win(winner: u32, loser: u32) -> (u32, u32) {
+    let expected = expected_score_bps(winner, loser);
+    let delta = (K_FACTOR * (SCALE - expected) + SCALE / 2) / SCALE;
+    (winner + delta, loser.saturating_sub(delta))
+}
+
+/// New ratings for `(a, b)` after a draw.
+///
+/// The lower-rated player gains what the higher-rated player loses.
+pub fn rate_draw(a: u32, b: u32) -> (u32, u32) {
+    let expected = expected_score_bps(a, b);
+    let delta = (K_FACTOR * expected.abs_diff(SCALE / 2) + SCALE / 2) / SCALE;
+    if a >= b {
+        (a.saturating_sub(delta), b + delta)
+    } else {
+        (a + delta, b.saturating_sub(delta))
+    }
+}
@@ -0,0 +1,658 @@
+#![no_std]
+
+//! # Game Hub
+//!
+//! The production Game Hub contract. Game contracts open and close sessions
+//! through the same `start_game` / `end_game` interface that `MockGameHub`
+//! exposes, but here the points are real: both stakes are escrowed in a
+//! configured Stellar asset token when a session starts, and the pot is paid
+//! to the winner (minus an optional treasury fee) when it ends. A draw
+//! returns each player's stake less their share of the fee, and a cancelled
+//! session refunds both stakes in full.
+//!
+//! A game contract can cancel its own session at any time (e.g. when both
+//! players agree to abort). Once a session passes its deadline without a
+//! result, anyone may cancel it, so stakes are never locked behind a game
+//! that expired or a player who disappeared.
+//!
+//! Only game contracts on the admin-managed allowlist may open sessions, and
+//! only the game contract that opened a session may end it.
+//!
+//! Every finished session also updates both players' stats and Elo ratings,
+//! which back the paged `get_player_stats` and `get_top_players` queries.
+
+use soroban_sdk::{
+    contract, contractevent, contractimpl, contracttype, token::TokenClient, Address, BytesN, Env,
+    Vec,
+};
+
+mod elo;
+
+pub use elo::{INITIAL_RATING, K_FACTOR};
+// Shared with the games, tournaments and matchmaking that call the hub
+pub use game_common::{GameOutcome, HubError as Error, PlayerStats, Session, SessionStatus};
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[contractevent]
+pub struct GameStarted {
+    pub session_id: u32,
+    pub game_id: Address,
+    pub player1: Address,
+    pub player2: Address,
+    pub player1_points: i128,
+    pub player2_points: i128,
+}
+
+#[contractevent]
+pub struct GameEnded {
+    pub session_id: u32,
+    pub outcome: GameOutcome,
+}
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Stellar asset contract used for stakes
+    Token,
+    /// Receives the fee taken from each pot
+    Treasury,
+    /// Fee in basis points (0 = no fee)
+    FeeBps,
+    Session(u32),
+    /// Allowlisted game contract
+    RegisteredGame(Address),
+    PlayerStats(Address),
+    /// Number of players with stats, for paging
+    PlayerCount,
+    /// Player by registration order, for paging
+    PlayerAt(u32),
+    /// A player's position in the `PlayerAt` index
+    PlayerIndex(Address),
+    /// Up to `LEADERBOARD_SIZE` `(player, rating)` pairs, highest rating first
+    Leaderboard,
+}
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Basis-point denominator (100% = 10_000 bps)
+pub const MAX_FEE_BPS: u32 = 10_000;
+
+/// TTL for session storage (30 days in ledgers, ~5 seconds per ledger)
+const SESSION_TTL_LEDGERS: u32 = 518_400;
+
+/// Ledgers a session may stay open before anyone can cancel it. Matches the
+/// 30-day TTL game contracts use for their own state.
+pub const SESSION_DEADLINE_LEDGERS: u32 = 518_400;
+
+/// Instance TTL bump threshold (1 day in ledgers)
+const INSTANCE_TTL_THRESHOLD: u32 = 17_280;
+
+/// Number of players kept on the ranked leaderboard
+pub const LEADERBOARD_SIZE: u32 = 100;
+
+/// Maximum number of entries returned by a paged query
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+// ============================================================================
+// Contract Definition
+// ============================================================================
+
+#[contract]
+pub struct GameHub;
+
+#[contractimpl]
+impl GameHub {
+    /// Initialize the hub with its admin, stake token and fee configuration.
+    ///
+    /// # Arguments
+    /// * `admin` - Admin address (can configure fees and upgrade contract)
+    /// * `token` - Stellar asset contract that stakes are paid in
+    /// * `treasury` - Address that receives the fee from each pot
+    /// * `fee_bps` - Fee taken from each pot, in basis points (0 = no fee)
+    pub fn __constructor(
+        env: Env,
+        admin: Address,
+        token: Address,
+        treasury: Address,
+        fee_bps: u32,
+    ) -> Result<(), Error> {
+        if fee_bps > MAX_FEE_BPS {
+            return Err(Error::InvalidFee);
+        }
+        game_common::init_admin(&env, &admin);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+        Ok(())
+    }
+
+    // ========================================================================
+    // Game Hub Interface
+    // ========================================================================
+
+    /// Start a game session and move both stakes into escrow.
+    ///
+    /// Each player transfers their points to the hub, so both players must
+    /// authorize the transfer as part of the game contract's `start_game`.
+    ///
+    /// **Auth:** Requires authorization from `game_id`, which must be a
+    /// registered game contract.
+    ///
+    /// # Arguments
+    /// * `game_id` - Address of the game contract calling this method
+    /// * `session_id` - Unique identifier for this game session
+    /// * `player1` - Address of first player
+    /// * `player2` - Address of second player
+    /// * `player1_points` - Points staked by player 1
+    /// * `player2_points` - Points staked by player 2
+    pub fn start_game(
+        env: Env,
+        game_id: Address,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+    ) -> Result<(), Error> {
+        game_id.require_auth();
+        let game_key = DataKey::RegisteredGame(game_id.clone());
+        if !env.storage().persistent().has(&game_key) {
+            return Err(Error::GameNotRegistered);
+        }
+        // Keep the allowlist entry live for as long as the game is in use
+        env.storage()
+            .persistent()
+            .extend_ttl(&game_key, SESSION_TTL_LEDGERS, SESSION_TTL_LEDGERS);
+
+        if player1_points < 0 || player2_points < 0 {
+            return Err(Error::InvalidPoints);
+        }
+
+        let key = DataKey::Session(session_id);
+        if env.storage().persistent().has(&key) {
+            return Err(Error::SessionAlreadyExists);
+        }
+
+        // Refuse the session up front if either player cannot cover their stake
+        let token = Self::token_client(&env);
+        if token.balance(&player1) < player1_points || token.balance(&player2) < player2_points {
+            return Err(Error::InsufficientBalance);
+        }
+
+        // Move both stakes into escrow
+        let hub = env.current_contract_address();
+        if player1_points > 0 {
+            token.transfer(&player1, &hub, &player1_points);
+        }
+        if player2_points > 0 {
+            token.transfer(&player2, &hub, &player2_points);
+        }
+
+        let session = Session {
+            game_id: game_id.clone(),
+            player1: player1.clone(),
+            player2: player2.clone(),
+            player1_points,
+            player2_points,
+            status: SessionStatus::Active,
+            deadline: env.ledger().sequence() + SESSION_DEADLINE_LEDGERS,
+        };
+        // Keep the record live past the deadline so it can still be cancelled
+        let ttl = SESSION_DEADLINE_LEDGERS + SESSION_TTL_LEDGERS;
+        env.storage().persistent().set(&key, &session);
+        env.storage().persistent().extend_ttl(&key, ttl, ttl);
+
+        GameStarted {
+            session_id,
+            game_id,
+            player1,
+            player2,
+            player1_points,
+            player2_points,
+        }
+        .publish(&env);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_TTL_THRESHOLD, SESSION_TTL_LEDGERS);
+
+        Ok(())
+    }
+
+    /// End a game session and settle the escrowed pot.
+    ///
+    /// A decisive result pays both stakes to the winner minus the treasury
+    /// fee. A draw returns each stake minus the fee on it, and a cancelled
+    /// session refunds both stakes without a fee.
+    ///
+    /// **Auth:** Requires authorization from the game contract that opened
+    /// the session.
+    ///
+    /// # Arguments
+    /// * `session_id` - The game session being ended
+    /// * `outcome` - Result reported by the game contract
+    pub fn end_game(env: Env, session_id: u32, outcome: GameOutcome) -> Result<(), Error> {
+        let session = Self::load_active_session(&env, session_id)?;
+        session.game_id.require_auth();
+
+        Self::settle(&env, session_id, session, outcome);
+        Ok(())
+    }
+
+    /// Cancel a session that will not complete and refund both stakes.
+    ///
+    /// No fee is taken and player stats are left untouched.
+    ///
+    /// **Auth:** Before the session deadline, requires authorization from the
+    /// game contract that opened the session (e.g. on a mutual abort). After
+    /// the deadline, anyone may call it.
+    ///
+    /// # Arguments
+    /// * `session_id` - The game session to cancel
+    pub fn cancel_session(env: Env, session_id: u32) -> Result<(), Error> {
+        let session = Self::load_active_session(&env, session_id)?;
+        if env.ledger().sequence() <= session.deadline {
+            session.game_id.require_auth();
+        }
+
+        Self::settle(&env, session_id, session, GameOutcome::Cancelled);
+        Ok(())
+    }
+
+    // ========================================================================
+    // Query Functions
+    // ========================================================================
+
+    /// Get a session, its owning game contract and its stakes.
+    pub fn get_session(env: Env, session_id: u32) -> Result<Session, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Session(session_id))
+            .ok_or(Error::SessionNotFound)
+    }
+
+    /// Check whether a game contract is on the allowlist.
+    pub fn is_game(env: Env, game_id: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::RegisteredGame(game_id))
+    }
+
+    /// Get the stake token address.
+    pub fn get_token(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Token)
+            .expect("Token not set")
+    }
+
+    /// Get the treasury address.
+    pub fn get_treasury(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Treasury)
+            .expect("Treasury not set")
+    }
+
+    /// Get the fee taken from each pot, in basis points.
+    pub fn get_fee_bps(env: Env) -> u32 {
+        env.storage().instance().get(&DataKey::FeeBps).unwrap_or(0)
+    }
+
+    /// Get one player's stats.
+    ///
+    /// Players who have not finished a game yet get empty stats at the
+    /// initial rating.
+    pub fn get_stats(env: Env, player: Address) -> PlayerStats {
+        Self::load_stats(&env, &player)
+    }
+
+    /// Page through every player's stats in the order they first played.
+    ///
+    /// Players whose index entry has expired are skipped, so a page can be
+    /// shorter than `limit`.
+    ///
+    /// # Arguments
+    /// * `start` - Index of the first player to return
+    /// * `limit` - Maximum number of players to return (capped at `MAX_PAGE_SIZE`)
+    pub fn get_player_stats(env: Env, start: u32, limit: u32) -> Vec<PlayerStats> {
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PlayerCount)
+            .unwrap_or(0);
+        let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+
+        let mut page = Vec::new(&env);
+        for i in start..end {
+            let player: Option<Address> = env.storage().persistent().get(&DataKey::PlayerAt(i));
+            if let Some(player) = player {
+                page.push_back(Self::load_stats(&env, &player));
+            }
+        }
+        page
+    }
+
+    /// Page through the leaderboard, highest rating first.
+    ///
+    /// Only the top `LEADERBOARD_SIZE` players are ranked.
+    ///
+    /// # Arguments
+    /// * `start` - Rank (0-based) of the first player to return
+    /// * `limit` - Maximum number of players to return (capped at `MAX_PAGE_SIZE`)
+    pub fn get_top_players(env: Env, start: u32, limit: u32) -> Vec<PlayerStats> {
+        let board = Self::load_leaderboard(&env);
+        let end = start
+            .saturating_add(limit.min(MAX_PAGE_SIZE))
+            .min(board.len());
+
+        let mut page = Vec::new(&env);
+        for i in start..end {
+            let (player, _) = board.get_unchecked(i);
+            page.push_back(Self::load_stats(&env, &player));
+        }
+        page
+    }
+
+    // ========================================================================
+    // Internal Helper Functions
+    // ========================================================================
+
+    fn load_active_session(env: &Env, session_id: u32) -> Result<Session, Error> {
+        let session: Session = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Session(session_id))
+            .ok_or(Error::SessionNotFound)?;
+        if session.status != SessionStatus::Active {
+            return Err(Error::SessionAlreadyEnded);
+        }
+        Ok(session)
+    }
+
+    /// Mark a session ended, pay out the pot and update player stats
+    fn settle(env: &Env, session_id: u32, mut session: Session, outcome: GameOutcome) {
+        let key = DataKey::Session(session_id);
+        session.status = SessionStatus::Ended(outcome);
+        env.storage().persistent().set(&key, &session);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, SESSION_TTL_LEDGERS, SESSION_TTL_LEDGERS);
+
+        let pot = session.player1_points + session.player2_points;
+        let (player1_payout, player2_payout) = match outcome {
+            GameOutcome::Player1 => (pot - Self::fee_for(env, pot), 0),
+            GameOutcome::Player2 => (0, pot - Self::fee_for(env, pot)),
+            GameOutcome::Draw => (
+                session.player1_points - Self::fee_for(env, session.player1_points),
+                session.player2_points - Self::fee_for(env, session.player2_points),
+            ),
+            GameOutcome::Cancelled => (session.player1_points, session.player2_points),
+        };
+        let fee = pot - player1_payout - player2_payout;
+
+        let token = Self::token_client(env);
+        let hub = env.current_contract_address();
+        if fee > 0 {
+            let treasury: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::Treasury)
+                .expect("Treasury not set");
+            token.transfer(&hub, &treasury, &fee);
+        }
+        if player1_payout > 0 {
+            token.transfer(&hub, &session.player1, &player1_payout);
+        }
+        if player2_payout > 0 {
+            token.transfer(&hub, &session.player2, &player2_payout);
+        }
+
+        if outcome != GameOutcome::Cancelled {
+            Self::record_result(env, &session, outcome);
+        }
+
+        GameEnded {
+            session_id,
+            outcome,
+        }
+        .publish(env);
+    }
+
+    fn token_client(env: &Env) -> TokenClient<'_> {
+        let token: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .expect("Token not set");
+        TokenClient::new(env, &token)
+    }
+
+    /// Update both players' stats, ratings and leaderboard positions
+    fn record_result(env: &Env, session: &Session, outcome: GameOutcome) {
+        let mut player1 = Self::load_stats(env, &session.player1);
+        let mut player2 = Self::load_stats(env, &session.player2);
+        player1.games_played += 1;
+        player2.games_played += 1;
+
+        match outcome {
+            GameOutcome::Player1 => {
+                (player1.rating, player2.rating) = elo::rate_win(player1.rating, player2.rating);
+                player1.wins += 1;
+                player1.points_won += session.player2_points;
+                player2.losses += 1;
+                player2.points_lost += session.player2_points;
+            }
+            GameOutcome::Player2 => {
+                (player2.rating, player1.rating) = elo::rate_win(player2.rating, player1.rating);
+                player2.wins += 1;
+                player2.points_won += session.player1_points;
+                player1.losses += 1;
+                player1.points_lost += session.player1_points;
+            }
+            GameOutcome::Draw => {
+                (player1.rating, player2.rating) = elo::rate_draw(player1.rating, player2.rating);
+                player1.draws += 1;
+                player2.draws += 1;
+            }
+            GameOutcome::Cancelled => return,
+        }
+
+        Self::save_stats(env, &player1);
+        Self::save_stats(env, &player2);
+
+        let mut board = Self::load_leaderboard(env);
+        Self::rank(&mut board, &player1);
+        Self::rank(&mut board, &player2);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Leaderboard, &board);
+        env.storage().persistent().extend_ttl(
+            &DataKey::Leaderboard,
+            SESSION_TTL_LEDGERS,
+            SESSION_TTL_LEDGERS,
+        );
+    }
+
+    fn load_stats(env: &Env, player: &Address) -> PlayerStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PlayerStats(player.clone()))
+            .unwrap_or(PlayerStats {
+                player: player.clone(),
+                games_played: 0,
+                wins: 0,
+                losses: 0,
+                draws: 0,
+                points_won: 0,
+                points_lost: 0,
+                rating: INITIAL_RATING,
+            })
+    }
+
+    /// Store a player's stats, indexing them for paging on first save.
+    ///
+    /// Every save keeps the player's index entries and the player count alive
+    /// as long as the stats themselves.
+    fn save_stats(env: &Env, stats: &PlayerStats) {
+        let key = DataKey::PlayerStats(stats.player.clone());
+        let index_key = DataKey::PlayerIndex(stats.player.clone());
+        let index: u32 = match env.storage().persistent().get(&index_key) {
+            Some(index) => index,
+            None => {
+                let count: u32 = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::PlayerCount)
+                    .unwrap_or(0);
+                env.storage().persistent().set(&index_key, &count);
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::PlayerAt(count), &stats.player);
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::PlayerCount, &(count + 1));
+                count
+            }
+        };
+
+        env.storage().persistent().set(&key, stats);
+        for key in [key, index_key, DataKey::PlayerAt(index), DataKey::PlayerCount] {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, SESSION_TTL_LEDGERS, SESSION_TTL_LEDGERS);
+        }
+    }
+
+    fn load_leaderboard(env: &Env) -> Vec<(Address, u32)> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Leaderboard)
+            .unwrap_or(Vec::new(env))
+    }
+
+    /// Move a player to their rating's position on the leaderboard.
+    ///
+    /// Only the rated player's position changes, so the rest of the board
+    /// stays sorted. Players at or below the cut-off drop off the end. The
+    /// board carries each rating, so no other player's stats are read.
+    fn rank(board: &mut Vec<(Address, u32)>, stats: &PlayerStats) {
+        if let Some(i) = board.iter().position(|(player, _)| player == stats.player) {
+            board.remove(i as u32);
+        }
+
+        // Insert after every player with an equal or higher rating
+        let pos = board
+            .iter()
+            .position(|(_, rating)| rating < stats.rating)
+            .map_or(board.len(), |i| i as u32);
+        if pos < LEADERBOARD_SIZE {
+            board.insert(pos, (stats.player.clone(), stats.rating));
+        }
+        while board.len() > LEADERBOARD_SIZE {
+            board.pop_back();
+        }
+    }
+
+    /// Treasury fee for a pot, rounded down
+    fn fee_for(env: &Env, pot: i128) -> i128 {
+        let fee_bps: u32 = env.storage().instance().get(&DataKey::FeeBps).unwrap_or(0);
+        pot * fee_bps as i128 / MAX_FEE_BPS as i128
+    }
+
+    // ========================================================================
+    // Admin Functions
+    // ========================================================================
+
+    /// Get the current admin address.
+    pub fn get_admin(env: Env) -> Address {
+        game_common::get_admin(&env)
+    }
+
+    /// Get the admin proposed by `propose_admin`, if it has not accepted yet.
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        game_common::get_pending_admin(&env)
+    }
+
+    /// Propose a new admin address. The current admin keeps control until the
+    /// new admin calls `accept_admin`, so a mistyped address cannot lock the
+    /// hub.
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        game_common::propose_admin(&env, &new_admin);
+    }
+
+    /// Accept a pending admin proposal (pending admin only).
+    pub fn accept_admin(env: Env) {
+        game_common::accept_admin(&env);
+    }
+
+    /// Withdraw a pending admin proposal.
+    pub fn cancel_admin_proposal(env: Env) {
+        game_common::cancel_admin_proposal(&env);
+    }
+
+    /// Add a game contract to the allowlist (admin only).
+    ///
+    /// # Arguments
+    /// * `game_address` - Game contract allowed to open sessions
+    pub fn add_game(env: Env, game_address: Address) {
+        game_common::require_admin(&env);
+
+        let key = DataKey::RegisteredGame(game_address);
+        env.storage().persistent().set(&key, &true);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, SESSION_TTL_LEDGERS, SESSION_TTL_LEDGERS);
+    }
+
+    /// Remove a game contract from the allowlist (admin only).
+    ///
+    /// Sessions it already opened can still be ended by it.
+    ///
+    /// # Arguments
+    /// * `game_address` - Game contract to remove
+    pub fn remove_game(env: Env, game_address: Address) {
+        game_common::require_admin(&env);
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::RegisteredGame(game_address));
+    }
+
+    /// Set the treasury address and fee (admin only).
+    ///
+    /// # Arguments
+    /// * `treasury` - Address that receives the fee from each pot
+    /// * `fee_bps` - Fee in basis points (0 = no fee, max 10_000)
+    pub fn set_fee(env: Env, treasury: Address, fee_bps: u32) -> Result<(), Error> {
+        game_common::require_admin(&env);
+
+        if fee_bps > MAX_FEE_BPS {
+            return Err(Error::InvalidFee);
+        }
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        env.storage().instance().set(&DataKey::FeeBps, &fee_bps);
+        Ok(())
+    }
+
+    /// Upgrade the contract WASM.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        game_common::upgrade(&env, new_wasm_hash);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod test;
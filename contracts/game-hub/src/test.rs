@@ -0,0 +1,1098 @@
+#![cfg(test)]
+
+// Unit tests for the game hub escrow, plus an integration test that drives a
+// real game contract (dice-duel) through the hub's GameHub interface.
+
+use crate::{
+    elo, DataKey, Error, GameHub, GameHubClient, GameOutcome, SessionStatus, INITIAL_RATING,
+    K_FACTOR, SESSION_DEADLINE_LEDGERS, SESSION_TTL_LEDGERS,
+};
+use dice_duel::{DiceDuelContract, DiceDuelContractClient};
+use game_common::{AdminChanged, AdminProposed};
+use soroban_sdk::testutils::{
+    storage::Persistent as _, Address as _, AuthorizedFunction, Events as _, Ledger as _, MockAuth,
+    MockAuthInvoke,
+};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{Address, Env, Event as _, IntoVal, Symbol, Vec};
+use twenty_one::{TwentyOneContract, TwentyOneContractClient};
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+const STARTING_BALANCE: i128 = 1000_0000000;
+
+struct Setup {
+    env: Env,
+    hub: GameHubClient<'static>,
+    token: TokenClient<'static>,
+    treasury: Address,
+    game: Address,
+    player1: Address,
+    player2: Address,
+}
+
+fn setup_test(fee_bps: u32) -> Setup {
+    let env = Env::default();
+    // Player stake transfers are authorized as sub-invocations of the hub call
+    env.mock_all_auths_allowing_non_root_auth();
+
+    env.ledger().set(soroban_sdk::testutils::LedgerInfo {
+        timestamp: 1441065600,
+        protocol_version: 25,
+        sequence_number: 100,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_ttl: u32::MAX / 2,
+        min_persistent_entry_ttl: u32::MAX / 2,
+        max_entry_ttl: u32::MAX / 2,
+    });
+
+    // Deploy the stake token
+    let token_admin = Address::generate(&env);
+    let sac = env.register_stellar_asset_contract_v2(token_admin);
+    let token = TokenClient::new(&env, &sac.address());
+    let token_admin_client = StellarAssetClient::new(&env, &sac.address());
+
+    // Deploy the hub
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let hub_addr = env.register(GameHub, (&admin, &sac.address(), &treasury, fee_bps));
+    let hub = GameHubClient::new(&env, &hub_addr);
+
+    // Register a game contract on the allowlist
+    let game = Address::generate(&env);
+    hub.add_game(&game);
+
+    let player1 = Address::generate(&env);
+    let player2 = Address::generate(&env);
+    token_admin_client.mint(&player1, &STARTING_BALANCE);
+    token_admin_client.mint(&player2, &STARTING_BALANCE);
+
+    Setup {
+        env,
+        hub,
+        token,
+        treasury,
+        game,
+        player1,
+        player2,
+    }
+}
+
+/// Assert that a Result contains a specific hub error
+fn assert_hub_error<T, E>(
+    result: &Result<Result<T, E>, Result<Error, soroban_sdk::InvokeError>>,
+    expected_error: Error,
+) {
+    match result {
+        Err(Ok(actual_error)) => {
+            assert_eq!(
+                *actual_error, expected_error,
+                "Expected error {:?} (code {}), but got {:?} (code {})",
+                expected_error, expected_error as u32, actual_error, *actual_error as u32
+            );
+        }
+        Err(Err(_invoke_error)) => {
+            panic!(
+                "Expected contract error {:?} (code {}), but got invocation error",
+                expected_error, expected_error as u32
+            );
+        }
+        Ok(Err(_conv_error)) => {
+            panic!(
+                "Expected contract error {:?} (code {}), but got conversion error",
+                expected_error, expected_error as u32
+            );
+        }
+        Ok(Ok(_)) => {
+            panic!(
+                "Expected error {:?} (code {}), but operation succeeded",
+                expected_error, expected_error as u32
+            );
+        }
+    }
+}
+
+// ============================================================================
+// Escrow Tests
+// ============================================================================
+
+#[test]
+fn test_start_game_escrows_stakes() {
+    let s = setup_test(0);
+
+    s.hub.start_game(
+        &s.game,
+        &1,
+        &s.player1,
+        &s.player2,
+        &100_0000000,
+        &40_0000000,
+    );
+
+    assert_eq!(s.token.balance(&s.player1), STARTING_BALANCE - 100_0000000);
+    assert_eq!(s.token.balance(&s.player2), STARTING_BALANCE - 40_0000000);
+    assert_eq!(s.token.balance(&s.hub.address), 140_0000000);
+
+    let session = s.hub.get_session(&1);
+    assert_eq!(session.game_id, s.game);
+    assert_eq!(session.player1, s.player1);
+    assert_eq!(session.player2, s.player2);
+    assert_eq!(session.player1_points, 100_0000000);
+    assert_eq!(session.player2_points, 40_0000000);
+    assert_eq!(session.status, SessionStatus::Active);
+}
+
+#[test]
+fn test_end_game_pays_winner() {
+    let s = setup_test(0);
+
+    s.hub.start_game(
+        &s.game,
+        &1,
+        &s.player1,
+        &s.player2,
+        &100_0000000,
+        &100_0000000,
+    );
+    s.hub.end_game(&1, &GameOutcome::Player2);
+
+    assert_eq!(s.token.balance(&s.player1), STARTING_BALANCE - 100_0000000);
+    assert_eq!(s.token.balance(&s.player2), STARTING_BALANCE + 100_0000000);
+    assert_eq!(s.token.balance(&s.hub.address), 0);
+    assert_eq!(s.token.balance(&s.treasury), 0);
+}
+
+#[test]
+fn test_end_game_takes_treasury_fee() {
+    // 2.5% fee
+    let s = setup_test(250);
+
+    s.hub.start_game(
+        &s.game,
+        &1,
+        &s.player1,
+        &s.player2,
+        &100_0000000,
+        &100_0000000,
+    );
+    s.hub.end_game(&1, &GameOutcome::Player1);
+
+    let pot = 200_0000000;
+    let fee = 5_0000000;
+    assert_eq!(s.token.balance(&s.treasury), fee);
+    assert_eq!(
+        s.token.balance(&s.player1),
+        STARTING_BALANCE - 100_0000000 + pot - fee
+    );
+    assert_eq!(s.token.balance(&s.hub.address), 0);
+}
+
+#[test]
+fn test_draw_returns_stakes_less_fee() {
+    let s = setup_test(250);
+
+    s.hub.start_game(
+        &s.game,
+        &1,
+        &s.player1,
+        &s.player2,
+        &40_0000000,
+        &20_0000000,
+    );
+    s.hub.end_game(&1, &GameOutcome::Draw);
+
+    // Each player pays 2.5% of their own stake, not of the pot
+    assert_eq!(s.token.balance(&s.player1), STARTING_BALANCE - 1_0000000);
+    assert_eq!(s.token.balance(&s.player2), STARTING_BALANCE - 5000000);
+    assert_eq!(s.token.balance(&s.treasury), 1_5000000);
+    assert_eq!(s.token.balance(&s.hub.address), 0);
+
+    assert_eq!(
+        s.hub.get_session(&1).status,
+        SessionStatus::Ended(GameOutcome::Draw)
+    );
+}
+
+#[test]
+fn test_cancelled_session_refunds_stakes() {
+    let s = setup_test(250);
+
+    s.hub.start_game(
+        &s.game,
+        &1,
+        &s.player1,
+        &s.player2,
+        &40_0000000,
+        &20_0000000,
+    );
+    s.hub.end_game(&1, &GameOutcome::Cancelled);
+
+    // Full refund and no fee
+    assert_eq!(s.token.balance(&s.player1), STARTING_BALANCE);
+    assert_eq!(s.token.balance(&s.player2), STARTING_BALANCE);
+    assert_eq!(s.token.balance(&s.treasury), 0);
+    assert_eq!(s.token.balance(&s.hub.address), 0);
+    assert_eq!(
+        s.hub.get_session(&1).status,
+        SessionStatus::Ended(GameOutcome::Cancelled)
+    );
+}
+
+#[test]
+fn test_zero_stakes() {
+    let s = setup_test(250);
+
+    s.hub
+        .start_game(&s.game, &1, &s.player1, &s.player2, &0, &0);
+    s.hub.end_game(&1, &GameOutcome::Player1);
+
+    assert_eq!(s.token.balance(&s.player1), STARTING_BALANCE);
+    assert_eq!(s.token.balance(&s.player2), STARTING_BALANCE);
+    assert_eq!(s.token.balance(&s.treasury), 0);
+}
+
+#[test]
+fn test_insufficient_balance_refused() {
+    let s = setup_test(0);
+
+    let result = s.hub.try_start_game(
+        &s.game,
+        &1,
+        &s.player1,
+        &s.player2,
+        &100_0000000,
+        &(STARTING_BALANCE + 1),
+    );
+    assert_hub_error(&result, Error::InsufficientBalance);
+
+    // Nothing moved and no session was opened
+    assert_eq!(s.token.balance(&s.player1), STARTING_BALANCE);
+    assert_eq!(s.token.balance(&s.player2), STARTING_BALANCE);
+    assert_hub_error(&s.hub.try_get_session(&1), Error::SessionNotFound);
+}
+
+#[test]
+fn test_negative_points_refused() {
+    let s = setup_test(0);
+
+    let result = s
+        .hub
+        .try_start_game(&s.game, &1, &s.player1, &s.player2, &-1, &100);
+    assert_hub_error(&result, Error::InvalidPoints);
+}
+
+#[test]
+fn test_duplicate_open_session_refused() {
+    let s = setup_test(0);
+
+    s.hub.start_game(
+        &s.game,
+        &1,
+        &s.player1,
+        &s.player2,
+        &10_0000000,
+        &10_0000000,
+    );
+    let result = s.hub.try_start_game(
+        &s.game,
+        &1,
+        &s.player1,
+        &s.player2,
+        &10_0000000,
+        &10_0000000,
+    );
+    assert_hub_error(&result, Error::SessionAlreadyExists);
+}
+
+#[test]
+fn test_end_unknown_session() {
+    let s = setup_test(0);
+
+    let result = s.hub.try_end_game(&999, &GameOutcome::Player1);
+    assert_hub_error(&result, Error::SessionNotFound);
+}
+
+// ============================================================================
+// Authorization Tests
+// ============================================================================
+
+#[test]
+fn test_start_game_requires_game_auth() {
+    let s = setup_test(0);
+
+    s.hub
+        .start_game(&s.game, &1, &s.player1, &s.player2, &0, &0);
+
+    let auths = s.env.auths();
+    assert!(auths.iter().any(|(addr, invocation)| {
+        *addr == s.game
+            && invocation.function
+                == AuthorizedFunction::Contract((
+                    s.hub.address.clone(),
+                    Symbol::new(&s.env, "start_game"),
+                    (&s.game, 1u32, &s.player1, &s.player2, 0i128, 0i128).into_val(&s.env),
+                ))
+    }));
+}
+
+#[test]
+fn test_unregistered_game_refused() {
+    let s = setup_test(0);
+    let rogue = Address::generate(&s.env);
+
+    let result = s
+        .hub
+        .try_start_game(&rogue, &1, &s.player1, &s.player2, &10_0000000, &10_0000000);
+    assert_hub_error(&result, Error::GameNotRegistered);
+    assert_eq!(s.token.balance(&s.player1), STARTING_BALANCE);
+}
+
+#[test]
+fn test_removed_game_refused() {
+    let s = setup_test(0);
+    assert!(s.hub.is_game(&s.game));
+
+    s.hub.remove_game(&s.game);
+    assert!(!s.hub.is_game(&s.game));
+
+    let result = s
+        .hub
+        .try_start_game(&s.game, &1, &s.player1, &s.player2, &0, &0);
+    assert_hub_error(&result, Error::GameNotRegistered);
+}
+
+#[test]
+fn test_start_game_extends_registration_ttl() {
+    let s = setup_test(0);
+    s.env.ledger().with_mut(|l| l.min_persistent_entry_ttl = 10);
+    let game = Address::generate(&s.env);
+    s.hub.add_game(&game);
+
+    let ttl = || {
+        s.env.as_contract(&s.hub.address, || {
+            s.env
+                .storage()
+                .persistent()
+                .get_ttl(&DataKey::RegisteredGame(game.clone()))
+        })
+    };
+    assert_eq!(ttl(), SESSION_TTL_LEDGERS);
+
+    // A game in regular use never drops off the allowlist
+    s.env
+        .ledger()
+        .with_mut(|l| l.sequence_number += SESSION_TTL_LEDGERS - 1);
+    assert_eq!(ttl(), 1);
+    s.hub.start_game(&game, &1, &s.player1, &s.player2, &0, &0);
+    assert_eq!(ttl(), SESSION_TTL_LEDGERS);
+}
+
+#[test]
+fn test_end_game_only_from_opening_game() {
+    let s = setup_test(0);
+    let other_game = Address::generate(&s.env);
+    s.hub.add_game(&other_game);
+
+    s.hub.start_game(
+        &s.game,
+        &1,
+        &s.player1,
+        &s.player2,
+        &10_0000000,
+        &10_0000000,
+    );
+
+    // Another registered game cannot end a session it did not open
+    let result = s
+        .hub
+        .mock_auths(&[MockAuth {
+            address: &other_game,
+            invoke: &MockAuthInvoke {
+                contract: &s.hub.address,
+                fn_name: "end_game",
+                args: (1u32, GameOutcome::Player1).into_val(&s.env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_end_game(&1, &GameOutcome::Player1);
+    assert!(result.is_err());
+    assert_eq!(s.hub.get_session(&1).status, SessionStatus::Active);
+
+    // The opening game can
+    s.hub
+        .mock_auths(&[MockAuth {
+            address: &s.game,
+            invoke: &MockAuthInvoke {
+                contract: &s.hub.address,
+                fn_name: "end_game",
+                args: (1u32, GameOutcome::Player1).into_val(&s.env),
+                sub_invokes: &[],
+            },
+        }])
+        .end_game(&1, &GameOutcome::Player1);
+    assert_eq!(
+        s.hub.get_session(&1).status,
+        SessionStatus::Ended(GameOutcome::Player1)
+    );
+}
+
+#[test]
+fn test_end_game_twice_refused() {
+    let s = setup_test(0);
+
+    s.hub.start_game(
+        &s.game,
+        &1,
+        &s.player1,
+        &s.player2,
+        &10_0000000,
+        &10_0000000,
+    );
+    s.hub.end_game(&1, &GameOutcome::Player1);
+
+    let result = s.hub.try_end_game(&1, &GameOutcome::Player2);
+    assert_hub_error(&result, Error::SessionAlreadyEnded);
+
+    // The pot was paid exactly once
+    assert_eq!(s.token.balance(&s.player1), STARTING_BALANCE + 10_0000000);
+    assert_eq!(s.token.balance(&s.player2), STARTING_BALANCE - 10_0000000);
+}
+
+#[test]
+fn test_ended_session_cannot_be_restarted() {
+    let s = setup_test(0);
+    s.hub.start_game(
+        &s.game,
+        &1,
+        &s.player1,
+        &s.player2,
+        &10_0000000,
+        &10_0000000,
+    );
+    s.hub.end_game(&1, &GameOutcome::Player1);
+
+    // Reusing the ID after the game ended would otherwise reopen the escrow
+    let result = s
+        .hub
+        .try_start_game(&s.game, &1, &s.player1, &s.player2, &5_0000000, &5_0000000);
+    assert_hub_error(&result, Error::SessionAlreadyExists);
+
+    let session = s.hub.get_session(&1);
+    assert_eq!(session.player1_points, 10_0000000);
+    assert_eq!(session.status, SessionStatus::Ended(GameOutcome::Player1));
+}
+
+// ============================================================================
+// Cancellation Tests
+// ============================================================================
+
+#[test]
+fn test_game_can_cancel_session() {
+    let s = setup_test(250);
+
+    s.hub.start_game(
+        &s.game,
+        &1,
+        &s.player1,
+        &s.player2,
+        &40_0000000,
+        &20_0000000,
+    );
+    s.hub.cancel_session(&1);
+
+    // Both stakes come back in full and nobody's record changes
+    assert_eq!(s.token.balance(&s.player1), STARTING_BALANCE);
+    assert_eq!(s.token.balance(&s.player2), STARTING_BALANCE);
+    assert_eq!(s.token.balance(&s.treasury), 0);
+    assert_eq!(s.token.balance(&s.hub.address), 0);
+    assert_eq!(
+        s.hub.get_session(&1).status,
+        SessionStatus::Ended(GameOutcome::Cancelled)
+    );
+    assert_eq!(s.hub.get_stats(&s.player1).games_played, 0);
+
+    // The session cannot be ended or cancelled again
+    let result = s.hub.try_end_game(&1, &GameOutcome::Player1);
+    assert_hub_error(&result, Error::SessionAlreadyEnded);
+    let result = s.hub.try_cancel_session(&1);
+    assert_hub_error(&result, Error::SessionAlreadyEnded);
+}
+
+#[test]
+fn test_cancel_before_deadline_requires_game_auth() {
+    let s = setup_test(0);
+
+    s.hub.start_game(
+        &s.game,
+        &1,
+        &s.player1,
+        &s.player2,
+        &10_0000000,
+        &10_0000000,
+    );
+    assert_eq!(
+        s.hub.get_session(&1).deadline,
+        100 + SESSION_DEADLINE_LEDGERS
+    );
+
+    // A player cannot walk away with the escrow while the game is live
+    let result = s
+        .hub
+        .mock_auths(&[MockAuth {
+            address: &s.player1,
+            invoke: &MockAuthInvoke {
+                contract: &s.hub.address,
+                fn_name: "cancel_session",
+                args: (1u32,).into_val(&s.env),
+                sub_invokes: &[],
+            },
+        }])
+        .try_cancel_session(&1);
+    assert!(result.is_err());
+
+    // Still refused on the deadline ledger itself
+    s.env
+        .ledger()
+        .set_sequence_number(100 + SESSION_DEADLINE_LEDGERS);
+    let result = s.hub.set_auths(&[]).try_cancel_session(&1);
+    assert!(result.is_err());
+    assert_eq!(s.hub.get_session(&1).status, SessionStatus::Active);
+    assert_eq!(s.token.balance(&s.hub.address), 20_0000000);
+}
+
+#[test]
+fn test_anyone_can_cancel_after_deadline() {
+    let s = setup_test(0);
+
+    s.hub.start_game(
+        &s.game,
+        &1,
+        &s.player1,
+        &s.player2,
+        &10_0000000,
+        &30_0000000,
+    );
+
+    // The game expired without a result
+    s.env
+        .ledger()
+        .set_sequence_number(100 + SESSION_DEADLINE_LEDGERS + 1);
+    s.hub.set_auths(&[]).cancel_session(&1);
+
+    assert_eq!(s.token.balance(&s.player1), STARTING_BALANCE);
+    assert_eq!(s.token.balance(&s.player2), STARTING_BALANCE);
+    assert_eq!(s.token.balance(&s.hub.address), 0);
+    assert_eq!(
+        s.hub.get_session(&1).status,
+        SessionStatus::Ended(GameOutcome::Cancelled)
+    );
+}
+
+#[test]
+fn test_cancel_unknown_session() {
+    let s = setup_test(0);
+
+    let result = s.hub.try_cancel_session(&999);
+    assert_hub_error(&result, Error::SessionNotFound);
+}
+
+// ============================================================================
+// Player Stats & Rating Tests
+// ============================================================================
+
+/// Play an unstaked session between two players and report the result
+fn play(s: &Setup, session_id: u32, player1: &Address, player2: &Address, outcome: GameOutcome) {
+    s.hub
+        .start_game(&s.game, &session_id, player1, player2, &0, &0);
+    s.hub.end_game(&session_id, &outcome);
+}
+
+#[test]
+fn test_new_player_has_initial_rating() {
+    let s = setup_test(0);
+
+    let stats = s.hub.get_stats(&s.player1);
+    assert_eq!(stats.player, s.player1);
+    assert_eq!(stats.games_played, 0);
+    assert_eq!(stats.rating, INITIAL_RATING);
+    assert_eq!(s.hub.get_player_stats(&0, &10).len(), 0);
+    assert_eq!(s.hub.get_top_players(&0, &10).len(), 0);
+}
+
+#[test]
+fn test_end_game_records_stats() {
+    let s = setup_test(250);
+
+    s.hub.start_game(
+        &s.game,
+        &1,
+        &s.player1,
+        &s.player2,
+        &30_0000000,
+        &10_0000000,
+    );
+    s.hub.end_game(&1, &GameOutcome::Player2);
+
+    // Points are the stakes themselves; the treasury fee is not deducted
+    let winner = s.hub.get_stats(&s.player2);
+    assert_eq!(winner.games_played, 1);
+    assert_eq!(winner.wins, 1);
+    assert_eq!(winner.losses, 0);
+    assert_eq!(winner.points_won, 30_0000000);
+    assert_eq!(winner.points_lost, 0);
+
+    let loser = s.hub.get_stats(&s.player1);
+    assert_eq!(loser.games_played, 1);
+    assert_eq!(loser.wins, 0);
+    assert_eq!(loser.losses, 1);
+    assert_eq!(loser.points_won, 0);
+    assert_eq!(loser.points_lost, 30_0000000);
+}
+
+#[test]
+fn test_rating_between_equal_players() {
+    let s = setup_test(0);
+
+    play(&s, 1, &s.player1, &s.player2, GameOutcome::Player1);
+
+    // Expected score is 0.5, so the winner takes K/2 from the loser
+    assert_eq!(
+        s.hub.get_stats(&s.player1).rating,
+        INITIAL_RATING + K_FACTOR / 2
+    );
+    assert_eq!(
+        s.hub.get_stats(&s.player2).rating,
+        INITIAL_RATING - K_FACTOR / 2
+    );
+}
+
+#[test]
+fn test_rating_upset_moves_more_points() {
+    let s = setup_test(0);
+
+    play(&s, 1, &s.player1, &s.player2, GameOutcome::Player1);
+    play(&s, 2, &s.player1, &s.player2, GameOutcome::Player1);
+    let favourite = s.hub.get_stats(&s.player1).rating;
+    let underdog = s.hub.get_stats(&s.player2).rating;
+    assert!(favourite > underdog);
+
+    // The second win against a weaker player was worth less than the first
+    assert!(favourite - (INITIAL_RATING + K_FACTOR / 2) < K_FACTOR / 2);
+
+    // The underdog winning gains more than an even game would give
+    play(&s, 3, &s.player1, &s.player2, GameOutcome::Player2);
+    let gained = s.hub.get_stats(&s.player2).rating - underdog;
+    let lost = favourite - s.hub.get_stats(&s.player1).rating;
+    assert!(gained > K_FACTOR / 2);
+    assert_eq!(gained, lost);
+}
+
+#[test]
+fn test_elo_expected_score() {
+    assert_eq!(elo::expected_score_bps(1200, 1200), 5000);
+    assert_eq!(elo::expected_score_bps(1600, 1200), 9091);
+    assert_eq!(elo::expected_score_bps(1200, 1600), 909);
+    // Interpolated between the 0 and 50 point entries
+    assert_eq!(elo::expected_score_bps(1225, 1200), 5357);
+    // Differences beyond 800 are clamped
+    assert_eq!(
+        elo::expected_score_bps(3000, 1000),
+        elo::expected_score_bps(1800, 1000)
+    );
+
+    assert_eq!(elo::rate_win(1200, 1200), (1216, 1184));
+    assert_eq!(elo::rate_win(1600, 1200), (1603, 1197));
+    assert_eq!(elo::rate_win(1200, 1600), (1229, 1571));
+    // The loser's rating never goes below zero
+    assert_eq!(elo::rate_win(10, 5), (26, 0));
+}
+
+#[test]
+fn test_elo_draw() {
+    // A draw between equals changes nothing
+    assert_eq!(elo::rate_draw(1200, 1200), (1200, 1200));
+    // The underdog gains what the favourite loses, whichever side it is on
+    assert_eq!(elo::rate_draw(1600, 1200), (1587, 1213));
+    assert_eq!(elo::rate_draw(1200, 1600), (1213, 1587));
+}
+
+#[test]
+fn test_draw_records_stats() {
+    let s = setup_test(0);
+
+    play(&s, 1, &s.player1, &s.player2, GameOutcome::Player1);
+    let favourite = s.hub.get_stats(&s.player1).rating;
+    let underdog = s.hub.get_stats(&s.player2).rating;
+
+    play(&s, 2, &s.player1, &s.player2, GameOutcome::Draw);
+
+    let player1 = s.hub.get_stats(&s.player1);
+    let player2 = s.hub.get_stats(&s.player2);
+    assert_eq!(player1.games_played, 2);
+    assert_eq!(player1.draws, 1);
+    assert_eq!(player2.draws, 1);
+    assert_eq!(player2.wins + player2.losses, 1);
+    assert!(player1.rating < favourite);
+    assert_eq!(favourite - player1.rating, player2.rating - underdog);
+}
+
+#[test]
+fn test_cancelled_session_leaves_stats_untouched() {
+    let s = setup_test(0);
+
+    play(&s, 1, &s.player1, &s.player2, GameOutcome::Cancelled);
+
+    assert_eq!(s.hub.get_stats(&s.player1).games_played, 0);
+    assert_eq!(s.hub.get_stats(&s.player1).rating, INITIAL_RATING);
+    assert_eq!(s.hub.get_player_stats(&0, &10).len(), 0);
+    assert_eq!(s.hub.get_top_players(&0, &10).len(), 0);
+}
+
+#[test]
+fn test_get_player_stats_paged() {
+    let s = setup_test(0);
+    let player3 = Address::generate(&s.env);
+
+    play(&s, 1, &s.player1, &s.player2, GameOutcome::Player1);
+    play(&s, 2, &player3, &s.player1, GameOutcome::Player1);
+
+    // Players are listed in the order they first finished a game
+    let page = s.hub.get_player_stats(&0, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get_unchecked(0).player, s.player1);
+    assert_eq!(page.get_unchecked(0).games_played, 2);
+    assert_eq!(page.get_unchecked(1).player, s.player2);
+
+    let page = s.hub.get_player_stats(&2, &2);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get_unchecked(0).player, player3);
+
+    assert_eq!(s.hub.get_player_stats(&3, &2).len(), 0);
+    assert_eq!(s.hub.get_player_stats(&0, &u32::MAX).len(), 3);
+}
+
+#[test]
+fn test_stats_writes_keep_player_index_alive() {
+    let s = setup_test(0);
+    s.env.ledger().with_mut(|l| l.min_persistent_entry_ttl = 10);
+    let player3 = Address::generate(&s.env);
+
+    play(&s, 1, &s.player1, &s.player2, GameOutcome::Player1);
+    let ttl = |key: DataKey| {
+        s.env
+            .as_contract(&s.hub.address, || s.env.storage().persistent().get_ttl(&key))
+    };
+
+    // Player1 keeps playing; player2's stats and index entry expire
+    s.env
+        .ledger()
+        .with_mut(|l| l.sequence_number += SESSION_TTL_LEDGERS - 1);
+    assert_eq!(ttl(DataKey::PlayerAt(0)), 1);
+    assert_eq!(ttl(DataKey::PlayerCount), 1);
+    play(&s, 2, &s.player1, &player3, GameOutcome::Player2);
+    for key in [
+        DataKey::PlayerStats(s.player1.clone()),
+        DataKey::PlayerIndex(s.player1.clone()),
+        DataKey::PlayerAt(0),
+        DataKey::PlayerCount,
+    ] {
+        assert_eq!(ttl(key), SESSION_TTL_LEDGERS);
+    }
+    assert_eq!(ttl(DataKey::PlayerAt(1)), 1);
+
+    // Replaying keeps each player at their original index
+    play(&s, 3, &player3, &s.player1, GameOutcome::Player1);
+    let page = s.hub.get_player_stats(&0, &10);
+    assert_eq!(page.len(), 3);
+    assert_eq!(page.get_unchecked(0).player, s.player1);
+    assert_eq!(page.get_unchecked(0).games_played, 3);
+    assert_eq!(page.get_unchecked(2).player, player3);
+    assert_eq!(page.get_unchecked(2).games_played, 2);
+}
+
+#[test]
+fn test_missing_player_index_entries_are_skipped() {
+    let s = setup_test(0);
+    let player3 = Address::generate(&s.env);
+    play(&s, 1, &s.player1, &s.player2, GameOutcome::Player1);
+    play(&s, 2, &player3, &s.player1, GameOutcome::Player1);
+
+    // An index entry that is gone is left out instead of failing the page
+    s.env.as_contract(&s.hub.address, || {
+        s.env.storage().persistent().remove(&DataKey::PlayerAt(1))
+    });
+    let page = s.hub.get_player_stats(&0, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get_unchecked(0).player, s.player1);
+    assert_eq!(page.get_unchecked(1).player, player3);
+}
+
+#[test]
+fn test_get_top_players_sorted_by_rating() {
+    let s = setup_test(0);
+    let player3 = Address::generate(&s.env);
+
+    play(&s, 1, &player3, &s.player1, GameOutcome::Player1);
+    play(&s, 2, &player3, &s.player2, GameOutcome::Player1);
+    play(&s, 3, &s.player2, &s.player1, GameOutcome::Player1);
+
+    let top = s.hub.get_top_players(&0, &10);
+    assert_eq!(top.len(), 3);
+    assert_eq!(top.get_unchecked(0).player, player3);
+    assert_eq!(top.get_unchecked(1).player, s.player2);
+    assert_eq!(top.get_unchecked(2).player, s.player1);
+    assert!(top.get_unchecked(0).rating > top.get_unchecked(1).rating);
+    assert!(top.get_unchecked(1).rating > top.get_unchecked(2).rating);
+
+    let page = s.hub.get_top_players(&1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get_unchecked(0).player, s.player2);
+
+    // Beating the leader moves player2 to the top without duplicating anyone
+    play(&s, 4, &s.player2, &player3, GameOutcome::Player1);
+    let top = s.hub.get_top_players(&0, &10);
+    assert_eq!(top.len(), 3);
+    assert_eq!(top.get_unchecked(0).player, s.player2);
+    assert_eq!(top.get_unchecked(1).player, player3);
+    assert_eq!(top.get_unchecked(2).player, s.player1);
+
+    // The board keeps each player's current rating alongside them
+    let board: Vec<(Address, u32)> = s.env.as_contract(&s.hub.address, || {
+        s.env.storage().persistent().get(&DataKey::Leaderboard).unwrap()
+    });
+    for (i, stats) in top.iter().enumerate() {
+        assert_eq!(board.get_unchecked(i as u32), (stats.player, stats.rating));
+    }
+}
+
+// ============================================================================
+// Admin Function Tests
+// ============================================================================
+
+#[test]
+fn test_set_fee() {
+    let s = setup_test(0);
+    let new_treasury = Address::generate(&s.env);
+
+    s.hub.set_fee(&new_treasury, &100);
+    assert_eq!(s.hub.get_treasury(), new_treasury);
+    assert_eq!(s.hub.get_fee_bps(), 100);
+
+    let result = s.hub.try_set_fee(&new_treasury, &10_001);
+    assert_hub_error(&result, Error::InvalidFee);
+}
+
+// ============================================================================
+// Integration Tests
+// ============================================================================
+
+#[test]
+fn test_dice_duel_through_hub() {
+    let s = setup_test(100);
+
+    let admin = Address::generate(&s.env);
+    let game_id = s.env.register(DiceDuelContract, (&admin, &s.hub.address));
+    let game = DiceDuelContractClient::new(&s.env, &game_id);
+    s.hub.add_game(&game_id);
+
+    let points = 50_0000000;
+    game.start_game(&7, &s.player1, &s.player2, &points, &points);
+    assert_eq!(s.hub.get_session(&7).game_id, game_id);
+    assert_eq!(s.token.balance(&s.hub.address), 2 * points);
+
+    game.roll(&7, &s.player1);
+    game.roll(&7, &s.player2);
+    game.reveal_winner(&7);
+    let SessionStatus::Ended(outcome) = s.hub.get_session(&7).status else {
+        panic!("session should have ended");
+    };
+
+    let fee = 1_0000000; // 1% of the 100 XLM pot
+    let (winner, loser) = match outcome {
+        GameOutcome::Player1 => (&s.player1, &s.player2),
+        GameOutcome::Player2 => (&s.player2, &s.player1),
+        _ => {
+            // A tie refunds each stake less 1%
+            assert!(game.get_game(&7).is_draw);
+            assert_eq!(s.token.balance(&s.player1), STARTING_BALANCE - fee / 2);
+            assert_eq!(s.token.balance(&s.player2), STARTING_BALANCE - fee / 2);
+            assert_eq!(s.token.balance(&s.hub.address), 0);
+            return;
+        }
+    };
+    assert_eq!(game.get_game(&7).winner.as_ref(), Some(winner));
+    assert_eq!(s.token.balance(winner), STARTING_BALANCE + points - fee);
+    assert_eq!(s.token.balance(loser), STARTING_BALANCE - points);
+    assert_eq!(s.token.balance(&s.treasury), fee);
+    assert_eq!(s.token.balance(&s.hub.address), 0);
+}
+
+#[test]
+fn test_twenty_one_draw_refunds_through_hub() {
+    let s = setup_test(100);
+
+    let admin = Address::generate(&s.env);
+    let game_id = s.env.register(TwentyOneContract, (&admin, &s.hub.address));
+    let game = TwentyOneContractClient::new(&s.env, &game_id);
+    s.hub.add_game(&game_id);
+
+    // Deals are seeded by session and players; cancel until two hands tie
+    let points = 50_0000000;
+    let session_id = (1u32..100)
+        .find(|&session_id| {
+            game.start_game(&session_id, &s.player1, &s.player2, &points, &points);
+            game.stick(&session_id, &s.player1);
+            game.stick(&session_id, &s.player2);
+            let tie = game.get_hand_value(&session_id, &s.player1)
+                == game.get_hand_value(&session_id, &s.player2);
+            if !tie {
+                game.abort_game(&session_id, &s.player1);
+                game.abort_game(&session_id, &s.player2);
+            }
+            tie
+        })
+        .expect("expected at least one tied deal");
+
+    assert_eq!(game.reveal_winner(&session_id), GameOutcome::Draw);
+    assert!(game.get_game(&session_id).is_draw);
+    assert_eq!(
+        s.hub.get_session(&session_id).status,
+        SessionStatus::Ended(GameOutcome::Draw)
+    );
+
+    // Both stakes come back less 1% of the pot
+    let fee = 1_0000000;
+    assert_eq!(s.token.balance(&s.player1), STARTING_BALANCE - fee / 2);
+    assert_eq!(s.token.balance(&s.player2), STARTING_BALANCE - fee / 2);
+    assert_eq!(s.token.balance(&s.treasury), fee);
+    assert_eq!(s.token.balance(&s.hub.address), 0);
+    assert_eq!(s.hub.get_stats(&s.player1).draws, 1);
+    assert_eq!(s.hub.get_stats(&s.player2).draws, 1);
+}
+
+#[test]
+fn test_dice_duel_abort_refunds_through_hub() {
+    let s = setup_test(100);
+
+    let admin = Address::generate(&s.env);
+    let game_id = s.env.register(DiceDuelContract, (&admin, &s.hub.address));
+    let game = DiceDuelContractClient::new(&s.env, &game_id);
+    s.hub.add_game(&game_id);
+
+    game.start_game(&8, &s.player1, &s.player2, &50_0000000, &20_0000000);
+    game.roll(&8, &s.player1);
+    assert!(!game.abort_game(&8, &s.player1));
+    assert!(game.abort_game(&8, &s.player2));
+
+    assert_eq!(
+        s.hub.get_session(&8).status,
+        SessionStatus::Ended(GameOutcome::Cancelled)
+    );
+    assert_eq!(s.token.balance(&s.player1), STARTING_BALANCE);
+    assert_eq!(s.token.balance(&s.player2), STARTING_BALANCE);
+    assert_eq!(s.token.balance(&s.treasury), 0);
+    assert_eq!(s.token.balance(&s.hub.address), 0);
+}
+
+#[test]
+fn test_dice_duel_abort_after_hub_cancelled_session() {
+    let s = setup_test(100);
+
+    let admin = Address::generate(&s.env);
+    let game_id = s.env.register(DiceDuelContract, (&admin, &s.hub.address));
+    let game = DiceDuelContractClient::new(&s.env, &game_id);
+    s.hub.add_game(&game_id);
+
+    game.start_game(&8, &s.player1, &s.player2, &50_0000000, &20_0000000);
+
+    // The session expires and someone else refunds it at the hub
+    s.env
+        .ledger()
+        .set_sequence_number(100 + SESSION_DEADLINE_LEDGERS + 1);
+    s.hub.set_auths(&[]).cancel_session(&8);
+
+    // The players can still close the game, and nothing is refunded twice
+    assert!(!game.abort_game(&8, &s.player1));
+    assert!(game.abort_game(&8, &s.player2));
+    assert_eq!(game.reveal_winner(&8), dice_duel::GameOutcome::Cancelled);
+    assert_eq!(s.token.balance(&s.player1), STARTING_BALANCE);
+    assert_eq!(s.token.balance(&s.player2), STARTING_BALANCE);
+    assert_eq!(s.token.balance(&s.hub.address), 0);
+}
+
+#[test]
+fn test_paused_game_sessions_can_still_be_refunded() {
+    let s = setup_test(100);
+
+    let admin = Address::generate(&s.env);
+    let game_id = s.env.register(DiceDuelContract, (&admin, &s.hub.address));
+    let game = DiceDuelContractClient::new(&s.env, &game_id);
+    s.hub.add_game(&game_id);
+
+    game.start_game(&9, &s.player1, &s.player2, &50_0000000, &20_0000000);
+    game.start_game(&10, &s.player1, &s.player2, &50_0000000, &20_0000000);
+    game.pause();
+
+    // The game can no longer settle, but a mutual abort still refunds
+    assert!(game.try_reveal_winner(&9).is_err());
+    assert!(!game.abort_game(&9, &s.player1));
+    assert!(game.abort_game(&9, &s.player2));
+
+    // ...and so does the hub's own cancel once the deadline has passed
+    s.env
+        .ledger()
+        .set_sequence_number(100 + SESSION_DEADLINE_LEDGERS + 1);
+    s.hub.set_auths(&[]).cancel_session(&10);
+
+    assert_eq!(
+        s.hub.get_session(&10).status,
+        SessionStatus::Ended(GameOutcome::Cancelled)
+    );
+    assert_eq!(s.token.balance(&s.player1), STARTING_BALANCE);
+    assert_eq!(s.token.balance(&s.player2), STARTING_BALANCE);
+    assert_eq!(s.token.balance(&s.hub.address), 0);
+}
+
+#[test]
+fn test_two_step_admin_transfer() {
+    let s = setup_test(0);
+    let admin = s.hub.get_admin();
+    let new_admin = Address::generate(&s.env);
+
+    s.hub.propose_admin(&new_admin);
+    assert_eq!(s.env.auths()[0].0, admin);
+    assert_eq!(
+        s.env.events().all(),
+        [AdminProposed {
+            admin: admin.clone(),
+            pending_admin: new_admin.clone(),
+        }
+        .to_xdr(&s.env, &s.hub.address)]
+    );
+    assert_eq!(s.hub.get_admin(), admin);
+    assert_eq!(s.hub.get_pending_admin(), Some(new_admin.clone()));
+
+    s.hub.accept_admin();
+    assert_eq!(s.env.auths()[0].0, new_admin);
+    assert_eq!(
+        s.env.events().all(),
+        [AdminChanged {
+            previous_admin: admin,
+            new_admin: new_admin.clone(),
+        }
+        .to_xdr(&s.env, &s.hub.address)]
+    );
+    assert_eq!(s.hub.get_admin(), new_admin);
+    assert_eq!(s.hub.get_pending_admin(), None);
+
+    // A withdrawn proposal can no longer be accepted
+    let typo_admin = Address::generate(&s.env);
+    s.hub.propose_admin(&typo_admin);
+    s.hub.cancel_admin_proposal();
+    assert_eq!(s.env.auths()[0].0, new_admin);
+    assert!(s.hub.try_accept_admin().is_err());
+    assert_eq!(s.hub.get_admin(), new_admin);
+}
@@ -0,0 +1,584 @@
+#![no_std]
+
+//! # Matchmaking
+//!
+//! An on-chain queue for finding opponents. A player enqueues for one of the
+//! hub's registered game contracts with the range of stakes they are willing
+//! to play for and, optionally, how far from their own hub rating an opponent
+//! may be. `match_pair` pairs the two longest-waiting compatible entries for a
+//! game and starts their session on that game contract, with both players
+//! staking the highest amount both bands allow.
+//!
+//! The game contract's `start_game` still requires both players to authorize
+//! the session, so `match_pair` is submitted together with both players'
+//! signatures (e.g. by a relayer that simulates it to find the pairing).
+//!
+//! Games whose players commit to hidden state at the start of a session, like
+//! The Resistance, take each player's commitment when they enqueue. Entries
+//! with a commitment are only matched with each other, and `match_pair`
+//! forwards both commitments to the game's `start_game`.
+//!
+//! `match_pair` always picks the same pair from the same queue, so a player
+//! who is compatible with many others but never signs would hold up the
+//! queue until their entry expires. `match_players` lets the relayer pair any
+//! two compatible entries instead, e.g. the first pair whose players both
+//! signed.
+//!
+//! Entries expire `ENTRY_LIFETIME_LEDGERS` after they are queued. Expired
+//! entries are never matched, and `expire` removes them from the queue, along
+//! with players whose entry is gone (e.g. archived).
+
+use soroban_sdk::{
+    contract, contracterror, contractevent, contractimpl, contracttype, xdr::ToXdr,
+    Address, Bytes, BytesN, Env, Vec,
+};
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    GameNotRegistered = 1,
+    InvalidStakeBand = 2,
+    AlreadyQueued = 3,
+    NotQueued = 4,
+    QueueFull = 5,
+    NoMatch = 6,
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[contractevent]
+pub struct Enqueued {
+    pub player: Address,
+    pub game_id: Address,
+    pub min_stake: i128,
+    pub max_stake: i128,
+}
+
+#[contractevent]
+pub struct Dequeued {
+    pub player: Address,
+    pub game_id: Address,
+}
+
+#[contractevent]
+pub struct Matched {
+    pub session_id: u32,
+    pub game_id: Address,
+    pub player1: Address,
+    pub player2: Address,
+    pub stake: i128,
+}
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+/// A player waiting for an opponent
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueueEntry {
+    pub player: Address,
+    pub game_id: Address,
+    /// Smallest stake the player will play for
+    pub min_stake: i128,
+    /// Largest stake the player will play for
+    pub max_stake: i128,
+    /// Largest rating difference the player accepts (`None` = any opponent)
+    pub max_rating_gap: Option<u32>,
+    /// The player's hub rating when they enqueued
+    pub rating: u32,
+    /// Last ledger on which the entry can be matched
+    pub expires_at: u32,
+    /// Commitment forwarded to games whose `start_game` takes one
+    pub commitment: Option<BytesN<32>>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Queued players for a game contract, longest-waiting first
+    Queue(Address),
+    Entry(Address),
+    MatchCount,
+}
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Ledgers an entry stays matchable (~1 hour at ~5 seconds per ledger)
+pub const ENTRY_LIFETIME_LEDGERS: u32 = 720;
+
+/// Maximum number of players waiting on one game contract
+pub const MAX_QUEUE_SIZE: u32 = 50;
+
+/// TTL for queue storage (30 days in ledgers, ~5 seconds per ledger)
+const QUEUE_TTL_LEDGERS: u32 = 518_400;
+
+/// Instance TTL bump threshold (1 day in ledgers)
+const INSTANCE_TTL_THRESHOLD: u32 = 17_280;
+
+// ============================================================================
+// Contract Definition
+// ============================================================================
+
+#[contract]
+pub struct MatchmakingContract;
+
+#[contractimpl]
+impl MatchmakingContract {
+    /// Initialize the contract with GameHub address and admin
+    ///
+    /// # Arguments
+    /// * `admin` - Admin address (can upgrade contract)
+    /// * `game_hub` - Address of the GameHub contract
+    pub fn __constructor(env: Env, admin: Address, game_hub: Address) {
+        game_common::init(&env, &admin, &game_hub);
+    }
+
+    // ========================================================================
+    // Queue Functions
+    // ========================================================================
+
+    /// Join the queue for a game contract.
+    ///
+    /// A player can wait in one queue at a time. An expired entry is replaced.
+    ///
+    /// **Auth:** Requires authorization from `player`.
+    ///
+    /// # Arguments
+    /// * `player` - Player looking for an opponent
+    /// * `game_id` - Game contract registered on the hub
+    /// * `min_stake` - Smallest stake the player will play for
+    /// * `max_stake` - Largest stake the player will play for
+    /// * `max_rating_gap` - Largest hub rating difference accepted, if any
+    /// * `commitment` - The player's commitment, for games whose `start_game`
+    ///   takes one; `None` for every other game
+    pub fn enqueue(
+        env: Env,
+        player: Address,
+        game_id: Address,
+        min_stake: i128,
+        max_stake: i128,
+        max_rating_gap: Option<u32>,
+        commitment: Option<BytesN<32>>,
+    ) -> Result<(), Error> {
+        player.require_auth();
+
+        if min_stake < 0 || min_stake > max_stake {
+            return Err(Error::InvalidStakeBand);
+        }
+        let hub = game_common::hub_client(&env);
+        if !hub.is_game(&game_id) {
+            return Err(Error::GameNotRegistered);
+        }
+
+        if let Some(existing) = Self::load_entry(&env, &player) {
+            if existing.expires_at >= env.ledger().sequence() {
+                return Err(Error::AlreadyQueued);
+            }
+            Self::remove_entry(&env, &existing);
+        }
+
+        let mut queue = Self::load_queue(&env, &game_id);
+        // A player whose entry was lost may still hold a place in the queue
+        if let Some(index) = queue.first_index_of(&player) {
+            queue.remove(index);
+        }
+        if queue.len() >= MAX_QUEUE_SIZE {
+            return Err(Error::QueueFull);
+        }
+        queue.push_back(player.clone());
+        Self::save_queue(&env, &game_id, &queue);
+
+        let entry = QueueEntry {
+            player: player.clone(),
+            game_id: game_id.clone(),
+            min_stake,
+            max_stake,
+            max_rating_gap,
+            rating: hub.get_stats(&player).rating,
+            expires_at: env.ledger().sequence() + ENTRY_LIFETIME_LEDGERS,
+            commitment,
+        };
+        let key = DataKey::Entry(player.clone());
+        env.storage().persistent().set(&key, &entry);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, QUEUE_TTL_LEDGERS, QUEUE_TTL_LEDGERS);
+
+        Enqueued {
+            player,
+            game_id,
+            min_stake,
+            max_stake,
+        }
+        .publish(&env);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_TTL_THRESHOLD, QUEUE_TTL_LEDGERS);
+
+        Ok(())
+    }
+
+    /// Leave the queue.
+    ///
+    /// **Auth:** Requires authorization from `player`.
+    pub fn dequeue(env: Env, player: Address) -> Result<(), Error> {
+        player.require_auth();
+
+        let entry = Self::load_entry(&env, &player).ok_or(Error::NotQueued)?;
+        Self::remove_entry(&env, &entry);
+
+        Dequeued {
+            player,
+            game_id: entry.game_id,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Pair the two longest-waiting compatible players for a game and start
+    /// their session on it.
+    ///
+    /// Two entries are compatible when their stake bands overlap, each
+    /// player's rating is within the other's accepted gap and either both or
+    /// neither carry a commitment. Both stake the highest amount in the
+    /// overlap. Anyone may call this, but the game
+    /// contract requires both players to authorize the session.
+    ///
+    /// # Arguments
+    /// * `game_id` - Game contract whose queue to match from
+    ///
+    /// # Returns
+    /// * `u32` - The hub session id of the new game
+    pub fn match_pair(env: Env, game_id: Address) -> Result<u32, Error> {
+        let (mut queue, entries) = Self::load_entries(&env, &game_id);
+        let now = env.ledger().sequence();
+
+        for i in 0..entries.len() {
+            let first = entries.get_unchecked(i);
+            if first.expires_at < now {
+                continue;
+            }
+            for j in (i + 1)..entries.len() {
+                let second = entries.get_unchecked(j);
+                if second.expires_at < now {
+                    continue;
+                }
+                let Some(stake) = Self::matched_stake(&first, &second) else {
+                    continue;
+                };
+
+                queue.remove(j);
+                queue.remove(i);
+                return Ok(Self::start_session(&env, &game_id, &queue, first, second, stake));
+            }
+        }
+
+        Err(Error::NoMatch)
+    }
+
+    /// Pair two given queued players for a game and start their session on it.
+    ///
+    /// Like `match_pair`, but the caller chooses the pair, so players who do
+    /// not sign cannot hold up compatible players behind them. `player1` must
+    /// have waited longer. Anyone may call this, but the game contract
+    /// requires both players to authorize the session.
+    ///
+    /// # Arguments
+    /// * `game_id` - Game contract whose queue to match from
+    /// * `player1` - First queued player (plays as player 1)
+    /// * `player2` - Second queued player, queued after `player1`
+    ///
+    /// # Returns
+    /// * `u32` - The hub session id of the new game
+    pub fn match_players(
+        env: Env,
+        game_id: Address,
+        player1: Address,
+        player2: Address,
+    ) -> Result<u32, Error> {
+        let (mut queue, entries) = Self::load_entries(&env, &game_id);
+        let now = env.ledger().sequence();
+
+        let i = queue.first_index_of(&player1).ok_or(Error::NotQueued)?;
+        let j = queue.first_index_of(&player2).ok_or(Error::NotQueued)?;
+        let first = entries.get_unchecked(i);
+        let second = entries.get_unchecked(j);
+        if j <= i || first.expires_at < now || second.expires_at < now {
+            return Err(Error::NoMatch);
+        }
+        let stake = Self::matched_stake(&first, &second).ok_or(Error::NoMatch)?;
+
+        queue.remove(j);
+        queue.remove(i);
+        Ok(Self::start_session(&env, &game_id, &queue, first, second, stake))
+    }
+
+    /// Remove expired entries from a game's queue, and players whose entry
+    /// is gone. Anyone may call this.
+    ///
+    /// # Returns
+    /// * `u32` - Number of entries removed
+    pub fn expire(env: Env, game_id: Address) -> u32 {
+        let queue = Self::load_queue(&env, &game_id);
+        let (queued, entries) = Self::load_entries(&env, &game_id);
+        let now = env.ledger().sequence();
+
+        let mut live = Vec::new(&env);
+        for (player, entry) in queued.iter().zip(entries.iter()) {
+            if entry.expires_at < now {
+                env.storage().persistent().remove(&DataKey::Entry(player));
+            } else {
+                live.push_back(player);
+            }
+        }
+
+        let removed = queue.len() - live.len();
+        if removed > 0 {
+            Self::save_queue(&env, &game_id, &live);
+        }
+        removed
+    }
+
+    // ========================================================================
+    // Query Functions
+    // ========================================================================
+
+    /// Get a player's queue entry, if they are queued (including expired entries).
+    pub fn get_entry(env: Env, player: Address) -> Option<QueueEntry> {
+        Self::load_entry(&env, &player)
+    }
+
+    /// Get the players queued for a game, longest-waiting first.
+    pub fn get_queue(env: Env, game_id: Address) -> Vec<Address> {
+        Self::load_queue(&env, &game_id)
+    }
+
+    // ========================================================================
+    // Helpers
+    // ========================================================================
+
+    /// Stake two entries would play for, or `None` if they are incompatible
+    fn matched_stake(a: &QueueEntry, b: &QueueEntry) -> Option<i128> {
+        if a.commitment.is_some() != b.commitment.is_some() {
+            return None;
+        }
+
+        let low = a.min_stake.max(b.min_stake);
+        let high = a.max_stake.min(b.max_stake);
+        if low > high {
+            return None;
+        }
+
+        let gap = a.rating.abs_diff(b.rating);
+        if a.max_rating_gap.is_some_and(|max| gap > max)
+            || b.max_rating_gap.is_some_and(|max| gap > max)
+        {
+            return None;
+        }
+
+        Some(high)
+    }
+
+    /// Take a matched pair's entries out of storage, save the rest of the
+    /// queue and start the pair's session.
+    fn start_session(
+        env: &Env,
+        game_id: &Address,
+        queue: &Vec<Address>,
+        first: QueueEntry,
+        second: QueueEntry,
+        stake: i128,
+    ) -> u32 {
+        Self::save_queue(env, game_id, queue);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Entry(first.player.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Entry(second.player.clone()));
+
+        let session_id = Self::next_session_id(env);
+        game_common::start_game(
+            env,
+            game_id,
+            session_id,
+            &first.player,
+            &second.player,
+            stake,
+            &first.commitment.zip(second.commitment),
+        );
+
+        Matched {
+            session_id,
+            game_id: game_id.clone(),
+            player1: first.player,
+            player2: second.player,
+            stake,
+        }
+        .publish(env);
+
+        session_id
+    }
+
+    /// Derive a session id from this contract and a match counter, skipping
+    /// ids already taken on the hub.
+    fn next_session_id(env: &Env) -> u32 {
+        let hub = game_common::hub_client(env);
+        let mut count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::MatchCount)
+            .unwrap_or(0);
+
+        loop {
+            let mut data = Bytes::new(env);
+            data.append(&env.current_contract_address().to_xdr(env));
+            data.extend_from_array(&count.to_be_bytes());
+            let hash = env.crypto().keccak256(&data).to_array();
+            let session_id = u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]);
+
+            count += 1;
+            if hub.try_get_session(&session_id).is_err() {
+                env.storage().instance().set(&DataKey::MatchCount, &count);
+                return session_id;
+            }
+        }
+    }
+
+    fn load_entry(env: &Env, player: &Address) -> Option<QueueEntry> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Entry(player.clone()))
+    }
+
+    /// A game's queue and the entry of each player in it, in queue order.
+    /// Players whose entry is gone (e.g. archived) or now waits in another
+    /// queue are left out.
+    fn load_entries(env: &Env, game_id: &Address) -> (Vec<Address>, Vec<QueueEntry>) {
+        let mut queue = Vec::new(env);
+        let mut entries = Vec::new(env);
+        for player in Self::load_queue(env, game_id).iter() {
+            match Self::load_entry(env, &player) {
+                Some(entry) if entry.game_id == *game_id => {
+                    queue.push_back(player);
+                    entries.push_back(entry);
+                }
+                _ => {}
+            }
+        }
+        (queue, entries)
+    }
+
+    /// Delete an entry and take its player out of the game's queue
+    fn remove_entry(env: &Env, entry: &QueueEntry) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Entry(entry.player.clone()));
+
+        let mut queue = Self::load_queue(env, &entry.game_id);
+        if let Some(index) = queue.first_index_of(&entry.player) {
+            queue.remove(index);
+            Self::save_queue(env, &entry.game_id, &queue);
+        }
+    }
+
+    fn load_queue(env: &Env, game_id: &Address) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Queue(game_id.clone()))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn save_queue(env: &Env, game_id: &Address, queue: &Vec<Address>) {
+        let key = DataKey::Queue(game_id.clone());
+        env.storage().persistent().set(&key, queue);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, QUEUE_TTL_LEDGERS, QUEUE_TTL_LEDGERS);
+    }
+
+
+    // ========================================================================
+    // Admin Functions
+    // ========================================================================
+
+    /// Get the current admin address
+    ///
+    /// # Returns
+    /// * `Address` - The admin address
+    pub fn get_admin(env: Env) -> Address {
+        game_common::get_admin(&env)
+    }
+
+    /// Get the admin proposed by `propose_admin`, if it has not accepted yet
+    ///
+    /// # Returns
+    /// * `Option<Address>` - The pending admin address
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        game_common::get_pending_admin(&env)
+    }
+
+    /// Propose a new admin address
+    ///
+    /// The current admin keeps control until the new admin calls
+    /// `accept_admin`, so a mistyped address cannot lock the contract.
+    ///
+    /// # Arguments
+    /// * `new_admin` - The proposed admin address
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        game_common::propose_admin(&env, &new_admin);
+    }
+
+    /// Accept a pending admin proposal (must be authorized by the pending admin)
+    pub fn accept_admin(env: Env) {
+        game_common::accept_admin(&env);
+    }
+
+    /// Withdraw a pending admin proposal
+    pub fn cancel_admin_proposal(env: Env) {
+        game_common::cancel_admin_proposal(&env);
+    }
+
+    /// Get the current GameHub contract address
+    ///
+    /// # Returns
+    /// * `Address` - The GameHub contract address
+    pub fn get_hub(env: Env) -> Address {
+        game_common::get_hub(&env)
+    }
+
+    /// Set a new GameHub contract address
+    ///
+    /// # Arguments
+    /// * `new_hub` - The new GameHub contract address
+    pub fn set_hub(env: Env, new_hub: Address) {
+        game_common::set_hub(&env, &new_hub);
+    }
+
+    /// Update the contract WASM hash (upgrade contract)
+    ///
+    /// # Arguments
+    /// * `new_wasm_hash` - The hash of the new WASM binary
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        game_common::upgrade(&env, new_wasm_hash);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod test;
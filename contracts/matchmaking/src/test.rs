@@ -0,0 +1,479 @@
+#![cfg(test)]
+
+// Unit tests for the matchmaking queue, driven against the real game hub with
+// matched sessions started on dice-duel and, with commitments, the-resistance.
+
+use crate::{
+    DataKey, Error, MatchmakingContract, MatchmakingContractClient, ENTRY_LIFETIME_LEDGERS,
+    MAX_QUEUE_SIZE,
+};
+use dice_duel::{DiceDuelContract, DiceDuelContractClient};
+use game_common::{AdminChanged, HubChanged};
+use game_hub::{GameHub, GameHubClient, GameOutcome, INITIAL_RATING};
+use soroban_sdk::testutils::{Address as _, Events as _, Ledger as _};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{vec, Address, Bytes, BytesN, Env, Event as _};
+use the_resistance::{TheResistanceContract, TheResistanceContractClient};
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+const STARTING_BALANCE: i128 = 1000_0000000;
+
+struct Setup {
+    env: Env,
+    hub: GameHubClient<'static>,
+    token: TokenClient<'static>,
+    token_admin: StellarAssetClient<'static>,
+    dice: DiceDuelContractClient<'static>,
+    matchmaking: MatchmakingContractClient<'static>,
+}
+
+fn setup_test() -> Setup {
+    let env = Env::default();
+    // Players authorize their sessions and stakes as sub-invocations
+    env.mock_all_auths_allowing_non_root_auth();
+
+    env.ledger().set(soroban_sdk::testutils::LedgerInfo {
+        timestamp: 1441065600,
+        protocol_version: 25,
+        sequence_number: 100,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_ttl: u32::MAX / 2,
+        min_persistent_entry_ttl: u32::MAX / 2,
+        max_entry_ttl: u32::MAX / 2,
+    });
+
+    let token_admin = Address::generate(&env);
+    let sac = env.register_stellar_asset_contract_v2(token_admin);
+    let token = TokenClient::new(&env, &sac.address());
+    let token_admin = StellarAssetClient::new(&env, &sac.address());
+
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let hub_addr = env.register(GameHub, (&admin, &sac.address(), &treasury, 0u32));
+    let hub = GameHubClient::new(&env, &hub_addr);
+
+    let dice_addr = env.register(DiceDuelContract, (&admin, &hub_addr));
+    let dice = DiceDuelContractClient::new(&env, &dice_addr);
+    hub.add_game(&dice_addr);
+
+    let matchmaking_addr = env.register(MatchmakingContract, (&admin, &hub_addr));
+    let matchmaking = MatchmakingContractClient::new(&env, &matchmaking_addr);
+
+    Setup {
+        env,
+        hub,
+        token,
+        token_admin,
+        dice,
+        matchmaking,
+    }
+}
+
+fn new_player(s: &Setup) -> Address {
+    let player = Address::generate(&s.env);
+    s.token_admin.mint(&player, &STARTING_BALANCE);
+    player
+}
+
+/// Settle a zero-stake session on the hub so `winner` gains rating from `loser`
+fn rate(s: &Setup, session_id: u32, winner: &Address, loser: &Address) {
+    let game = Address::generate(&s.env);
+    s.hub.add_game(&game);
+    s.hub.start_game(&game, &session_id, winner, loser, &0, &0);
+    s.hub.end_game(&session_id, &GameOutcome::Player1);
+}
+
+/// Delete a player's entry while leaving them in the queue, as archival would
+fn lose_entry(s: &Setup, player: &Address) {
+    s.env.as_contract(&s.matchmaking.address, || {
+        s.env
+            .storage()
+            .persistent()
+            .remove(&DataKey::Entry(player.clone()));
+    });
+}
+
+/// Assert that a Result contains a specific matchmaking error
+fn assert_matchmaking_error<T, E>(
+    result: &Result<Result<T, E>, Result<Error, soroban_sdk::InvokeError>>,
+    expected_error: Error,
+) {
+    match result {
+        Err(Ok(actual_error)) => {
+            assert_eq!(
+                *actual_error, expected_error,
+                "Expected error {:?} (code {}), but got {:?} (code {})",
+                expected_error, expected_error as u32, actual_error, *actual_error as u32
+            );
+        }
+        Err(Err(_invoke_error)) => {
+            panic!(
+                "Expected contract error {:?} (code {}), but got invocation error",
+                expected_error, expected_error as u32
+            );
+        }
+        Ok(Err(_conv_error)) => {
+            panic!(
+                "Expected contract error {:?} (code {}), but got conversion error",
+                expected_error, expected_error as u32
+            );
+        }
+        Ok(Ok(_)) => {
+            panic!(
+                "Expected error {:?} (code {}), but operation succeeded",
+                expected_error, expected_error as u32
+            );
+        }
+    }
+}
+
+// ============================================================================
+// Queue Tests
+// ============================================================================
+
+#[test]
+fn test_enqueue_records_entry() {
+    let s = setup_test();
+    let player = new_player(&s);
+
+    s.matchmaking.enqueue(&player, &s.dice.address, &10, &50, &Some(100), &None);
+
+    let entry = s.matchmaking.get_entry(&player).unwrap();
+    assert_eq!(entry.game_id, s.dice.address);
+    assert_eq!(entry.min_stake, 10);
+    assert_eq!(entry.max_stake, 50);
+    assert_eq!(entry.max_rating_gap, Some(100));
+    assert_eq!(entry.rating, INITIAL_RATING);
+    assert_eq!(entry.expires_at, 100 + ENTRY_LIFETIME_LEDGERS);
+    assert_eq!(entry.commitment, None);
+    assert_eq!(
+        s.matchmaking.get_queue(&s.dice.address),
+        vec![&s.env, player]
+    );
+}
+
+#[test]
+fn test_enqueue_rejects_invalid_requests() {
+    let s = setup_test();
+    let player = new_player(&s);
+    let game = s.dice.address.clone();
+
+    let result = s.matchmaking.try_enqueue(&player, &game, &-1, &10, &None, &None);
+    assert_matchmaking_error(&result, Error::InvalidStakeBand);
+    let result = s.matchmaking.try_enqueue(&player, &game, &20, &10, &None, &None);
+    assert_matchmaking_error(&result, Error::InvalidStakeBand);
+
+    let unregistered = Address::generate(&s.env);
+    let result = s.matchmaking.try_enqueue(&player, &unregistered, &0, &10, &None, &None);
+    assert_matchmaking_error(&result, Error::GameNotRegistered);
+
+    s.matchmaking.enqueue(&player, &game, &0, &10, &None, &None);
+    let result = s.matchmaking.try_enqueue(&player, &game, &0, &10, &None, &None);
+    assert_matchmaking_error(&result, Error::AlreadyQueued);
+}
+
+#[test]
+fn test_dequeue() {
+    let s = setup_test();
+    let player = new_player(&s);
+    s.matchmaking.enqueue(&player, &s.dice.address, &0, &10, &None, &None);
+
+    s.matchmaking.dequeue(&player);
+
+    assert_eq!(s.matchmaking.get_entry(&player), None);
+    assert!(s.matchmaking.get_queue(&s.dice.address).is_empty());
+    let result = s.matchmaking.try_dequeue(&player);
+    assert_matchmaking_error(&result, Error::NotQueued);
+}
+
+#[test]
+fn test_queue_full() {
+    let s = setup_test();
+    for _ in 0..MAX_QUEUE_SIZE {
+        let player = Address::generate(&s.env);
+        s.matchmaking.enqueue(&player, &s.dice.address, &0, &10, &None, &None);
+    }
+
+    let player = Address::generate(&s.env);
+    let result = s.matchmaking.try_enqueue(&player, &s.dice.address, &0, &10, &None, &None);
+    assert_matchmaking_error(&result, Error::QueueFull);
+}
+
+// ============================================================================
+// Matching Tests
+// ============================================================================
+
+#[test]
+fn test_match_pair_starts_session() {
+    let s = setup_test();
+    let player1 = new_player(&s);
+    let player2 = new_player(&s);
+    s.matchmaking.enqueue(&player1, &s.dice.address, &10, &50, &None, &None);
+    s.matchmaking.enqueue(&player2, &s.dice.address, &30, &100, &None, &None);
+
+    let session_id = s.matchmaking.match_pair(&s.dice.address);
+
+    // Highest stake both bands allow, escrowed by the hub
+    let session = s.hub.get_session(&session_id);
+    assert_eq!(session.game_id, s.dice.address);
+    assert_eq!(session.player1, player1);
+    assert_eq!(session.player2, player2);
+    assert_eq!(session.player1_points, 50);
+    assert_eq!(session.player2_points, 50);
+    assert_eq!(s.token.balance(&player1), STARTING_BALANCE - 50);
+    assert_eq!(s.token.balance(&s.hub.address), 100);
+
+    // The game is live on dice-duel and both players left the queue
+    let game = s.dice.get_game(&session_id);
+    assert_eq!(game.player1, player1);
+    assert_eq!(game.player2, player2);
+    assert!(s.matchmaking.get_queue(&s.dice.address).is_empty());
+    assert_eq!(s.matchmaking.get_entry(&player1), None);
+    assert_eq!(s.matchmaking.get_entry(&player2), None);
+
+    // Queued again, the same pair gets a fresh session
+    s.matchmaking.enqueue(&player1, &s.dice.address, &0, &0, &None, &None);
+    s.matchmaking.enqueue(&player2, &s.dice.address, &0, &0, &None, &None);
+    let next_session = s.matchmaking.match_pair(&s.dice.address);
+    assert_ne!(next_session, session_id);
+}
+
+#[test]
+fn test_match_pair_skips_incompatible_stakes() {
+    let s = setup_test();
+    let player1 = new_player(&s);
+    let player2 = new_player(&s);
+    let player3 = new_player(&s);
+    s.matchmaking.enqueue(&player1, &s.dice.address, &10, &20, &None, &None);
+    s.matchmaking.enqueue(&player2, &s.dice.address, &30, &40, &None, &None);
+
+    let result = s.matchmaking.try_match_pair(&s.dice.address);
+    assert_matchmaking_error(&result, Error::NoMatch);
+
+    s.matchmaking.enqueue(&player3, &s.dice.address, &15, &35, &None, &None);
+    let session_id = s.matchmaking.match_pair(&s.dice.address);
+
+    let session = s.hub.get_session(&session_id);
+    assert_eq!(session.player1, player1);
+    assert_eq!(session.player2, player3);
+    assert_eq!(session.player1_points, 20);
+    assert_eq!(
+        s.matchmaking.get_queue(&s.dice.address),
+        vec![&s.env, player2]
+    );
+}
+
+#[test]
+fn test_match_pair_respects_rating_gap() {
+    let s = setup_test();
+    let strong = new_player(&s);
+    let weak = new_player(&s);
+    let newcomer = new_player(&s);
+    rate(&s, 1, &strong, &weak);
+    let gap = s.hub.get_stats(&strong).rating - s.hub.get_stats(&weak).rating;
+    let half_gap = s.hub.get_stats(&strong).rating - INITIAL_RATING;
+
+    // The strong player only accepts opponents close to their rating
+    s.matchmaking.enqueue(&strong, &s.dice.address, &0, &10, &Some(half_gap), &None);
+    s.matchmaking.enqueue(&weak, &s.dice.address, &0, &10, &None, &None);
+    let result = s.matchmaking.try_match_pair(&s.dice.address);
+    assert_matchmaking_error(&result, Error::NoMatch);
+    assert!(gap > half_gap);
+
+    // A newcomer sits within both gaps
+    s.matchmaking.enqueue(&newcomer, &s.dice.address, &0, &10, &Some(half_gap), &None);
+    let session_id = s.matchmaking.match_pair(&s.dice.address);
+
+    let session = s.hub.get_session(&session_id);
+    assert_eq!(session.player1, strong);
+    assert_eq!(session.player2, newcomer);
+}
+
+#[test]
+fn test_match_players_pairs_chosen_entries() {
+    let s = setup_test();
+    let wide = new_player(&s);
+    let player2 = new_player(&s);
+    let player3 = new_player(&s);
+    let outsider = new_player(&s);
+    s.matchmaking.enqueue(&wide, &s.dice.address, &0, &100, &None, &None);
+    s.matchmaking.enqueue(&player2, &s.dice.address, &0, &10, &None, &None);
+    s.matchmaking.enqueue(&player3, &s.dice.address, &5, &20, &None, &None);
+
+    // match_pair would always try the first player; pair the others instead
+    let result = s
+        .matchmaking
+        .try_match_players(&s.dice.address, &player2, &outsider);
+    assert_matchmaking_error(&result, Error::NotQueued);
+    let result = s
+        .matchmaking
+        .try_match_players(&s.dice.address, &player3, &player2);
+    assert_matchmaking_error(&result, Error::NoMatch);
+
+    let session_id = s
+        .matchmaking
+        .match_players(&s.dice.address, &player2, &player3);
+    let session = s.hub.get_session(&session_id);
+    assert_eq!(session.player1, player2);
+    assert_eq!(session.player2, player3);
+    assert_eq!(session.player1_points, 10);
+    assert_eq!(s.matchmaking.get_queue(&s.dice.address), vec![&s.env, wide]);
+}
+
+#[test]
+fn test_match_pair_forwards_commitments() {
+    let s = setup_test();
+    let admin = Address::generate(&s.env);
+    let dummy_vk = Bytes::from_array(&s.env, &[0u8; 64]);
+    let resistance_addr = s.env.register(TheResistanceContract, (&admin, &s.hub.address, dummy_vk));
+    let resistance = TheResistanceContractClient::new(&s.env, &resistance_addr);
+    s.hub.add_game(&resistance_addr);
+
+    // An entry without a commitment is never paired with one that has one
+    let uncommitted = new_player(&s);
+    let player1 = new_player(&s);
+    let player2 = new_player(&s);
+    let commitment1 = BytesN::from_array(&s.env, &[1u8; 32]);
+    let commitment2 = BytesN::from_array(&s.env, &[2u8; 32]);
+    s.matchmaking.enqueue(&uncommitted, &resistance_addr, &0, &10, &None, &None);
+    s.matchmaking.enqueue(&player1, &resistance_addr, &0, &10, &None, &Some(commitment1.clone()));
+    assert_matchmaking_error(&s.matchmaking.try_match_pair(&resistance_addr), Error::NoMatch);
+    s.matchmaking.enqueue(&player2, &resistance_addr, &0, &10, &None, &Some(commitment2.clone()));
+
+    let session_id = s.matchmaking.match_pair(&resistance_addr);
+
+    let game = resistance.get_game(&session_id);
+    assert_eq!(game.player1, player1);
+    assert_eq!(game.player2, player2);
+    assert_eq!(game.player1_commitment, commitment1);
+    assert_eq!(game.player2_commitment, commitment2);
+    assert_eq!(game.player1_points, 10);
+    assert_eq!(s.matchmaking.get_queue(&resistance_addr), vec![&s.env, uncommitted]);
+}
+
+#[test]
+fn test_missing_entries_are_skipped_and_dropped() {
+    let s = setup_test();
+    let lost = new_player(&s);
+    let player2 = new_player(&s);
+    let player3 = new_player(&s);
+    for player in [&lost, &player2, &player3] {
+        s.matchmaking.enqueue(player, &s.dice.address, &0, &10, &None, &None);
+    }
+
+    // The first entry is gone but the player is still queued
+    lose_entry(&s, &lost);
+
+    let session_id = s.matchmaking.match_pair(&s.dice.address);
+    let session = s.hub.get_session(&session_id);
+    assert_eq!(session.player1, player2);
+    assert_eq!(session.player2, player3);
+    assert!(s.matchmaking.get_queue(&s.dice.address).is_empty());
+
+    // Without a match, expire drops them
+    s.matchmaking.enqueue(&lost, &s.dice.address, &0, &10, &None, &None);
+    lose_entry(&s, &lost);
+    let result = s.matchmaking.try_match_pair(&s.dice.address);
+    assert_matchmaking_error(&result, Error::NoMatch);
+    assert_eq!(s.matchmaking.expire(&s.dice.address), 1);
+    assert!(s.matchmaking.get_queue(&s.dice.address).is_empty());
+
+    // Queuing again does not leave a second place behind
+    s.matchmaking.enqueue(&lost, &s.dice.address, &0, &10, &None, &None);
+    lose_entry(&s, &lost);
+    s.matchmaking.enqueue(&lost, &s.dice.address, &0, &10, &None, &None);
+    assert_eq!(s.matchmaking.get_queue(&s.dice.address), vec![&s.env, lost]);
+}
+
+// ============================================================================
+// Expiry Tests
+// ============================================================================
+
+#[test]
+fn test_expired_entries_are_not_matched() {
+    let s = setup_test();
+    let stale = new_player(&s);
+    let fresh = new_player(&s);
+    s.matchmaking.enqueue(&stale, &s.dice.address, &0, &10, &None, &None);
+
+    s.env
+        .ledger()
+        .set_sequence_number(100 + ENTRY_LIFETIME_LEDGERS + 1);
+    s.matchmaking.enqueue(&fresh, &s.dice.address, &0, &10, &None, &None);
+
+    let result = s.matchmaking.try_match_pair(&s.dice.address);
+    assert_matchmaking_error(&result, Error::NoMatch);
+
+    assert_eq!(s.matchmaking.expire(&s.dice.address), 1);
+    assert_eq!(s.matchmaking.get_entry(&stale), None);
+    assert_eq!(
+        s.matchmaking.get_queue(&s.dice.address),
+        vec![&s.env, fresh.clone()]
+    );
+    assert_eq!(s.matchmaking.expire(&s.dice.address), 0);
+
+    // The stale player can queue again and be matched
+    s.matchmaking.enqueue(&stale, &s.dice.address, &0, &10, &None, &None);
+    let session_id = s.matchmaking.match_pair(&s.dice.address);
+    assert_eq!(s.hub.get_session(&session_id).player1, fresh);
+}
+
+#[test]
+fn test_expired_entry_is_replaced_on_enqueue() {
+    let s = setup_test();
+    let player = new_player(&s);
+    s.matchmaking.enqueue(&player, &s.dice.address, &0, &10, &None, &None);
+
+    s.env
+        .ledger()
+        .set_sequence_number(100 + ENTRY_LIFETIME_LEDGERS + 1);
+    s.matchmaking.enqueue(&player, &s.dice.address, &5, &20, &None, &None);
+
+    let entry = s.matchmaking.get_entry(&player).unwrap();
+    assert_eq!(entry.min_stake, 5);
+    assert_eq!(
+        s.matchmaking.get_queue(&s.dice.address),
+        vec![&s.env, player]
+    );
+}
+
+#[test]
+fn test_two_step_admin_transfer_and_hub_change() {
+    let s = setup_test();
+    let admin = s.matchmaking.get_admin();
+    let new_admin = Address::generate(&s.env);
+
+    // The proposal only takes effect once the new admin accepts
+    s.matchmaking.propose_admin(&new_admin);
+    assert_eq!(s.matchmaking.get_admin(), admin);
+    s.matchmaking.accept_admin();
+    assert_eq!(s.env.auths()[0].0, new_admin);
+    assert_eq!(
+        s.env.events().all(),
+        [AdminChanged {
+            previous_admin: admin,
+            new_admin: new_admin.clone(),
+        }
+        .to_xdr(&s.env, &s.matchmaking.address)]
+    );
+    assert_eq!(s.matchmaking.get_admin(), new_admin);
+    assert_eq!(s.matchmaking.get_pending_admin(), None);
+
+    // Hub changes are authorized by the new admin and logged
+    let new_hub = Address::generate(&s.env);
+    s.matchmaking.set_hub(&new_hub);
+    assert_eq!(s.env.auths()[0].0, new_admin);
+    assert_eq!(
+        s.env.events().all(),
+        [HubChanged {
+            previous_hub: s.hub.address.clone(),
+            new_hub: new_hub.clone(),
+        }
+        .to_xdr(&s.env, &s.matchmaking.address)]
+    );
+    assert_eq!(s.matchmaking.get_hub(), new_hub);
+}
@@ -1,12 +1,15 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractevent, contractimpl, Address, Env};
+use soroban_sdk::{contract, contractevent, contractimpl, contracttype, Address, Env};
 
 /// Mock Game Hub contract for game studio development
 ///
 /// This contract provides the same external interface that games expect
-/// (start_game, end_game) but does nothing internally. It exists purely
+/// (start_game, end_game, cancel_session) but does nothing internally. It exists purely
 /// for game contracts to compile and integrate during development.
+///
+/// It performs no authorization. Use the `game-hub` contract for the
+/// game allowlist, per-session caller checks and stake escrow.
 #[contract]
 pub struct MockGameHub;
 
@@ -20,10 +23,20 @@ pub struct GameStarted {
     pub player2_points: i128,
 }
 
+/// How a session ended (same shape as the `game-hub` contract's `GameOutcome`)
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum GameOutcome {
+    Player1,
+    Player2,
+    Draw,
+    Cancelled,
+}
+
 #[contractevent]
 pub struct GameEnded {
     pub session_id: u32,
-    pub player1_won: bool,
+    pub outcome: GameOutcome,
 }
 
 #[contractimpl]
@@ -60,20 +73,33 @@ impl MockGameHub {
         env.storage().instance().extend_ttl(17_280, 518_400);
     }
 
-    /// End a game session and declare winner
+    /// End a game session and report the outcome
     ///
     /// # Arguments
     /// * `session_id` - The game session being ended
-    /// * `player1_won` - True if player1 won, false if player2 won
+    /// * `outcome` - Which player won, a draw, or a cancelled session
     pub fn end_game(
         env: Env,
         session_id: u32,
-        player1_won: bool,
+        outcome: GameOutcome,
     ) {
         // No auth required for mock
         GameEnded {
             session_id,
-            player1_won,
+            outcome,
+        }
+        .publish(&env);
+    }
+
+    /// Cancel a game session (refunds both stakes in the real hub)
+    ///
+    /// # Arguments
+    /// * `session_id` - The game session being cancelled
+    pub fn cancel_session(env: Env, session_id: u32) {
+        // No auth or deadline check for mock
+        GameEnded {
+            session_id,
+            outcome: GameOutcome::Cancelled,
         }
         .publish(&env);
     }
@@ -92,6 +118,6 @@ mod test {
         let player1 = Address::generate(&env);
         let player2 = Address::generate(&env);
         client.start_game(&game_id, &1, &player1, &player2, &1000, &1000);
-        client.end_game(&1, &true);
+        client.end_game(&1, &GameOutcome::Player1);
     }
 }
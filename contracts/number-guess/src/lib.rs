@@ -3,36 +3,19 @@
 //! # Number Guess Game
 //!
 //! A simple two-player guessing game where players guess a number between 1 and 10.
-//! The player whose guess is closest to the randomly generated number wins.
+//! The player whose guess is closest to the randomly generated number wins;
+//! guesses the same distance away are a draw.
 //!
 //! **Game Hub Integration:**
 //! This game is Game Hub-aware and enforces all games to be played through the
 //! Game Hub contract. Games cannot be started or completed without points involvement.
 
+use game_common::GameError;
 use soroban_sdk::{
-    Address, Bytes, BytesN, Env, IntoVal, contract, contractclient, contracterror, contractimpl, contracttype, vec
+    Address, Bytes, BytesN, Env, IntoVal, contract, contracterror, contractimpl, contracttype, vec
 };
 
-// Import GameHub contract interface
-// This allows us to call into the GameHub contract
-#[contractclient(name = "GameHubClient")]
-pub trait GameHub {
-    fn start_game(
-        env: Env,
-        game_id: Address,
-        session_id: u32,
-        player1: Address,
-        player2: Address,
-        player1_points: i128,
-        player2_points: i128,
-    );
-
-    fn end_game(
-        env: Env,
-        session_id: u32,
-        player1_won: bool
-    );
-}
+pub use game_common::{GameOutcome, TtlConfig};
 
 // ============================================================================
 // Errors
@@ -47,6 +30,22 @@ pub enum Error {
     AlreadyGuessed = 3,
     BothPlayersNotGuessed = 4,
     GameAlreadyEnded = 5,
+    SessionAlreadyExists = 6,
+    ContractPaused = 7,
+    UnsupportedStorageVersion = 8,
+    InvalidTtl = 9,
+}
+
+impl From<GameError> for Error {
+    fn from(error: GameError) -> Self {
+        match error {
+            GameError::GameNotFound => Error::GameNotFound,
+            GameError::SessionAlreadyExists => Error::SessionAlreadyExists,
+            GameError::ContractPaused => Error::ContractPaused,
+            GameError::UnsupportedStorageVersion => Error::UnsupportedStorageVersion,
+            GameError::InvalidTtl => Error::InvalidTtl,
+        }
+    }
 }
 
 // ============================================================================
@@ -64,25 +63,93 @@ pub struct Game {
     pub player2_guess: Option<u32>,
     pub winning_number: Option<u32>,
     pub winner: Option<Address>,
+    /// Set when both guesses were equally close (`winner` stays `None`)
+    pub is_draw: bool,
+    /// Set when each player agrees to abort; both together cancel the game
+    pub player1_abort: bool,
+    pub player2_abort: bool,
 }
 
+/// `Game` as stored before storage versioning, without the draw and abort
+/// flags. These records are converted when loaded.
 #[contracttype]
-#[derive(Clone)]
-pub enum DataKey {
-    Game(u32),
-    GameHubAddress,
-    Admin,
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyGame {
+    pub player1: Address,
+    pub player2: Address,
+    pub player1_points: i128,
+    pub player2_points: i128,
+    pub player1_guess: Option<u32>,
+    pub player2_guess: Option<u32>,
+    pub winning_number: Option<u32>,
+    pub winner: Option<Address>,
+}
+
+/// Storage schema version written by this release. Bump it and add a
+/// `VersionedGame` variant whenever the stored `Game` layout changes.
+pub const STORAGE_VERSION: u32 = 1;
+
+/// `Game` as stored. A schema change adds a variant for the new layout and
+/// converts older ones in `into_current`, so live sessions still decode after
+/// `upgrade`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VersionedGame {
+    V1(Game),
+}
+
+impl VersionedGame {
+    fn into_current(self) -> Game {
+        match self {
+            VersionedGame::V1(game) => game,
+        }
+    }
+}
+
+// Games saved before storage versioning are a bare `LegacyGame`
+impl From<LegacyGame> for VersionedGame {
+    fn from(game: LegacyGame) -> Self {
+        VersionedGame::V1(Game {
+            player1: game.player1,
+            player2: game.player2,
+            player1_points: game.player1_points,
+            player2_points: game.player2_points,
+            player1_guess: game.player1_guess,
+            player2_guess: game.player2_guess,
+            winning_number: game.winning_number,
+            winner: game.winner,
+            is_draw: false,
+            player1_abort: false,
+            player2_abort: false,
+        })
+    }
 }
 
 // ============================================================================
-// Storage TTL Management
+// Helper Functions
 // ============================================================================
-// TTL (Time To Live) ensures game data doesn't expire unexpectedly
-// Games are stored in temporary storage with a minimum 30-day retention
 
-/// TTL for game storage (30 days in ledgers, ~5 seconds per ledger)
-/// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
-const GAME_TTL_LEDGERS: u32 = 518_400;
+/// Load a game, whichever schema version it was saved with
+fn load_game(env: &Env, session_id: u32) -> Result<Game, Error> {
+    let stored = game_common::load_versioned_game::<VersionedGame, LegacyGame>(env, session_id)?;
+    Ok(stored.into_current())
+}
+
+/// Save a game in the current schema version
+fn save_game(env: &Env, session_id: u32, game: &Game) {
+    game_common::save_game(env, session_id, &VersionedGame::V1(game.clone()));
+}
+
+/// Outcome of a finished game, or `None` while it is still in progress
+fn outcome_of(game: &Game) -> Option<GameOutcome> {
+    match &game.winner {
+        Some(winner) if *winner == game.player1 => Some(GameOutcome::Player1),
+        Some(_) => Some(GameOutcome::Player2),
+        None if game.player1_abort && game.player2_abort => Some(GameOutcome::Cancelled),
+        None if game.is_draw => Some(GameOutcome::Draw),
+        None => None,
+    }
+}
 
 // ============================================================================
 // Contract Definition
@@ -100,10 +167,8 @@ impl NumberGuessContract {
     /// * `game_hub` - Address of the GameHub contract
     pub fn __constructor(env: Env, admin: Address, game_hub: Address) {
         // Store admin and GameHub address
-        env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage()
-            .instance()
-            .set(&DataKey::GameHubAddress, &game_hub);
+        game_common::init(&env, &admin, &game_hub);
+        game_common::set_storage_version(&env, STORAGE_VERSION);
     }
 
     /// Start a new game between two players with points.
@@ -126,24 +191,23 @@ impl NumberGuessContract {
         player1_points: i128,
         player2_points: i128,
     ) -> Result<(), Error> {
+        // No new games while paused
+        game_common::ensure_not_paused(&env)?;
+
         // Prevent self-play: Player 1 and Player 2 must be different
         if player1 == player2 {
             panic!("Cannot play against yourself: Player 1 and Player 2 must be different addresses");
         }
 
+        // Reject a reused session ID so a live game can never be overwritten
+        game_common::ensure_new_game(&env, session_id)?;
+
         // Require authentication from both players (they consent to committing points)
         player1.require_auth_for_args(vec![&env, session_id.into_val(&env), player1_points.into_val(&env)]);
         player2.require_auth_for_args(vec![&env, session_id.into_val(&env), player2_points.into_val(&env)]);
 
-        // Get GameHub address
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
-
         // Create GameHub client
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+        let game_hub = game_common::hub_client(&env);
 
         // Call Game Hub to start the session and lock points
         // This requires THIS contract's authorization (env.current_contract_address())
@@ -166,16 +230,13 @@ impl NumberGuessContract {
             player2_guess: None,
             winning_number: None,
             winner: None,
+            is_draw: false,
+            player1_abort: false,
+            player2_abort: false,
         };
 
         // Store game in temporary storage with 30-day TTL
-        let game_key = DataKey::Game(session_id);
-        env.storage().temporary().set(&game_key, &game);
-
-        // Set TTL to ensure game is retained for at least 30 days
-        env.storage()
-            .temporary()
-            .extend_ttl(&game_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+        save_game(&env, session_id, &game);
 
         // Event emitted by the Game Hub contract (GameStarted)
 
@@ -190,6 +251,9 @@ impl NumberGuessContract {
     /// * `player` - Address of the player making the guess
     /// * `guess` - The guessed number (1-10)
     pub fn make_guess(env: Env, session_id: u32, player: Address, guess: u32) -> Result<(), Error> {
+        // No moves while paused
+        game_common::ensure_not_paused(&env)?;
+
         player.require_auth();
 
         // Validate guess is in range
@@ -198,15 +262,10 @@ impl NumberGuessContract {
         }
 
         // Get game from temporary storage
-        let key = DataKey::Game(session_id);
-        let mut game: Game = env
-            .storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::GameNotFound)?;
-
-        // Check game is still active (no winner yet)
-        if game.winner.is_some() {
+        let mut game: Game = load_game(&env, session_id)?;
+
+        // Check game is still active (not won, drawn or aborted)
+        if outcome_of(&game).is_some() {
             return Err(Error::GameAlreadyEnded);
         }
 
@@ -226,13 +285,52 @@ impl NumberGuessContract {
         }
 
         // Store updated game in temporary storage
-        env.storage().temporary().set(&key, &game);
+        save_game(&env, session_id, &game);
 
         // No event emitted - game state can be queried via get_game()
 
         Ok(())
     }
 
+    /// Agree to abandon the game without a result.
+    /// Once both players have agreed, the GameHub session is cancelled and
+    /// both stakes are refunded.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player agreeing to abort
+    ///
+    /// # Returns
+    /// * `bool` - True if this call cancelled the game
+    pub fn abort_game(env: Env, session_id: u32, player: Address) -> Result<bool, Error> {
+        player.require_auth();
+
+        // Get game from temporary storage
+        let mut game: Game = load_game(&env, session_id)?;
+
+        if outcome_of(&game).is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+
+        if player == game.player1 {
+            game.player1_abort = true;
+        } else if player == game.player2 {
+            game.player2_abort = true;
+        } else {
+            return Err(Error::NotPlayer);
+        }
+
+        let cancelled = game.player1_abort && game.player2_abort;
+        if cancelled {
+            // Refund both stakes through the GameHub, unless it already has
+            game_common::cancel_session(&env, session_id);
+        }
+
+        save_game(&env, session_id, &game);
+
+        Ok(cancelled)
+    }
+
     /// Reveal the winner of the game and submit outcome to GameHub.
     /// Can only be called after both players have made their guesses.
     /// This generates the winning number, determines the winner, and ends the session.
@@ -241,19 +339,17 @@ impl NumberGuessContract {
     /// * `session_id` - The session ID of the game
     ///
     /// # Returns
-    /// * `Address` - Address of the winning player
-    pub fn reveal_winner(env: Env, session_id: u32) -> Result<Address, Error> {
+    /// * `GameOutcome` - Which player won, or `Draw` if both were equally close
+    pub fn reveal_winner(env: Env, session_id: u32) -> Result<GameOutcome, Error> {
+        // No moves while paused
+        game_common::ensure_not_paused(&env)?;
+
         // Get game from temporary storage
-        let key = DataKey::Game(session_id);
-        let mut game: Game = env
-            .storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::GameNotFound)?;
-
-        // Check if game already ended (has a winner)
-        if let Some(winner) = &game.winner {
-            return Ok(winner.clone());
+        let mut game: Game = load_game(&env, session_id)?;
+
+        // Check if game already ended (has a winner or is drawn)
+        if let Some(outcome) = outcome_of(&game) {
+            return Ok(outcome);
         }
 
         // Check both players have guessed
@@ -305,34 +401,30 @@ impl NumberGuessContract {
             winning_number - guess2
         };
 
-        // Determine winner (if equal distance, player1 wins)
-        let winner = if distance1 <= distance2 {
-            game.player1.clone()
+        // Determine outcome (equal distance is a draw)
+        let outcome = if distance1 < distance2 {
+            game.winner = Some(game.player1.clone());
+            GameOutcome::Player1
+        } else if distance2 < distance1 {
+            game.winner = Some(game.player2.clone());
+            GameOutcome::Player2
         } else {
-            game.player2.clone()
+            game.is_draw = true;
+            GameOutcome::Draw
         };
 
-        // Update game with winner (this marks the game as ended)
-        game.winner = Some(winner.clone());
-        env.storage().temporary().set(&key, &game);
-
-        // Get GameHub address
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
+        // Update game with outcome (this marks the game as ended)
+        save_game(&env, session_id, &game);
 
         // Create GameHub client
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+        let game_hub = game_common::hub_client(&env);
 
         // Call GameHub to end the session
         // This unlocks points and updates standings
         // Event emitted by the Game Hub contract (GameEnded)
-        let player1_won = winner == game.player1; // true if player1 won, false if player2 won
-        game_hub.end_game(&session_id, &player1_won);
+        game_hub.end_game(&session_id, &outcome);
 
-        Ok(winner)
+        Ok(outcome)
     }
 
     /// Get game information.
@@ -343,11 +435,16 @@ impl NumberGuessContract {
     /// # Returns
     /// * `Game` - The game state (includes winning number after game ends)
     pub fn get_game(env: Env, session_id: u32) -> Result<Game, Error> {
-        let key = DataKey::Game(session_id);
-        env.storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::GameNotFound)
+        load_game(&env, session_id)
+    }
+
+    /// Keep a game alive for another full game TTL.
+    /// Anyone may call this, e.g. spectators following an interesting game.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn bump_game(env: Env, session_id: u32) -> Result<(), Error> {
+        Ok(game_common::bump_game(&env, session_id)?)
     }
 
     // ========================================================================
@@ -359,25 +456,57 @@ impl NumberGuessContract {
     /// # Returns
     /// * `Address` - The admin address
     pub fn get_admin(env: Env) -> Address {
-        env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set")
+        game_common::get_admin(&env)
     }
 
-    /// Set a new admin address
+    /// Get the admin proposed by `propose_admin`, if it has not accepted yet
+    ///
+    /// # Returns
+    /// * `Option<Address>` - The pending admin address
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        game_common::get_pending_admin(&env)
+    }
+
+    /// Propose a new admin address
+    ///
+    /// The current admin keeps control until the new admin calls
+    /// `accept_admin`, so a mistyped address cannot lock the contract.
     ///
     /// # Arguments
-    /// * `new_admin` - The new admin address
-    pub fn set_admin(env: Env, new_admin: Address) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
-
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
+    /// * `new_admin` - The proposed admin address
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        game_common::propose_admin(&env, &new_admin);
+    }
+
+    /// Accept a pending admin proposal (must be authorized by the pending admin)
+    pub fn accept_admin(env: Env) {
+        game_common::accept_admin(&env);
+    }
+
+    /// Withdraw a pending admin proposal
+    pub fn cancel_admin_proposal(env: Env) {
+        game_common::cancel_admin_proposal(&env);
+    }
+
+    /// Check whether the contract is paused
+    ///
+    /// # Returns
+    /// * `bool` - True while new games and moves are blocked
+    pub fn is_paused(env: Env) -> bool {
+        game_common::is_paused(&env)
+    }
+
+    /// Emergency stop: block new games and moves
+    ///
+    /// Queries and `abort_game` keep working, and the GameHub can still
+    /// cancel and refund open sessions.
+    pub fn pause(env: Env) {
+        game_common::pause(&env);
+    }
+
+    /// Allow new games and moves again
+    pub fn unpause(env: Env) {
+        game_common::unpause(&env);
     }
 
     /// Get the current GameHub contract address
@@ -385,10 +514,7 @@ impl NumberGuessContract {
     /// # Returns
     /// * `Address` - The GameHub contract address
     pub fn get_hub(env: Env) -> Address {
-        env.storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set")
+        game_common::get_hub(&env)
     }
 
     /// Set a new GameHub contract address
@@ -396,16 +522,44 @@ impl NumberGuessContract {
     /// # Arguments
     /// * `new_hub` - The new GameHub contract address
     pub fn set_hub(env: Env, new_hub: Address) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
-
-        env.storage()
-            .instance()
-            .set(&DataKey::GameHubAddress, &new_hub);
+        game_common::set_hub(&env, &new_hub);
+    }
+
+    /// Get the storage TTL policy
+    ///
+    /// # Returns
+    /// * `TtlConfig` - Game and instance TTLs in ledgers
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        game_common::get_ttl_config(&env)
+    }
+
+    /// Set the storage TTL policy
+    ///
+    /// # Arguments
+    /// * `config` - Game and instance TTLs in ledgers (non-zero, at most the
+    ///   network maximum)
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        Ok(game_common::set_ttl_config(&env, &config)?)
+    }
+
+    /// Get the schema version of the stored data
+    ///
+    /// # Returns
+    /// * `u32` - The storage version (0 if deployed before versioning)
+    pub fn get_storage_version(env: Env) -> u32 {
+        game_common::get_storage_version(&env)
+    }
+
+    /// Bring stored data up to this release's schema version (call after
+    /// `upgrade`)
+    ///
+    /// Games saved by earlier releases are converted as they are loaded, so
+    /// live sessions keep working before and after the migration.
+    ///
+    /// # Returns
+    /// * `u32` - The storage version the data was at before migrating
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        Ok(game_common::migrate(&env, STORAGE_VERSION)?)
     }
 
     /// Update the contract WASM hash (upgrade contract)
@@ -413,14 +567,7 @@ impl NumberGuessContract {
     /// # Arguments
     /// * `new_wasm_hash` - The hash of the new WASM binary
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
-
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
+        game_common::upgrade(&env, new_wasm_hash);
     }
 }
 
@@ -6,7 +6,10 @@
 // Note: These tests use a minimal mock for isolation and speed.
 // For full integration tests with the real Game Hub contract, see the platform repo.
 
-use crate::{Error, NumberGuessContract, NumberGuessContractClient};
+use crate::{
+    Error, GameOutcome, LegacyGame, NumberGuessContract, NumberGuessContractClient, TtlConfig,
+    STORAGE_VERSION,
+};
 use soroban_sdk::testutils::{Address as _, Ledger as _};
 use soroban_sdk::{contract, contractimpl, Address, BytesN, Env};
 
@@ -31,10 +34,23 @@ impl MockGameHub {
         // Mock implementation - does nothing
     }
 
-    pub fn end_game(_env: Env, _session_id: u32, _player1_won: bool) {
+    pub fn end_game(_env: Env, _session_id: u32, _outcome: GameOutcome) {
         // Mock implementation - does nothing
     }
 
+    pub fn cancel_session(env: Env, session_id: u32) {
+        // Like the real hub, refuse a session that was already closed
+        if env.storage().instance().has(&session_id) {
+            env.panic_with_error(game_common::HubError::SessionAlreadyEnded);
+        }
+    }
+
+    /// Close a session the way the real hub does when anyone cancels it
+    /// after its deadline
+    pub fn close_session(env: Env, session_id: u32) {
+        env.storage().instance().set(&session_id, &true);
+    }
+
     pub fn add_game(_env: Env, _game_address: Address) {
         // Mock implementation - does nothing
     }
@@ -157,6 +173,7 @@ fn test_complete_game() {
     let game = client.get_game(&session_id);
     assert!(game.winning_number.is_none()); // Winning number not set yet
     assert!(game.winner.is_none()); // Game is still active
+    assert!(!game.is_draw);
     assert_eq!(game.player1, player1);
     assert_eq!(game.player2, player2);
     assert_eq!(game.player1_points, points);
@@ -167,13 +184,15 @@ fn test_complete_game() {
     client.make_guess(&session_id, &player2, &7);
 
     // Reveal winner
-    let winner = client.reveal_winner(&session_id);
-    assert!(winner == player1 || winner == player2);
+    let outcome = client.reveal_winner(&session_id);
 
     // Verify game is ended and winning number is now set
     let final_game = client.get_game(&session_id);
-    assert!(final_game.winner.is_some()); // Game has ended
-    assert_eq!(final_game.winner.unwrap(), winner);
+    match outcome {
+        GameOutcome::Player1 => assert_eq!(final_game.winner, Some(player1)),
+        GameOutcome::Player2 => assert_eq!(final_game.winner, Some(player2)),
+        _ => assert!(final_game.is_draw),
+    }
     assert!(final_game.winning_number.is_some());
     let winning_number = final_game.winning_number.unwrap();
     assert!(winning_number >= 1 && winning_number <= 10);
@@ -237,7 +256,7 @@ fn test_closest_guess_wins() {
     client.make_guess(&session_id, &player1, &5);
     client.make_guess(&session_id, &player2, &10);
 
-    let winner = client.reveal_winner(&session_id);
+    let outcome = client.reveal_winner(&session_id);
 
     // Get the final game state to check the winning number
     let game = client.get_game(&session_id);
@@ -255,19 +274,20 @@ fn test_closest_guess_wins() {
         winning_number - 10
     };
 
-    let expected_winner = if distance1 <= distance2 {
-        player1.clone()
+    // 5 and 10 can never be equally close to a whole number
+    let expected_outcome = if distance1 < distance2 {
+        GameOutcome::Player1
     } else {
-        player2.clone()
+        GameOutcome::Player2
     };
     assert_eq!(
-        winner, expected_winner,
+        outcome, expected_outcome,
         "Player with closer guess should win"
     );
 }
 
 #[test]
-fn test_tie_game_player1_wins() {
+fn test_tie_game_is_draw() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 6u32;
@@ -277,8 +297,17 @@ fn test_tie_game_player1_wins() {
     client.make_guess(&session_id, &player1, &5);
     client.make_guess(&session_id, &player2, &5);
 
-    let winner = client.reveal_winner(&session_id);
-    assert_eq!(winner, player1, "Player1 should win in a tie");
+    let outcome = client.reveal_winner(&session_id);
+    assert_eq!(outcome, GameOutcome::Draw, "Equal guesses should be a draw");
+
+    let game = client.get_game(&session_id);
+    assert!(game.winner.is_none());
+    assert!(game.is_draw);
+
+    // The game is over: no more guesses, and reveal keeps reporting the draw
+    let result = client.try_make_guess(&session_id, &player1, &3);
+    assert_number_guess_error(&result, Error::GameAlreadyEnded);
+    assert_eq!(client.reveal_winner(&session_id), GameOutcome::Draw);
 }
 
 #[test]
@@ -293,7 +322,7 @@ fn test_exact_guess_wins() {
     client.make_guess(&session_id, &player1, &5);
     client.make_guess(&session_id, &player2, &10);
 
-    let winner = client.reveal_winner(&session_id);
+    let outcome = client.reveal_winner(&session_id);
     let game = client.get_game(&session_id);
     let winning_number = game.winning_number.unwrap();
 
@@ -308,12 +337,12 @@ fn test_exact_guess_wins() {
     } else {
         winning_number - 10
     };
-    let expected_winner = if distance1 <= distance2 {
-        player1.clone()
+    let expected_outcome = if distance1 < distance2 {
+        GameOutcome::Player1
     } else {
-        player2.clone()
+        GameOutcome::Player2
     };
-    assert_eq!(winner, expected_winner);
+    assert_eq!(outcome, expected_outcome);
 }
 
 // ============================================================================
@@ -419,7 +448,7 @@ fn test_cannot_guess_after_game_ended() {
     client.make_guess(&session_id, &player2, &7);
 
     // Reveal winner - game ends
-    let _winner = client.reveal_winner(&session_id);
+    let _outcome = client.reveal_winner(&session_id);
 
     // Try to make another guess after game has ended - should fail
     let result = client.try_make_guess(&session_id, &player1, &3);
@@ -437,12 +466,11 @@ fn test_cannot_reveal_twice() {
     client.make_guess(&session_id, &player2, &7);
 
     // First reveal succeeds
-    let winner = client.reveal_winner(&session_id);
-    assert!(winner == player1 || winner == player2);
+    let outcome = client.reveal_winner(&session_id);
 
-    // Second reveal should return same winner (idempotent)
-    let winner2 = client.reveal_winner(&session_id);
-    assert_eq!(winner, winner2);
+    // Second reveal should return same outcome (idempotent)
+    let outcome2 = client.reveal_winner(&session_id);
+    assert_eq!(outcome, outcome2);
 }
 
 // ============================================================================
@@ -469,24 +497,153 @@ fn test_multiple_games_independent() {
     client.make_guess(&session2, &player4, &2);
 
     // Reveal both winners
-    let winner1 = client.reveal_winner(&session1);
-    let winner2 = client.reveal_winner(&session2);
-
-    assert!(winner1 == player1 || winner1 == player2);
-    assert!(winner2 == player3 || winner2 == player4);
+    client.reveal_winner(&session1);
+    client.reveal_winner(&session2);
 
     // Verify both games are independent
     let final_game1 = client.get_game(&session1);
     let final_game2 = client.get_game(&session2);
 
-    assert!(final_game1.winner.is_some()); // Game 1 has ended
-    assert!(final_game2.winner.is_some()); // Game 2 has ended
+    assert!(final_game1.winner.is_some() || final_game1.is_draw); // Game 1 has ended
+    assert!(final_game2.winner.is_some() || final_game2.is_draw); // Game 2 has ended
+    if let Some(winner1) = final_game1.winner {
+        assert!(winner1 == player1 || winner1 == player2);
+    }
+    if let Some(winner2) = final_game2.winner {
+        assert!(winner2 == player3 || winner2 == player4);
+    }
 
     // Note: winning numbers could be the same by chance, so we just verify they're both set
     assert!(final_game1.winning_number.is_some());
     assert!(final_game2.winning_number.is_some());
 }
 
+#[test]
+fn test_reused_session_cannot_overwrite_live_game() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let player3 = Address::generate(&env);
+    let player4 = Address::generate(&env);
+
+    let session_id = 22u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.make_guess(&session_id, &player1, &4);
+
+    // A second start with the same session ID must not replace the live game
+    let result = client.try_start_game(&session_id, &player3, &player4, &1, &1);
+    assert_number_guess_error(&result, Error::SessionAlreadyExists);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.player1, player1);
+    assert_eq!(game.player2, player2);
+    assert_eq!(game.player1_guess, Some(4));
+}
+
+#[test]
+fn test_ended_session_cannot_be_restarted() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 23u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.make_guess(&session_id, &player1, &3);
+    client.make_guess(&session_id, &player2, &8);
+    let outcome = client.reveal_winner(&session_id);
+
+    let result = client.try_start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    assert_number_guess_error(&result, Error::SessionAlreadyExists);
+    assert_eq!(client.reveal_winner(&session_id), outcome);
+}
+
+#[test]
+fn test_abort_needs_both_players() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 24u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    // One player alone cannot end the game
+    assert!(!client.abort_game(&session_id, &player1));
+    client.make_guess(&session_id, &player1, &4);
+
+    // Once both agree the game is cancelled
+    assert!(client.abort_game(&session_id, &player2));
+    let game = client.get_game(&session_id);
+    assert!(game.winner.is_none());
+    assert!(game.winning_number.is_none());
+
+    let result = client.try_make_guess(&session_id, &player2, &6);
+    assert_number_guess_error(&result, Error::GameAlreadyEnded);
+    assert_eq!(client.reveal_winner(&session_id), GameOutcome::Cancelled);
+}
+
+#[test]
+fn test_abort_rejected_for_non_player_and_finished_game() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 25u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    let result = client.try_abort_game(&session_id, &Address::generate(&env));
+    assert_number_guess_error(&result, Error::NotPlayer);
+
+    client.make_guess(&session_id, &player1, &3);
+    client.make_guess(&session_id, &player2, &8);
+    client.reveal_winner(&session_id);
+
+    let result = client.try_abort_game(&session_id, &player1);
+    assert_number_guess_error(&result, Error::GameAlreadyEnded);
+}
+
+#[test]
+fn test_abort_after_hub_closed_session() {
+    let (_env, client, hub, player1, player2) = setup_test();
+
+    let session_id = 40u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    // The session expired and the hub already refunded it
+    hub.close_session(&session_id);
+
+    // The players can still end the game
+    assert!(!client.abort_game(&session_id, &player1));
+    assert!(client.abort_game(&session_id, &player2));
+    let result = client.try_abort_game(&session_id, &player1);
+    assert_number_guess_error(&result, Error::GameAlreadyEnded);
+    assert_eq!(client.reveal_winner(&session_id), GameOutcome::Cancelled);
+}
+
+#[test]
+fn test_pause_blocks_new_games_and_moves() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    client.start_game(&26, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&27, &player1, &player2, &100_0000000, &100_0000000);
+    client.make_guess(&26, &player1, &4);
+
+    client.pause();
+    assert_eq!(env.auths()[0].0, client.get_admin());
+    assert!(client.is_paused());
+
+    // Every entry point that starts a game or moves is blocked
+    let result = client.try_start_game(&28, &player1, &player2, &100_0000000, &100_0000000);
+    assert_number_guess_error(&result, Error::ContractPaused);
+    let result = client.try_make_guess(&26, &player2, &6);
+    assert_number_guess_error(&result, Error::ContractPaused);
+    let result = client.try_reveal_winner(&26);
+    assert_number_guess_error(&result, Error::ContractPaused);
+
+    // Queries and the mutual abort (which cancels on the hub) still work
+    assert_eq!(client.get_game(&26).player1_guess, Some(4));
+    assert!(!client.abort_game(&27, &player1));
+    assert!(client.abort_game(&27, &player2));
+
+    // Play resumes where it stopped
+    client.unpause();
+    assert!(!client.is_paused());
+    client.make_guess(&26, &player2, &6);
+    client.reveal_winner(&26);
+    client.start_game(&28, &player1, &player2, &100_0000000, &100_0000000);
+}
+
 #[test]
 fn test_asymmetric_points() {
     let (_env, client, _hub, player1, player2) = setup_test();
@@ -507,7 +664,77 @@ fn test_asymmetric_points() {
 
     // Game completes successfully with asymmetric points
     let final_game = client.get_game(&session_id);
-    assert!(final_game.winner.is_some()); // Game has ended
+    assert!(final_game.is_draw); // Game has ended
+}
+
+#[test]
+fn test_live_sessions_survive_storage_migration() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    // A session saved by this release, and one saved in the layout used before
+    // storage versioning (no draw or abort flags), on a contract with no
+    // version recorded
+    client.start_game(&28, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&29, &player1, &player2, &100_0000000, &100_0000000);
+    client.make_guess(&28, &player1, &3);
+    let game = client.get_game(&29);
+    let legacy_game = LegacyGame {
+        player1: game.player1.clone(),
+        player2: game.player2.clone(),
+        player1_points: game.player1_points,
+        player2_points: game.player2_points,
+        player1_guess: game.player1_guess,
+        player2_guess: game.player2_guess,
+        winning_number: game.winning_number,
+        winner: game.winner.clone(),
+    };
+    env.as_contract(&client.address, || {
+        game_common::save_game(&env, 29, &legacy_game);
+        env.storage()
+            .instance()
+            .remove(&game_common::DataKey::StorageVersion);
+    });
+    assert_eq!(client.get_storage_version(), 0);
+
+    // Legacy games load with the new flags cleared and keep playing before
+    // the migration...
+    assert_eq!(client.get_game(&29), game);
+    client.make_guess(&29, &player1, &4);
+
+    assert_eq!(client.migrate(), 0);
+    assert_eq!(client.get_storage_version(), STORAGE_VERSION);
+    assert_eq!(client.migrate(), STORAGE_VERSION);
+
+    // ...and after it
+    client.make_guess(&29, &player2, &6);
+    client.reveal_winner(&29);
+    client.make_guess(&28, &player2, &7);
+    client.reveal_winner(&28);
+}
+
+#[test]
+fn test_bump_game_and_ttl_config() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    client.start_game(&30, &player1, &player2, &100_0000000, &100_0000000);
+
+    // Anyone can keep a game alive; no authorization is needed
+    client.bump_game(&30);
+    assert!(env.auths().is_empty());
+    let result = client.try_bump_game(&999);
+    assert_number_guess_error(&result, Error::GameNotFound);
+
+    let config = TtlConfig {
+        game_ledgers: 17_280,
+        instance_ledgers: 34_560,
+    };
+    client.set_ttl_config(&config);
+    assert_eq!(client.get_ttl_config(), config);
+    let result = client.try_set_ttl_config(&TtlConfig {
+        game_ledgers: 0,
+        instance_ledgers: 34_560,
+    });
+    assert_number_guess_error(&result, Error::InvalidTtl);
+    assert_eq!(client.get_ttl_config(), config);
 }
 
 // ============================================================================
@@ -5,39 +5,40 @@
 //! A ZK-powered 2-player galactic strategy game on Stellar/Soroban.
 //!
 //! **Gameplay:**
-//! - Each player secretly places 10 bases among 200 stars
+//! - Each player secretly places 10 bases among 200 stars and commits to them
+//!   with a salted Poseidon2 hash
 //! - Players take turns scanning stars to find opponent's bases
 //! - Scans are verified by ZK proofs (no one can cheat about base locations)
 //! - First player to find all 10 opponent bases wins
 //!
 //! **ZK Circuit:**
 //! The circuit proves: "Given my committed bases, the target star is/isn't a base"
-//! without revealing other base locations.
+//! without revealing other base locations. Every proof is bound to its session
+//! and turn, so it cannot be replayed in another game or on a later turn.
+//!
+//! Proofs are checked against the stored VK, or, once the admin calls
+//! `set_verifier`, by a deployed `zk-verifier` contract against a VK
+//! registered there. Checking against the stored VK links the verifier into
+//! this contract and needs the `local-verifier` feature (on by default);
+//! without it, scans fail with `VkNotSet` until a verifier contract is set.
+//!
+//! Scans accept plain UltraHonk proofs and ZK ones (`bb prove --zk`), told
+//! apart by length. A plain proof can leak information about the hidden
+//! bases; the admin can require ZK proofs with `set_require_zk_proofs` once
+//! the ZK path has been checked against the deployed circuit's proofs.
 
+use game_common::{GameError, VerifierConfig, VerifierError, VkChanged};
 use soroban_sdk::{
-    contract, contractclient, contracterror, contractimpl, contracttype,
+    contract, contracterror, contractevent, contractimpl, contracttype,
     vec, Address, Bytes, BytesN, Env, IntoVal, Vec,
 };
-use ultrahonk_soroban_verifier::{UltraHonkVerifier, PROOF_BYTES};
-
-// ============================================================================
-// Game Hub Interface
-// ============================================================================
-
-#[contractclient(name = "GameHubClient")]
-pub trait GameHub {
-    fn start_game(
-        env: Env,
-        game_id: Address,
-        session_id: u32,
-        player1: Address,
-        player2: Address,
-        player1_points: i128,
-        player2_points: i128,
-    );
+#[cfg(feature = "local-verifier")]
+use ultrahonk_soroban_verifier::UltraHonkVerifier;
+use ultrahonk_soroban_verifier::{
+    format::is_proof_len, PublicInputsBuilder, VerifyError, ZK_PROOF_BYTES,
+};
 
-    fn end_game(env: Env, session_id: u32, player1_won: bool);
-}
+pub use game_common::{GameOutcome, TtlConfig};
 
 // ============================================================================
 // Constants
@@ -49,9 +50,6 @@ pub const TOTAL_STARS: u32 = 200;
 /// Number of bases each player places
 pub const BASES_PER_PLAYER: u32 = 10;
 
-/// TTL for game storage (30 days in ledgers)
-const GAME_TTL_LEDGERS: u32 = 518_400;
-
 // ============================================================================
 // Errors
 // ============================================================================
@@ -72,6 +70,62 @@ pub enum Error {
     VkParseError = 10,
     CommitmentMismatch = 11,
     GameNotReady = 12,
+    CommitmentReused = 13,
+    SessionAlreadyExists = 14,
+    ContractPaused = 15,
+    UnsupportedStorageVersion = 16,
+    InvalidTtl = 17,
+    PublicInputsMismatch = 18,
+    SumcheckRoundFailed = 19,
+    SumcheckFinalMismatch = 20,
+    ShplonkDenominatorZero = 21,
+    PairingCheckFailed = 22,
+}
+
+impl From<GameError> for Error {
+    fn from(error: GameError) -> Self {
+        match error {
+            GameError::GameNotFound => Error::GameNotFound,
+            GameError::SessionAlreadyExists => Error::SessionAlreadyExists,
+            GameError::ContractPaused => Error::ContractPaused,
+            GameError::UnsupportedStorageVersion => Error::UnsupportedStorageVersion,
+            GameError::InvalidTtl => Error::InvalidTtl,
+        }
+    }
+}
+
+impl From<VerifyError> for Error {
+    fn from(error: VerifyError) -> Self {
+        // Same mapping as the verifier contract, so a scan fails the same way
+        // whether it is verified locally or through `set_verifier`
+        VerifierError::from(error).into()
+    }
+}
+
+impl From<VerifierError> for Error {
+    fn from(error: VerifierError) -> Self {
+        match error {
+            VerifierError::VkNotFound => Error::VkNotSet,
+            VerifierError::VkParseError => Error::VkParseError,
+            VerifierError::InvalidProof => Error::InvalidProof,
+            VerifierError::PublicInputsMismatch => Error::PublicInputsMismatch,
+            VerifierError::SumcheckRoundFailed => Error::SumcheckRoundFailed,
+            VerifierError::SumcheckFinalMismatch => Error::SumcheckFinalMismatch,
+            VerifierError::ShplonkDenominatorZero => Error::ShplonkDenominatorZero,
+            VerifierError::PairingCheckFailed => Error::PairingCheckFailed,
+            VerifierError::ProofVerificationFailed => Error::ProofVerificationFailed,
+        }
+    }
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+/// The admin started or stopped requiring ZK scan proofs
+#[contractevent]
+pub struct ProofPolicyChanged {
+    pub require_zk: bool,
 }
 
 // ============================================================================
@@ -86,9 +140,9 @@ pub struct Game {
     pub player1_points: i128,
     pub player2_points: i128,
 
-    /// Poseidon hash of player1's 10 base locations
+    /// Salted Poseidon2 hash of player1's 10 base locations
     pub player1_commitment: BytesN<32>,
-    /// Poseidon hash of player2's 10 base locations
+    /// Salted Poseidon2 hash of player2's 10 base locations
     pub player2_commitment: BytesN<32>,
 
     /// Number of opponent bases player1 has found
@@ -104,18 +158,200 @@ pub struct Game {
     /// Whose turn is it (player1 or player2 address)
     pub current_turn: Address,
 
+    /// Number of scans verified so far (anti-replay nonce bound into each proof)
+    pub turn: u32,
+
     /// Winner (once game ends)
     pub winner: Option<Address>,
+
+    /// Player1 agreed to abort the game
+    pub player1_abort: bool,
+    /// Player2 agreed to abort the game (both together cancel it)
+    pub player2_abort: bool,
+}
+
+/// `Game` as stored before storage versioning, without the scan nonce or the
+/// abort flags. These records are converted when loaded; every verified scan
+/// was recorded, so the scan count is the nonce.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyGame {
+    pub player1: Address,
+    pub player2: Address,
+    pub player1_points: i128,
+    pub player2_points: i128,
+    pub player1_commitment: BytesN<32>,
+    pub player2_commitment: BytesN<32>,
+    pub player1_found: u32,
+    pub player2_found: u32,
+    pub player1_scanned: Vec<u32>,
+    pub player2_scanned: Vec<u32>,
+    pub current_turn: Address,
+    pub winner: Option<Address>,
+}
+
+/// Storage schema version written by this release. Bump it and add a
+/// `VersionedGame` variant whenever the stored `Game` layout changes.
+pub const STORAGE_VERSION: u32 = 1;
+
+/// `Game` as stored. A schema change adds a variant for the new layout and
+/// converts older ones in `into_current`, so live sessions still decode after
+/// `upgrade`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VersionedGame {
+    V1(Game),
+}
+
+impl VersionedGame {
+    fn into_current(self) -> Game {
+        match self {
+            VersionedGame::V1(game) => game,
+        }
+    }
+}
+
+// Games saved before storage versioning are a bare `LegacyGame`
+impl From<LegacyGame> for VersionedGame {
+    fn from(game: LegacyGame) -> Self {
+        let turn = game.player1_scanned.len() + game.player2_scanned.len();
+        VersionedGame::V1(Game {
+            player1: game.player1,
+            player2: game.player2,
+            player1_points: game.player1_points,
+            player2_points: game.player2_points,
+            player1_commitment: game.player1_commitment,
+            player2_commitment: game.player2_commitment,
+            player1_found: game.player1_found,
+            player2_found: game.player2_found,
+            player1_scanned: game.player1_scanned,
+            player2_scanned: game.player2_scanned,
+            current_turn: game.current_turn,
+            turn,
+            winner: game.winner,
+            player1_abort: false,
+            player2_abort: false,
+        })
+    }
 }
 
+/// Keys beyond the shared `game_common::DataKey` ones
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
-    Game(u32),
-    GameHubAddress,
-    Admin,
     /// Verification key for ZK proofs (stored once at deploy)
     VerificationKey,
+    /// Verifier contract and registered VK used instead of `VerificationKey`
+    Verifier,
+    /// Marks a base commitment as used (salted commitments must be fresh)
+    UsedCommitment(BytesN<32>),
+    /// Whether only ZK proofs are accepted (unset: plain or ZK)
+    RequireZkProofs,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Load a game, whichever schema version it was saved with
+fn load_game(env: &Env, session_id: u32) -> Result<Game, Error> {
+    let stored = game_common::load_versioned_game::<VersionedGame, LegacyGame>(env, session_id)?;
+    Ok(stored.into_current())
+}
+
+/// Save a game in the current schema version
+fn save_game(env: &Env, session_id: u32, game: &Game) {
+    game_common::save_game(env, session_id, &VersionedGame::V1(game.clone()));
+}
+
+/// Build the public inputs for a scan proof.
+///
+/// Layout (one 32-byte field element each), matching the circuit:
+/// `[session_id, turn, bases_hash, target_star, is_base]`, where `bases_hash`
+/// is the opponent's salted commitment (the salt itself stays private).
+///
+/// `session_id` and `turn` bind the proof to a single scan of a single game,
+/// so a valid proof cannot be replayed elsewhere even if a commitment is reused.
+pub fn scan_public_inputs(
+    env: &Env,
+    session_id: u32,
+    turn: u32,
+    commitment: &BytesN<32>,
+    target_star: u32,
+    is_base: bool,
+) -> Bytes {
+    PublicInputsBuilder::new(env)
+        .push_u32(session_id)
+        .push_u32(turn)
+        .push_bytes32(commitment)
+        .push_u32(target_star)
+        .push_bool(is_base)
+        .build()
+}
+
+/// Whether the admin requires ZK scan proofs
+fn requires_zk_proofs(env: &Env) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::RequireZkProofs)
+        .unwrap_or(false)
+}
+
+/// Verify a scan proof against the stored VK.
+#[cfg(feature = "local-verifier")]
+fn verify_local(
+    env: &Env,
+    proof_bytes: &Bytes,
+    public_inputs: &Bytes,
+    require_zk: bool,
+) -> Result<(), Error> {
+    let vk_bytes: Bytes = env
+        .storage()
+        .instance()
+        .get(&DataKey::VerificationKey)
+        .ok_or(Error::VkNotSet)?;
+
+    let verifier = UltraHonkVerifier::new(env, &vk_bytes)?;
+    if require_zk {
+        verifier.verify_zk(proof_bytes, public_inputs)?;
+    } else {
+        verifier.verify_auto(proof_bytes, public_inputs)?;
+    }
+    Ok(())
+}
+
+/// Built without `local-verifier`: there is no verifier to check the stored
+/// VK with, so scans need `set_verifier`.
+#[cfg(not(feature = "local-verifier"))]
+fn verify_local(
+    _env: &Env,
+    _proof_bytes: &Bytes,
+    _public_inputs: &Bytes,
+    _require_zk: bool,
+) -> Result<(), Error> {
+    Err(Error::VkNotSet)
+}
+
+/// Verify a scan proof with a cross-contract call to the configured verifier.
+fn verify_remote(
+    env: &Env,
+    config: &VerifierConfig,
+    proof_bytes: &Bytes,
+    public_inputs: &Bytes,
+    require_zk: bool,
+) -> Result<(), Error> {
+    let verifier = game_common::verifier_client(env, config);
+    let result = if require_zk {
+        verifier.try_verify_zk(&config.vk_hash, proof_bytes, public_inputs)
+    } else {
+        verifier.try_verify(&config.vk_hash, proof_bytes, public_inputs)
+    };
+    match result {
+        Ok(Ok(())) => Ok(()),
+        Err(Ok(error)) => Err(error.into()),
+        // The verifier trapped or is not a verifier contract
+        Ok(Err(_)) | Err(Err(_)) => Err(Error::ProofVerificationFailed),
+    }
 }
 
 // ============================================================================
@@ -138,10 +374,8 @@ impl TheResistanceContract {
     /// * `game_hub` - Address of the GameHub contract
     /// * `vk_bytes` - Verification key bytes for the ZK circuit
     pub fn __constructor(env: Env, admin: Address, game_hub: Address, vk_bytes: Bytes) {
-        env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage()
-            .instance()
-            .set(&DataKey::GameHubAddress, &game_hub);
+        game_common::init(&env, &admin, &game_hub);
+        game_common::set_storage_version(&env, STORAGE_VERSION);
         env.storage()
             .instance()
             .set(&DataKey::VerificationKey, &vk_bytes);
@@ -153,7 +387,9 @@ impl TheResistanceContract {
 
     /// Start a new game between two players.
     ///
-    /// Both players must submit their base commitments (Poseidon hash of 10 star IDs).
+    /// Both players must submit their base commitments:
+    /// `Poseidon2([Poseidon2(xs), Poseidon2(ys), salt])` over their 10 bases.
+    /// The salt is a fresh secret per game, so each commitment may only be used once.
     /// Player1 goes first.
     ///
     /// # Arguments
@@ -162,8 +398,8 @@ impl TheResistanceContract {
     /// * `player2` - Address of second player
     /// * `player1_points` - Points committed by player 1
     /// * `player2_points` - Points committed by player 2
-    /// * `player1_commitment` - Poseidon hash of player1's 10 bases
-    /// * `player2_commitment` - Poseidon hash of player2's 10 bases
+    /// * `player1_commitment` - Salted Poseidon2 hash of player1's 10 bases
+    /// * `player2_commitment` - Salted Poseidon2 hash of player2's 10 bases
     pub fn start_game(
         env: Env,
         session_id: u32,
@@ -174,11 +410,17 @@ impl TheResistanceContract {
         player1_commitment: BytesN<32>,
         player2_commitment: BytesN<32>,
     ) -> Result<(), Error> {
+        // No new games while paused
+        game_common::ensure_not_paused(&env)?;
+
         // Prevent self-play
         if player1 == player2 {
             panic!("Cannot play against yourself");
         }
 
+        // Reject a reused session ID so a live game can never be overwritten
+        game_common::ensure_new_game(&env, session_id)?;
+
         // Require auth from both players (they commit points + base commitments)
         player1.require_auth_for_args(vec![
             &env,
@@ -193,14 +435,17 @@ impl TheResistanceContract {
             player2_commitment.clone().into_val(&env),
         ]);
 
-        // Get GameHub and register game
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub not set");
+        // Reject reused commitments: a repeated hash means a repeated salt,
+        // which would let an opponent link (and brute-force) the layout
+        if player1_commitment == player2_commitment
+            || Self::is_commitment_used(&env, &player1_commitment)
+            || Self::is_commitment_used(&env, &player2_commitment)
+        {
+            return Err(Error::CommitmentReused);
+        }
 
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+        // Get GameHub and register game
+        let game_hub = game_common::hub_client(&env);
         game_hub.start_game(
             &env.current_contract_address(),
             &session_id,
@@ -210,6 +455,9 @@ impl TheResistanceContract {
             &player2_points,
         );
 
+        Self::mark_commitment_used(&env, &player1_commitment);
+        Self::mark_commitment_used(&env, &player2_commitment);
+
         // Create game state
         let game = Game {
             player1: player1.clone(),
@@ -223,15 +471,14 @@ impl TheResistanceContract {
             player1_scanned: Vec::new(&env),
             player2_scanned: Vec::new(&env),
             current_turn: player1, // Player1 goes first
+            turn: 0,
             winner: None,
+            player1_abort: false,
+            player2_abort: false,
         };
 
         // Store game
-        let key = DataKey::Game(session_id);
-        env.storage().temporary().set(&key, &game);
-        env.storage()
-            .temporary()
-            .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+        save_game(&env, session_id, &game);
 
         Ok(())
     }
@@ -246,7 +493,9 @@ impl TheResistanceContract {
     /// * `session_id` - Game session ID
     /// * `player` - Address of scanning player
     /// * `target_star` - Star ID being scanned (0-199)
-    /// * `proof_bytes` - UltraHonk proof bytes
+    /// * `proof_bytes` - UltraHonk proof bytes, plain or ZK (ZK only if the
+    ///   admin requires it), generated for this `session_id` and the game's
+    ///   current `turn`
     /// * `is_base` - Whether the target is a base (proven by ZK circuit)
     ///
     /// # Returns
@@ -259,6 +508,9 @@ impl TheResistanceContract {
         proof_bytes: Bytes,
         is_base: bool,
     ) -> Result<bool, Error> {
+        // No moves while paused
+        game_common::ensure_not_paused(&env)?;
+
         player.require_auth();
 
         // Validate star ID
@@ -266,21 +518,23 @@ impl TheResistanceContract {
             return Err(Error::InvalidStarId);
         }
 
-        // Validate proof length
-        if proof_bytes.len() as usize != PROOF_BYTES {
+        // Validate proof length (plain or ZK; ZK only if the admin requires it)
+        let require_zk = requires_zk_proofs(&env);
+        let proof_len = proof_bytes.len() as usize;
+        let valid_len = if require_zk {
+            proof_len == ZK_PROOF_BYTES
+        } else {
+            is_proof_len(proof_len)
+        };
+        if !valid_len {
             return Err(Error::InvalidProof);
         }
 
         // Get game
-        let key = DataKey::Game(session_id);
-        let mut game: Game = env
-            .storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::GameNotFound)?;
-
-        // Check game not ended
-        if game.winner.is_some() {
+        let mut game: Game = load_game(&env, session_id)?;
+
+        // Check game not ended or aborted
+        if game.winner.is_some() || (game.player1_abort && game.player2_abort) {
             return Err(Error::GameAlreadyEnded);
         }
 
@@ -305,38 +559,28 @@ impl TheResistanceContract {
             }
         }
 
-        // Build public inputs for ZK verification
-        // Circuit public inputs: [bases_hash (32 bytes), target_star (32 bytes), is_base (32 bytes)]
-        let mut public_inputs = Bytes::new(&env);
-
-        // Add opponent's base commitment (32 bytes)
-        public_inputs.append(&Bytes::from_array(&env, &opponent_commitment.to_array()));
-
-        // Add target_star as 32-byte field element (big-endian, left-padded)
-        let mut star_bytes = [0u8; 32];
-        star_bytes[28..32].copy_from_slice(&target_star.to_be_bytes());
-        public_inputs.append(&Bytes::from_array(&env, &star_bytes));
-
-        // Add is_base as 32-byte field element (0 or 1)
-        let mut is_base_bytes = [0u8; 32];
-        is_base_bytes[31] = if is_base { 1 } else { 0 };
-        public_inputs.append(&Bytes::from_array(&env, &is_base_bytes));
-
-        // Get verification key and verify proof
-        let vk_bytes: Bytes = env
-            .storage()
-            .instance()
-            .get(&DataKey::VerificationKey)
-            .ok_or(Error::VkNotSet)?;
-
-        let verifier =
-            UltraHonkVerifier::new(&env, &vk_bytes).map_err(|_| Error::VkParseError)?;
+        // Build public inputs for ZK verification, bound to this session and turn
+        let public_inputs = scan_public_inputs(
+            &env,
+            session_id,
+            game.turn,
+            opponent_commitment,
+            target_star,
+            is_base,
+        );
 
-        verifier
-            .verify(&proof_bytes, &public_inputs)
-            .map_err(|_| Error::ProofVerificationFailed)?;
+        // Verify the proof locally or through the verifier contract
+        let remote: Option<VerifierConfig> = env.storage().instance().get(&DataKey::Verifier);
+        match remote {
+            Some(config) => {
+                verify_remote(&env, &config, &proof_bytes, &public_inputs, require_zk)?
+            }
+            None => verify_local(&env, &proof_bytes, &public_inputs, require_zk)?,
+        }
 
-        // Proof verified! Update game state
+        // Proof verified! Update game state (advancing the turn nonce
+        // invalidates this proof for any later scan)
+        game.turn += 1;
         if is_player1 {
             game.player1_scanned.push_back(target_star);
             if is_base {
@@ -363,37 +607,74 @@ impl TheResistanceContract {
 
         // If game ended, notify GameHub
         if let Some(ref winner) = game.winner {
-            let game_hub_addr: Address = env
-                .storage()
-                .instance()
-                .get(&DataKey::GameHubAddress)
-                .expect("GameHub not set");
-
-            let game_hub = GameHubClient::new(&env, &game_hub_addr);
-            let player1_won = *winner == game.player1;
-            game_hub.end_game(&session_id, &player1_won);
+            let game_hub = game_common::hub_client(&env);
+            // Turns alternate and the first to find every base wins, so a
+            // scan can never end the game in a draw
+            let outcome = if *winner == game.player1 {
+                GameOutcome::Player1
+            } else {
+                GameOutcome::Player2
+            };
+            game_hub.end_game(&session_id, &outcome);
         }
 
         // Save updated game state
-        env.storage().temporary().set(&key, &game);
-        env.storage()
-            .temporary()
-            .extend_ttl(&key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+        save_game(&env, session_id, &game);
 
         Ok(is_base)
     }
 
+    /// Agree to abandon the game without a result.
+    ///
+    /// Once both players have agreed, the GameHub session is cancelled and
+    /// both stakes are refunded.
+    ///
+    /// # Arguments
+    /// * `session_id` - Game session ID
+    /// * `player` - Address of the player agreeing to abort
+    ///
+    /// # Returns
+    /// * `bool` - True if this call cancelled the game
+    pub fn abort_game(env: Env, session_id: u32, player: Address) -> Result<bool, Error> {
+        player.require_auth();
+
+        let mut game: Game = load_game(&env, session_id)?;
+
+        if game.winner.is_some() || (game.player1_abort && game.player2_abort) {
+            return Err(Error::GameAlreadyEnded);
+        }
+
+        if player == game.player1 {
+            game.player1_abort = true;
+        } else if player == game.player2 {
+            game.player2_abort = true;
+        } else {
+            return Err(Error::NotPlayer);
+        }
+
+        let cancelled = game.player1_abort && game.player2_abort;
+        if cancelled {
+            // Refund both stakes through the GameHub, unless it already has
+            game_common::cancel_session(&env, session_id);
+        }
+
+        save_game(&env, session_id, &game);
+
+        Ok(cancelled)
+    }
+
     // ========================================================================
     // Query Functions
     // ========================================================================
 
     /// Get game state.
     pub fn get_game(env: Env, session_id: u32) -> Result<Game, Error> {
-        let key = DataKey::Game(session_id);
-        env.storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::GameNotFound)
+        load_game(&env, session_id)
+    }
+
+    /// Keep a game alive for another full game TTL (anyone may call).
+    pub fn bump_game(env: Env, session_id: u32) -> Result<(), Error> {
+        Ok(game_common::bump_game(&env, session_id)?)
     }
 
     /// Get the current turn for a game.
@@ -426,76 +707,167 @@ impl TheResistanceContract {
         }
     }
 
+    // ========================================================================
+    // Internal Helper Functions
+    // ========================================================================
+
+    /// Check whether a base commitment was already used in a previous game
+    fn is_commitment_used(env: &Env, commitment: &BytesN<32>) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::UsedCommitment(commitment.clone()))
+    }
+
+    /// Record a base commitment so it cannot be used again. The marker is
+    /// kept for the network's maximum TTL, long after the game itself expires.
+    fn mark_commitment_used(env: &Env, commitment: &BytesN<32>) {
+        let key = DataKey::UsedCommitment(commitment.clone());
+        let ttl = env.storage().max_ttl();
+        env.storage().persistent().set(&key, &true);
+        env.storage().persistent().extend_ttl(&key, ttl, ttl);
+    }
+
     // ========================================================================
     // Admin Functions
     // ========================================================================
 
     /// Get the current admin address.
     pub fn get_admin(env: Env) -> Address {
-        env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set")
+        game_common::get_admin(&env)
     }
 
-    /// Set a new admin address.
-    pub fn set_admin(env: Env, new_admin: Address) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
+    /// Get the admin proposed by `propose_admin`, if it has not accepted yet.
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        game_common::get_pending_admin(&env)
+    }
+
+    /// Propose a new admin address. Takes effect once the new admin calls
+    /// `accept_admin`.
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        game_common::propose_admin(&env, &new_admin);
+    }
+
+    /// Accept a pending admin proposal (pending admin only).
+    pub fn accept_admin(env: Env) {
+        game_common::accept_admin(&env);
+    }
+
+    /// Withdraw a pending admin proposal.
+    pub fn cancel_admin_proposal(env: Env) {
+        game_common::cancel_admin_proposal(&env);
+    }
+
+    /// Whether new games and scans are blocked.
+    pub fn is_paused(env: Env) -> bool {
+        game_common::is_paused(&env)
+    }
+
+    /// Emergency stop: block new games and scans (admin only). Queries and
+    /// `abort_game` keep working, and the hub can still cancel and refund.
+    pub fn pause(env: Env) {
+        game_common::pause(&env);
+    }
 
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
+    /// Allow new games and scans again (admin only).
+    pub fn unpause(env: Env) {
+        game_common::unpause(&env);
     }
 
     /// Get the GameHub contract address.
     pub fn get_hub(env: Env) -> Address {
-        env.storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub not set")
+        game_common::get_hub(&env)
     }
 
     /// Set a new GameHub contract address.
     pub fn set_hub(env: Env, new_hub: Address) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
+        game_common::set_hub(&env, &new_hub);
+    }
+
+    /// Update the verification key (admin only). Scans are verified against
+    /// it again if a verifier contract was set.
+    pub fn set_vk(env: Env, vk_bytes: Bytes) {
+        game_common::require_admin(&env);
 
         env.storage()
             .instance()
-            .set(&DataKey::GameHubAddress, &new_hub);
+            .set(&DataKey::VerificationKey, &vk_bytes);
+        env.storage().instance().remove(&DataKey::Verifier);
+
+        VkChanged {
+            vk_hash: env.crypto().keccak256(&vk_bytes).into(),
+        }
+        .publish(&env);
     }
 
-    /// Update the verification key (admin only).
-    pub fn set_vk(env: Env, vk_bytes: Bytes) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
+    /// Verify scans with a cross-contract call to `verifier` against the VK
+    /// registered there under `vk_hash`, instead of the stored VK (admin only).
+    ///
+    /// # Arguments
+    /// * `verifier` - Address of a deployed `zk-verifier` contract
+    /// * `vk_hash` - keccak256 of the VK, as returned by its `register_vk`
+    pub fn set_verifier(env: Env, verifier: Address, vk_hash: BytesN<32>) {
+        game_common::require_admin(&env);
+
+        let config = VerifierConfig {
+            verifier,
+            vk_hash: vk_hash.clone(),
+        };
+        env.storage().instance().set(&DataKey::Verifier, &config);
+
+        VkChanged { vk_hash }.publish(&env);
+    }
+
+    /// Get the verifier contract scans are checked by, if one was set.
+    pub fn get_verifier(env: Env) -> Option<VerifierConfig> {
+        env.storage().instance().get(&DataKey::Verifier)
+    }
+
+    /// Whether scans only accept ZK proofs.
+    pub fn requires_zk_proofs(env: Env) -> bool {
+        requires_zk_proofs(&env)
+    }
+
+    /// Accept only ZK scan proofs, or go back to plain or ZK (admin only).
+    ///
+    /// A plain proof does not hide the prover's private inputs, so it can leak
+    /// the base layout. Require ZK proofs once the deployed circuit's
+    /// `bb prove --zk` proofs are known to verify.
+    pub fn set_require_zk_proofs(env: Env, require_zk: bool) {
+        game_common::require_admin(&env);
 
         env.storage()
             .instance()
-            .set(&DataKey::VerificationKey, &vk_bytes);
+            .set(&DataKey::RequireZkProofs, &require_zk);
+
+        ProofPolicyChanged { require_zk }.publish(&env);
+    }
+
+    /// Get the storage TTL policy.
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        game_common::get_ttl_config(&env)
+    }
+
+    /// Set the storage TTL policy (admin only).
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        Ok(game_common::set_ttl_config(&env, &config)?)
+    }
+
+    /// Get the schema version of the stored data (0 if deployed before
+    /// versioning).
+    pub fn get_storage_version(env: Env) -> u32 {
+        game_common::get_storage_version(&env)
+    }
+
+    /// Bring stored data up to this release's schema version (admin only,
+    /// call after `upgrade`). Games saved by earlier releases are converted
+    /// as they are loaded. Returns the version the data was at.
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        Ok(game_common::migrate(&env, STORAGE_VERSION)?)
     }
 
     /// Upgrade the contract WASM.
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
-
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
+        game_common::upgrade(&env, new_wasm_hash);
     }
 }
 
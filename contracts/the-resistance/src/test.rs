@@ -5,10 +5,19 @@
 //! Note: Full ZK proof verification tests require real proofs from the Noir circuit.
 //! These tests verify game state management and basic error handling.
 
-use crate::{Error, TheResistanceContract, TheResistanceContractClient, BASES_PER_PLAYER, TOTAL_STARS};
-use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env};
-use ultrahonk_soroban_verifier::PROOF_BYTES;
+use crate::{
+    scan_public_inputs, DataKey, Error, GameOutcome, LegacyGame, ProofPolicyChanged,
+    TheResistanceContract, TheResistanceContractClient, TtlConfig, BASES_PER_PLAYER,
+    STORAGE_VERSION, TOTAL_STARS,
+};
+use game_common::{VerifierConfig, VerifierError, VkChanged, DEFAULT_GAME_TTL_LEDGERS};
+use soroban_sdk::testutils::{storage::Persistent as _, Address as _, Events as _, Ledger as _};
+use soroban_sdk::{contract, contractimpl, vec, Address, Bytes, BytesN, Env, Event as _};
+use ultrahonk_soroban_verifier::{
+    format::POINT_FIELDS, utils::bytes_to_vec, PublicInputsReader, VerifyError, PROOF_BYTES,
+    VK_BYTES, ZK_PROOF_BYTES,
+};
+use zk_verifier::{ZkVerifierContract, ZkVerifierContractClient};
 
 // ============================================================================
 // Mock GameHub for Unit Testing
@@ -31,10 +40,23 @@ impl MockGameHub {
         // Mock implementation
     }
 
-    pub fn end_game(_env: Env, _session_id: u32, _player1_won: bool) {
+    pub fn end_game(_env: Env, _session_id: u32, _outcome: GameOutcome) {
         // Mock implementation
     }
 
+    pub fn cancel_session(env: Env, session_id: u32) {
+        // Like the real hub, refuse a session that was already closed
+        if env.storage().instance().has(&session_id) {
+            env.panic_with_error(game_common::HubError::SessionAlreadyEnded);
+        }
+    }
+
+    /// Close a session the way the real hub does when anyone cancels it
+    /// after its deadline
+    pub fn close_session(env: Env, session_id: u32) {
+        env.storage().instance().set(&session_id, &true);
+    }
+
     pub fn add_game(_env: Env, _game_address: Address) {
         // Mock implementation
     }
@@ -89,7 +111,7 @@ fn setup_test() -> (
     (env, client, game_hub, player1, player2)
 }
 
-/// Helper to create a fake base commitment (Poseidon hash)
+/// Helper to create a fake base commitment (salted Poseidon2 hash)
 fn fake_commitment(env: &Env, seed: u8) -> BytesN<32> {
     let mut bytes = [0u8; 32];
     bytes[0] = seed;
@@ -97,6 +119,34 @@ fn fake_commitment(env: &Env, seed: u8) -> BytesN<32> {
     BytesN::from_array(env, &bytes)
 }
 
+/// Helper to create a bb v0.87.0 VK with the given header and zeroed commitments
+fn fake_vk(env: &Env, log_circuit_size: u64, public_inputs_size: u64) -> Bytes {
+    let mut bytes = [0u8; VK_BYTES];
+    bytes[0..8].copy_from_slice(&(1u64 << log_circuit_size).to_be_bytes());
+    bytes[8..16].copy_from_slice(&log_circuit_size.to_be_bytes());
+    bytes[16..24].copy_from_slice(&public_inputs_size.to_be_bytes());
+    bytes[24..32].copy_from_slice(&1u64.to_be_bytes());
+    Bytes::from_slice(env, &bytes)
+}
+
+/// Padded ZK proof whose first sumcheck univariate does not sum to zero
+fn bad_sumcheck_zk_proof() -> [u8; ZK_PROOF_BYTES] {
+    let mut proof = [0u8; ZK_PROOF_BYTES];
+    // Pairing points, 8 witness commitments, the first Libra commitment and
+    // the Libra sum come first
+    let first_univariate = (16 + 9 * POINT_FIELDS + 1) * 32;
+    proof[first_univariate + 31] = 1;
+    proof
+}
+
+/// Padded plain proof whose first sumcheck univariate does not sum to zero
+fn bad_sumcheck_proof() -> [u8; PROOF_BYTES] {
+    let mut proof = [0u8; PROOF_BYTES];
+    let first_univariate = (16 + 8 * POINT_FIELDS) * 32;
+    proof[first_univariate + 31] = 1;
+    proof
+}
+
 /// Assert that a Result contains a specific error
 fn assert_resistance_error<T, E>(
     result: &Result<Result<T, E>, Result<Error, soroban_sdk::InvokeError>>,
@@ -166,6 +216,7 @@ fn test_start_game() {
     assert_eq!(game.player1_found, 0);
     assert_eq!(game.player2_found, 0);
     assert_eq!(game.current_turn, player1); // Player1 goes first
+    assert_eq!(game.turn, 0);
     assert!(game.winner.is_none());
 }
 
@@ -224,6 +275,145 @@ fn test_cannot_self_play() {
     );
 }
 
+#[test]
+fn test_identical_commitments_rejected() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    // Both players submitting the same hash means a shared (or missing) salt
+    let commitment = fake_commitment(&env, 1);
+    let result = client.try_start_game(
+        &1u32,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &commitment,
+        &commitment,
+    );
+    assert_resistance_error(&result, Error::CommitmentReused);
+}
+
+#[test]
+fn test_reused_commitment_rejected() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let player3 = Address::generate(&env);
+
+    client.start_game(
+        &1u32,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 1),
+        &fake_commitment(&env, 2),
+    );
+
+    // Player1 reuses their commitment (same layout and salt) in a new game
+    let result = client.try_start_game(
+        &2u32,
+        &player1,
+        &player3,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 1),
+        &fake_commitment(&env, 3),
+    );
+    assert_resistance_error(&result, Error::CommitmentReused);
+
+    // A freshly salted commitment is accepted
+    client.start_game(
+        &2u32,
+        &player1,
+        &player3,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 4),
+        &fake_commitment(&env, 3),
+    );
+}
+
+#[test]
+fn test_reused_commitment_rejected_after_game_expires() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let player3 = Address::generate(&env);
+
+    // Entries written from here on only live for their requested TTL
+    let max_ttl = 6 * DEFAULT_GAME_TTL_LEDGERS;
+    env.ledger().with_mut(|li| {
+        li.min_temp_entry_ttl = 16;
+        li.min_persistent_entry_ttl = 16;
+        li.max_entry_ttl = max_ttl;
+    });
+
+    client.start_game(
+        &1u32,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 1),
+        &fake_commitment(&env, 2),
+    );
+
+    // Long after the game's TTL has run out...
+    env.ledger()
+        .with_mut(|li| li.sequence_number += DEFAULT_GAME_TTL_LEDGERS + 1);
+    // ...the commitment markers are still live, with more than a game's
+    // lifetime left...
+    for seed in [1, 2] {
+        let key = DataKey::UsedCommitment(fake_commitment(&env, seed));
+        let ttl = env.as_contract(&client.address, || env.storage().persistent().get_ttl(&key));
+        assert!(ttl > DEFAULT_GAME_TTL_LEDGERS, "{ttl}");
+    }
+
+    // ...and cannot be used again
+    let result = client.try_start_game(
+        &2u32,
+        &player1,
+        &player3,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 1),
+        &fake_commitment(&env, 3),
+    );
+    assert_resistance_error(&result, Error::CommitmentReused);
+}
+
+#[test]
+fn test_reused_session_cannot_overwrite_live_game() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let player3 = Address::generate(&env);
+    let player4 = Address::generate(&env);
+
+    client.start_game(
+        &1u32,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 1),
+        &fake_commitment(&env, 2),
+    );
+
+    // Fresh commitments, but the session ID is already live
+    let result = client.try_start_game(
+        &1u32,
+        &player3,
+        &player4,
+        &1,
+        &1,
+        &fake_commitment(&env, 3),
+        &fake_commitment(&env, 4),
+    );
+    assert_resistance_error(&result, Error::SessionAlreadyExists);
+
+    let game = client.get_game(&1u32);
+    assert_eq!(game.player1, player1);
+    assert_eq!(game.player2, player2);
+    assert_eq!(game.player1_commitment, fake_commitment(&env, 1));
+    assert_eq!(game.player2_commitment, fake_commitment(&env, 2));
+}
+
 #[test]
 fn test_game_not_found() {
     let (_env, client, _hub, _player1, _player2) = setup_test();
@@ -309,6 +499,96 @@ fn test_not_player_error_on_scans() {
     assert_resistance_error(&result, Error::NotPlayer);
 }
 
+#[test]
+fn test_live_sessions_survive_storage_migration() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    // A game saved in the layout used before storage versioning (no scan
+    // nonce or abort flags), after one scan each, on a contract with no
+    // version recorded
+    client.start_game(
+        &1u32,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 1),
+        &fake_commitment(&env, 2),
+    );
+    let game = client.get_game(&1u32);
+    let legacy_game = LegacyGame {
+        player1: game.player1.clone(),
+        player2: game.player2.clone(),
+        player1_points: game.player1_points,
+        player2_points: game.player2_points,
+        player1_commitment: game.player1_commitment.clone(),
+        player2_commitment: game.player2_commitment.clone(),
+        player1_found: 1,
+        player2_found: 0,
+        player1_scanned: vec![&env, 7],
+        player2_scanned: vec![&env, 12],
+        current_turn: player1.clone(),
+        winner: None,
+    };
+    env.as_contract(&client.address, || {
+        game_common::save_game(&env, 1, &legacy_game);
+        env.storage()
+            .instance()
+            .remove(&game_common::DataKey::StorageVersion);
+    });
+    assert_eq!(client.get_storage_version(), 0);
+
+    // The legacy game loads with the scan count as its nonce, before and
+    // after migrating
+    let loaded = client.get_game(&1u32);
+    assert_eq!(loaded.turn, 2);
+    assert_eq!(loaded.player1_scanned, legacy_game.player1_scanned);
+    assert_eq!(loaded.player1_found, 1);
+    assert!(!loaded.player1_abort && !loaded.player2_abort);
+    assert_eq!(client.migrate(), 0);
+    assert_eq!(client.get_storage_version(), STORAGE_VERSION);
+    assert_eq!(client.migrate(), STORAGE_VERSION);
+    assert_eq!(client.get_game(&1u32), loaded);
+
+    assert_eq!(client.get_current_turn(&1u32), player1);
+    assert!(!client.abort_game(&1u32, &player1));
+    assert!(client.abort_game(&1u32, &player2));
+    assert!(client.get_game(&1u32).winner.is_none());
+}
+
+#[test]
+fn test_bump_game_and_ttl_config() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    client.start_game(
+        &1u32,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 1),
+        &fake_commitment(&env, 2),
+    );
+
+    // Anyone can keep a game alive; no authorization is needed
+    client.bump_game(&1u32);
+    assert!(env.auths().is_empty());
+    let result = client.try_bump_game(&999);
+    assert_resistance_error(&result, Error::GameNotFound);
+
+    let config = TtlConfig {
+        game_ledgers: 17_280,
+        instance_ledgers: 34_560,
+    };
+    client.set_ttl_config(&config);
+    assert_eq!(client.get_ttl_config(), config);
+    let result = client.try_set_ttl_config(&TtlConfig {
+        game_ledgers: 0,
+        instance_ledgers: 34_560,
+    });
+    assert_resistance_error(&result, Error::InvalidTtl);
+    assert_eq!(client.get_ttl_config(), config);
+}
+
 // ============================================================================
 // Admin Function Tests
 // ============================================================================
@@ -330,8 +610,11 @@ fn test_admin_functions() {
     // Get initial admin
     assert_eq!(client.get_admin(), admin);
 
-    // Set new admin
-    client.set_admin(&new_admin);
+    // Propose new admin; it takes over once it accepts
+    client.propose_admin(&new_admin);
+    assert_eq!(client.get_admin(), admin);
+    assert_eq!(client.get_pending_admin(), Some(new_admin.clone()));
+    client.accept_admin();
     assert_eq!(client.get_admin(), new_admin);
 
     // Get initial hub
@@ -357,11 +640,52 @@ fn test_set_vk() {
 
     // Update VK (admin only)
     client.set_vk(&new_vk);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        env.events().all(),
+        [VkChanged {
+            vk_hash: env.crypto().keccak256(&new_vk).into(),
+        }
+        .to_xdr(&env, &contract_id)]
+    );
 
     // VK is updated - can verify by trying to start a game and scan
     // (which would fail with different errors depending on VK state)
 }
 
+#[test]
+fn test_set_verifier() {
+    let (env, client, _hub, _player1, _player2) = setup_test();
+    let admin = client.get_admin();
+
+    let verifier_id = env.register(ZkVerifierContract, ());
+    let verifier = ZkVerifierContractClient::new(&env, &verifier_id);
+    let vk_hash = verifier.register_vk(&fake_vk(&env, 1, 16 + 5));
+    assert_eq!(client.get_verifier(), None);
+
+    // Point scans at the verifier contract (admin only)
+    client.set_verifier(&verifier_id, &vk_hash);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        env.events().all(),
+        [VkChanged {
+            vk_hash: vk_hash.clone(),
+        }
+        .to_xdr(&env, &client.address)]
+    );
+    assert_eq!(
+        client.get_verifier(),
+        Some(VerifierConfig {
+            verifier: verifier_id,
+            vk_hash,
+        })
+    );
+
+    // Setting a VK goes back to verifying locally
+    client.set_vk(&fake_vk(&env, 1, 16 + 5));
+    assert_eq!(client.get_verifier(), None);
+}
+
 // ============================================================================
 // Constants Tests
 // ============================================================================
@@ -415,11 +739,82 @@ fn test_scan_invalid_proof_length() {
     );
 
     // Try to scan with wrong proof length
-    let fake_proof = Bytes::from_array(&env, &[0u8; 32]); // Should be PROOF_BYTES
+    let fake_proof = Bytes::from_array(&env, &[0u8; 32]); // Should be ZK_PROOF_BYTES
     let result = client.try_scan(&1u32, &player1, &50, &fake_proof, &false);
     assert_resistance_error(&result, Error::InvalidProof);
 }
 
+#[test]
+#[cfg(feature = "local-verifier")]
+fn test_scan_verifier_errors_are_distinct() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    client.start_game(
+        &1u32,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 1),
+        &fake_commitment(&env, 2),
+    );
+
+    let zero_proof = Bytes::from_slice(&env, &[0u8; ZK_PROOF_BYTES]);
+
+    // VK header without the 16 pairing point fields
+    client.set_vk(&fake_vk(&env, 1, 0));
+    let result = client.try_scan(&1u32, &player1, &50, &zero_proof, &false);
+    assert_resistance_error(&result, Error::VkParseError);
+
+    // VK expects 4 circuit inputs but scan binds 5
+    client.set_vk(&fake_vk(&env, 1, 16 + 4));
+    let result = client.try_scan(&1u32, &player1, &50, &zero_proof, &false);
+    assert_resistance_error(&result, Error::PublicInputsMismatch);
+
+    // First sumcheck univariate no longer sums to zero
+    client.set_vk(&fake_vk(&env, 1, 16 + 5));
+    let proof = bad_sumcheck_zk_proof();
+    let result = client.try_scan(&1u32, &player1, &50, &Bytes::from_slice(&env, &proof), &false);
+    assert_resistance_error(&result, Error::SumcheckRoundFailed);
+
+    // Proofs must be padded to the full round count
+    let truncated = &proof[..ZK_PROOF_BYTES - 32];
+    let result = client.try_scan(&1u32, &player1, &50, &Bytes::from_slice(&env, truncated), &false);
+    assert_resistance_error(&result, Error::InvalidProof);
+
+    // Nothing was recorded for the failed scans
+    assert_eq!(client.get_current_turn(&1u32), player1);
+}
+
+#[test]
+#[cfg(not(feature = "local-verifier"))]
+fn test_scan_needs_verifier_contract_without_local_verifier() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    client.start_game(
+        &1u32,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 1),
+        &fake_commitment(&env, 2),
+    );
+
+    // The stored VK is never used
+    client.set_vk(&fake_vk(&env, 1, 16 + 5));
+    let proof = Bytes::from_slice(&env, &bad_sumcheck_zk_proof());
+    let result = client.try_scan(&1u32, &player1, &50, &proof, &false);
+    assert_resistance_error(&result, Error::VkNotSet);
+
+    let verifier_id = env.register(ZkVerifierContract, ());
+    let verifier = ZkVerifierContractClient::new(&env, &verifier_id);
+    let vk_hash = verifier.register_vk(&fake_vk(&env, 1, 16 + 5));
+    client.set_verifier(&verifier_id, &vk_hash);
+    let result = client.try_scan(&1u32, &player1, &50, &proof, &false);
+    assert_resistance_error(&result, Error::SumcheckRoundFailed);
+}
+
 #[test]
 fn test_scan_not_your_turn() {
     let (env, client, _hub, player1, player2) = setup_test();
@@ -436,9 +831,135 @@ fn test_scan_not_your_turn() {
 
     // Player2 tries to scan but it's player1's turn
     // Use correct proof length to pass that check and reach the turn check
-    let fake_proof = Bytes::from_slice(&env, &[0u8; PROOF_BYTES]);
+    let fake_proof = Bytes::from_slice(&env, &[0u8; ZK_PROOF_BYTES]);
+    let result = client.try_scan(&1u32, &player2, &50, &fake_proof, &false);
+    assert_resistance_error(&result, Error::NotYourTurn);
+
+    // So do plain proofs, unless the admin requires ZK ones
+    let plain_proof = Bytes::from_slice(&env, &[0u8; PROOF_BYTES]);
+    let result = client.try_scan(&1u32, &player2, &50, &plain_proof, &false);
+    assert_resistance_error(&result, Error::NotYourTurn);
+    client.set_require_zk_proofs(&true);
+    let result = client.try_scan(&1u32, &player2, &50, &plain_proof, &false);
+    assert_resistance_error(&result, Error::InvalidProof);
+}
+
+#[test]
+fn test_scan_after_abort() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    client.start_game(
+        &1u32,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 1),
+        &fake_commitment(&env, 2),
+    );
+
+    // Both players must agree before the game is cancelled
+    assert!(!client.abort_game(&1u32, &player2));
+    assert!(client.abort_game(&1u32, &player1));
+
+    let fake_proof = Bytes::from_slice(&env, &[0u8; ZK_PROOF_BYTES]);
+    let result = client.try_scan(&1u32, &player1, &50, &fake_proof, &false);
+    assert_resistance_error(&result, Error::GameAlreadyEnded);
+
+    let result = client.try_abort_game(&1u32, &player1);
+    assert_resistance_error(&result, Error::GameAlreadyEnded);
+    assert!(client.get_game(&1u32).winner.is_none());
+}
+
+#[test]
+fn test_abort_after_hub_closed_session() {
+    let (env, client, hub, player1, player2) = setup_test();
+
+    client.start_game(
+        &1u32,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 1),
+        &fake_commitment(&env, 2),
+    );
+
+    // The session expired and the hub already refunded it
+    hub.close_session(&1u32);
+
+    // The players can still end the game
+    assert!(!client.abort_game(&1u32, &player1));
+    assert!(client.abort_game(&1u32, &player2));
+    let result = client.try_abort_game(&1u32, &player1);
+    assert_resistance_error(&result, Error::GameAlreadyEnded);
+}
+
+#[test]
+fn test_abort_by_non_player() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    client.start_game(
+        &1u32,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 1),
+        &fake_commitment(&env, 2),
+    );
+
+    let result = client.try_abort_game(&1u32, &Address::generate(&env));
+    assert_resistance_error(&result, Error::NotPlayer);
+}
+
+#[test]
+fn test_pause_blocks_new_games_and_scans() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    client.start_game(
+        &1u32,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 1),
+        &fake_commitment(&env, 2),
+    );
+
+    client.pause();
+    assert_eq!(env.auths()[0].0, client.get_admin());
+    assert!(client.is_paused());
+
+    // Starting a game and scanning are both blocked
+    let result = client.try_start_game(
+        &2u32,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 3),
+        &fake_commitment(&env, 4),
+    );
+    assert_resistance_error(&result, Error::ContractPaused);
+    let fake_proof = Bytes::from_slice(&env, &[0u8; ZK_PROOF_BYTES]);
+    let result = client.try_scan(&1u32, &player1, &50, &fake_proof, &false);
+    assert_resistance_error(&result, Error::ContractPaused);
+
+    // Queries still work
+    assert_eq!(client.get_current_turn(&1u32), player1);
+    assert_eq!(client.get_found_count(&1u32, &player1), 0);
+
+    // After unpausing, scans reach the normal checks again
+    client.unpause();
+    assert!(!client.is_paused());
     let result = client.try_scan(&1u32, &player2, &50, &fake_proof, &false);
     assert_resistance_error(&result, Error::NotYourTurn);
+
+    // The mutual abort (which cancels on the hub) works while paused
+    client.pause();
+    assert!(!client.abort_game(&1u32, &player1));
+    assert!(client.abort_game(&1u32, &player2));
 }
 
 #[test]
@@ -446,7 +967,193 @@ fn test_scan_game_not_found() {
     let (env, client, _hub, player1, _player2) = setup_test();
 
     // Use correct proof length to pass that check and reach the game lookup
-    let fake_proof = Bytes::from_slice(&env, &[0u8; PROOF_BYTES]);
+    let fake_proof = Bytes::from_slice(&env, &[0u8; ZK_PROOF_BYTES]);
     let result = client.try_scan(&999, &player1, &50, &fake_proof, &false);
     assert_resistance_error(&result, Error::GameNotFound);
 }
+
+#[test]
+fn test_verify_errors_map_like_verifier_contract() {
+    // Same mapping as zk-verifier, so a scan fails with the same error
+    // whether it is verified locally or through `set_verifier`
+    let pairs = [
+        (VerifyError::ProofLength { expected: 1, actual: 0 }, Error::InvalidProof),
+        (VerifyError::InvalidPoint, Error::InvalidProof),
+        (VerifyError::VkLength { actual: 0 }, Error::VkParseError),
+        (VerifyError::InvalidVkHeader, Error::VkParseError),
+        (VerifyError::PublicInputsNotAligned { len: 1 }, Error::PublicInputsMismatch),
+        (VerifyError::PublicInputsCount { expected: 1, actual: 0 }, Error::PublicInputsMismatch),
+        (VerifyError::SumcheckRound { round: 0 }, Error::SumcheckRoundFailed),
+        (VerifyError::SumcheckDenominatorZero { round: 0 }, Error::SumcheckRoundFailed),
+        (VerifyError::SumcheckFinalMismatch, Error::SumcheckFinalMismatch),
+        (VerifyError::ShplonkDenominatorZero, Error::ShplonkDenominatorZero),
+        (VerifyError::PairingFailed, Error::PairingCheckFailed),
+        (VerifyError::PublicInputDeltaDenominatorZero, Error::ProofVerificationFailed),
+        (VerifyError::GeminiDenominatorZero, Error::ProofVerificationFailed),
+        (VerifyError::LibraInconsistent, Error::ProofVerificationFailed),
+        (VerifyError::MsmLength, Error::ProofVerificationFailed),
+    ];
+    for (error, expected) in pairs {
+        assert_eq!(Error::from(error), expected, "{error:?}");
+        assert_eq!(Error::from(VerifierError::from(error)), expected, "{error:?}");
+    }
+}
+
+// ============================================================================
+// Cross-Contract Verification Tests
+// ============================================================================
+
+#[test]
+fn test_scan_via_verifier_contract() {
+    let (env, client, hub, player1, player2) = setup_test();
+
+    client.start_game(
+        &1u32,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 1),
+        &fake_commitment(&env, 2),
+    );
+
+    // The stored VK does not parse, so every error below comes from the
+    // verifier contract
+    let verifier_id = env.register(ZkVerifierContract, ());
+    let verifier = ZkVerifierContractClient::new(&env, &verifier_id);
+    let vk_hash = verifier.register_vk(&fake_vk(&env, 1, 16 + 5));
+    client.set_verifier(&verifier_id, &vk_hash);
+
+    // First sumcheck univariate no longer sums to zero
+    let proof = bad_sumcheck_zk_proof();
+    let result = client.try_scan(&1u32, &player1, &50, &Bytes::from_slice(&env, &proof), &false);
+    assert_resistance_error(&result, Error::SumcheckRoundFailed);
+
+    // The registered VK expects 4 circuit inputs but scan binds 5
+    let narrow_hash = verifier.register_vk(&fake_vk(&env, 1, 16 + 4));
+    client.set_verifier(&verifier_id, &narrow_hash);
+    let zero_proof = Bytes::from_slice(&env, &[0u8; ZK_PROOF_BYTES]);
+    let result = client.try_scan(&1u32, &player1, &50, &zero_proof, &false);
+    assert_resistance_error(&result, Error::PublicInputsMismatch);
+
+    // No VK registered under the hash
+    client.set_verifier(&verifier_id, &BytesN::from_array(&env, &[7u8; 32]));
+    let result = client.try_scan(&1u32, &player1, &50, &zero_proof, &false);
+    assert_resistance_error(&result, Error::VkNotSet);
+
+    // Not a verifier contract
+    client.set_verifier(&hub.address, &vk_hash);
+    let result = client.try_scan(&1u32, &player1, &50, &zero_proof, &false);
+    assert_resistance_error(&result, Error::ProofVerificationFailed);
+
+    // Nothing was recorded for the failed scans
+    assert_eq!(client.get_current_turn(&1u32), player1);
+}
+
+#[test]
+#[cfg(feature = "local-verifier")]
+fn test_zk_proofs_required_after_admin_opt_in() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    client.start_game(
+        &1u32,
+        &player1,
+        &player2,
+        &100_0000000,
+        &100_0000000,
+        &fake_commitment(&env, 1),
+        &fake_commitment(&env, 2),
+    );
+    client.set_vk(&fake_vk(&env, 1, 16 + 5));
+    let plain_proof = Bytes::from_slice(&env, &bad_sumcheck_proof());
+    let zk_proof = Bytes::from_slice(&env, &bad_sumcheck_zk_proof());
+
+    // Plain and ZK proofs both reach the verifier by default
+    assert!(!client.requires_zk_proofs());
+    let result = client.try_scan(&1u32, &player1, &50, &plain_proof, &false);
+    assert_resistance_error(&result, Error::SumcheckRoundFailed);
+    let result = client.try_scan(&1u32, &player1, &50, &zk_proof, &false);
+    assert_resistance_error(&result, Error::SumcheckRoundFailed);
+
+    // Once the admin requires ZK, plain proofs are rejected before verification
+    let admin = client.get_admin();
+    client.set_require_zk_proofs(&true);
+    assert_eq!(env.auths()[0].0, admin);
+    assert_eq!(
+        env.events().all(),
+        [ProofPolicyChanged { require_zk: true }.to_xdr(&env, &client.address)]
+    );
+    assert!(client.requires_zk_proofs());
+    let result = client.try_scan(&1u32, &player1, &50, &plain_proof, &false);
+    assert_resistance_error(&result, Error::InvalidProof);
+    let result = client.try_scan(&1u32, &player1, &50, &zk_proof, &false);
+    assert_resistance_error(&result, Error::SumcheckRoundFailed);
+
+    // The same policy applies through the verifier contract
+    let verifier_id = env.register(ZkVerifierContract, ());
+    let verifier = ZkVerifierContractClient::new(&env, &verifier_id);
+    let vk_hash = verifier.register_vk(&fake_vk(&env, 1, 16 + 5));
+    client.set_verifier(&verifier_id, &vk_hash);
+    let result = client.try_scan(&1u32, &player1, &50, &plain_proof, &false);
+    assert_resistance_error(&result, Error::InvalidProof);
+    let result = client.try_scan(&1u32, &player1, &50, &zk_proof, &false);
+    assert_resistance_error(&result, Error::SumcheckRoundFailed);
+
+    client.set_require_zk_proofs(&false);
+    let result = client.try_scan(&1u32, &player1, &50, &plain_proof, &false);
+    assert_resistance_error(&result, Error::SumcheckRoundFailed);
+
+    // Nothing was recorded for the failed scans
+    assert_eq!(client.get_current_turn(&1u32), player1);
+}
+
+// ============================================================================
+// Public Input Binding Tests
+// ============================================================================
+
+#[test]
+fn test_scan_public_inputs_layout() {
+    let env = Env::default();
+    let commitment = fake_commitment(&env, 7);
+
+    let inputs = scan_public_inputs(&env, 42, 3, &commitment, 150, true);
+    assert_eq!(inputs.len(), 5 * 32);
+
+    let mut expected = [0u8; 160];
+    expected[28..32].copy_from_slice(&42u32.to_be_bytes()); // session_id
+    expected[60..64].copy_from_slice(&3u32.to_be_bytes()); // turn
+    expected[64..96].copy_from_slice(&commitment.to_array()); // bases_hash
+    expected[124..128].copy_from_slice(&150u32.to_be_bytes()); // target_star
+    expected[159] = 1; // is_base
+    assert_eq!(inputs, Bytes::from_array(&env, &expected));
+}
+
+#[test]
+fn test_scan_public_inputs_bound_to_session_and_turn() {
+    let env = Env::default();
+    let commitment = fake_commitment(&env, 7);
+
+    // Same commitment, star and result: only the session/turn differ
+    let base = scan_public_inputs(&env, 1, 0, &commitment, 50, false);
+    let other_session = scan_public_inputs(&env, 2, 0, &commitment, 50, false);
+    let other_turn = scan_public_inputs(&env, 1, 1, &commitment, 50, false);
+
+    assert_ne!(base, other_session);
+    assert_ne!(base, other_turn);
+}
+
+#[test]
+fn test_scan_public_inputs_decode() {
+    let env = Env::default();
+    let commitment = fake_commitment(&env, 7);
+    let bytes = bytes_to_vec(&scan_public_inputs(&env, 42, 3, &commitment, 150, true));
+
+    let mut reader = PublicInputsReader::new(&bytes).unwrap();
+    assert_eq!(reader.next_u32(), Ok(42));
+    assert_eq!(reader.next_u32(), Ok(3));
+    assert_eq!(reader.next_bytes32(&env), Ok(commitment));
+    assert_eq!(reader.next_u32(), Ok(150));
+    assert_eq!(reader.next_bool(), Ok(true));
+    assert_eq!(reader.finish(), Ok(()));
+}
+
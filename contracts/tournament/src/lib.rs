@@ -0,0 +1,965 @@
+#![no_std]
+
+//! # Tournament
+//!
+//! Runs single-elimination and Swiss tournaments on top of the Game Hub.
+//! Players pay an entry fee into a prize pool when they register. Once the
+//! organizer starts the tournament, each round's pairings are played as
+//! ordinary hub sessions on one of the hub's registered game contracts, with
+//! nothing staked on the individual matches.
+//!
+//! A match is opened through the game contract's own `start_game`, so both
+//! players still authorize their session. Games whose players commit to
+//! hidden state at the start of a session, like The Resistance, take the
+//! players' commitments through `start_match`. When the game reports the result to
+//! the hub through `end_game`, anyone can call `report_result` to copy it from
+//! the hub session into the tournament. The last result of a round pairs the
+//! next round, and the last result of the tournament pays out the pool.
+//!
+//! A single-elimination match that ends in a draw, and any match whose
+//! session is cancelled, is replayed under a fresh session id.
+//!
+//! Each match has a deadline. Once it passes without a result, for example
+//! because one player never authorizes the session or stops playing, the
+//! organizer can award the match to the other player with `forfeit_match`.
+//! The organizer can also call off a tournament at any point before it
+//! finishes, which refunds every entry fee.
+
+use soroban_sdk::{
+    contract, contracterror, contractevent, contractimpl, contracttype, token::TokenClient,
+    xdr::ToXdr, Address, Bytes, BytesN, Env, Vec,
+};
+
+use game_common::SessionStatus;
+
+pub use game_common::GameOutcome;
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    TournamentNotFound = 1,
+    InvalidConfig = 2,
+    GameNotRegistered = 3,
+    RegistrationClosed = 4,
+    AlreadyRegistered = 5,
+    TournamentFull = 6,
+    NotEnoughEntrants = 7,
+    NotInProgress = 8,
+    MatchNotFound = 9,
+    MatchAlreadyStarted = 10,
+    MatchNotStarted = 11,
+    SessionNotEnded = 12,
+    MatchDeadlineNotPassed = 13,
+    NotInMatch = 14,
+    MatchAlreadyFinished = 15,
+    TournamentOver = 16,
+}
+
+// ============================================================================
+// Events
+// ============================================================================
+
+#[contractevent]
+pub struct TournamentCreated {
+    pub tournament_id: u32,
+    pub organizer: Address,
+    pub game_id: Address,
+}
+
+#[contractevent]
+pub struct RoundStarted {
+    pub tournament_id: u32,
+    pub round: u32,
+}
+
+#[contractevent]
+pub struct MatchForfeited {
+    pub tournament_id: u32,
+    pub round: u32,
+    pub match_index: u32,
+    pub winner: Address,
+}
+
+#[contractevent]
+pub struct TournamentFinished {
+    pub tournament_id: u32,
+    pub winner: Address,
+}
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Format {
+    /// Losers are knocked out until one player remains
+    SingleElimination,
+    /// Everyone plays the given number of rounds against players on a
+    /// similar score; the highest score wins
+    Swiss(u32),
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TournamentStatus {
+    /// Accepting entrants
+    Registration,
+    /// Rounds are being played
+    InProgress,
+    /// Prizes have been paid
+    Finished,
+    /// Called off by the organizer; entry fees were refunded
+    Cancelled,
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum MatchStatus {
+    /// Paired, waiting for `start_match`
+    Pending,
+    /// Session is open on the game contract
+    Started,
+    /// Result copied from the hub
+    Finished(GameOutcome),
+}
+
+/// A pairing in the current round
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Match {
+    pub player1: Address,
+    pub player2: Address,
+    /// Hub session the match is played under
+    pub session_id: u32,
+    /// Bumped each time the match is replayed or its session id is taken
+    pub attempt: u32,
+    pub status: MatchStatus,
+    /// Ledger sequence after which the organizer may award a forfeit
+    pub deadline: u32,
+}
+
+/// A registered player and their progress through the tournament
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Entrant {
+    pub player: Address,
+    /// 2 per win or bye, 1 per draw
+    pub score: u32,
+    /// Last round the player took part in (the champion's is one past the final)
+    pub last_round: u32,
+    pub had_bye: bool,
+    pub opponents: Vec<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tournament {
+    pub organizer: Address,
+    /// Registered game contract every match is played on
+    pub game_id: Address,
+    /// Hub stake token that entry fees and prizes are paid in
+    pub token: Address,
+    pub format: Format,
+    pub entry_fee: i128,
+    pub max_entrants: u32,
+    /// Share of the pool for each finishing place, in basis points
+    pub prize_bps: Vec<u32>,
+    pub status: TournamentStatus,
+    /// Current round (0 until the tournament starts)
+    pub round: u32,
+    /// In registration order
+    pub entrants: Vec<Entrant>,
+    /// Pairings for the current round
+    pub matches: Vec<Match>,
+    /// Player sitting out the current round, if the field is odd
+    pub bye: Option<Address>,
+    /// Final placings, filled in when the tournament finishes
+    pub standings: Vec<Address>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Tournament(u32),
+    TournamentCount,
+}
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Basis-point denominator (100% = 10_000 bps)
+pub const MAX_BPS: u32 = 10_000;
+
+/// Largest field a tournament may have; keeps pairing within budget
+pub const MAX_ENTRANTS: u32 = 64;
+
+/// Score for a win or a bye
+pub const WIN_POINTS: u32 = 2;
+
+/// Score for a draw
+pub const DRAW_POINTS: u32 = 1;
+
+/// Ledgers a match may go without a result before it can be forfeited, from
+/// the start of its round or its last replay (7 days, ~5 seconds per ledger)
+pub const MATCH_DEADLINE_LEDGERS: u32 = 120_960;
+
+/// TTL for tournament storage (30 days in ledgers, ~5 seconds per ledger)
+const TOURNAMENT_TTL_LEDGERS: u32 = 518_400;
+
+/// Instance TTL bump threshold (1 day in ledgers)
+const INSTANCE_TTL_THRESHOLD: u32 = 17_280;
+
+// ============================================================================
+// Contract Definition
+// ============================================================================
+
+#[contract]
+pub struct TournamentContract;
+
+#[contractimpl]
+impl TournamentContract {
+    /// Initialize the contract with GameHub address and admin
+    ///
+    /// # Arguments
+    /// * `admin` - Admin address (can upgrade contract)
+    /// * `game_hub` - Address of the GameHub contract
+    pub fn __constructor(env: Env, admin: Address, game_hub: Address) {
+        game_common::init(&env, &admin, &game_hub);
+    }
+
+    // ========================================================================
+    // Organizer Functions
+    // ========================================================================
+
+    /// Create a tournament and open registration.
+    ///
+    /// Entry fees and prizes are paid in the hub's stake token. Whatever the
+    /// prize split leaves over, including rounding, goes to the winner.
+    ///
+    /// **Auth:** Requires authorization from `organizer`.
+    ///
+    /// # Arguments
+    /// * `organizer` - Address that starts (or cancels) the tournament
+    /// * `game_id` - Game contract registered on the hub to play matches on
+    /// * `format` - Single elimination, or Swiss with a number of rounds
+    /// * `entry_fee` - Paid into the pool by each entrant (0 = free)
+    /// * `max_entrants` - Field size limit, between 2 and `MAX_ENTRANTS`
+    /// * `prize_bps` - Share of the pool for 1st, 2nd, ... place
+    ///
+    /// # Returns
+    /// * `u32` - The new tournament's id
+    pub fn create_tournament(
+        env: Env,
+        organizer: Address,
+        game_id: Address,
+        format: Format,
+        entry_fee: i128,
+        max_entrants: u32,
+        prize_bps: Vec<u32>,
+    ) -> Result<u32, Error> {
+        organizer.require_auth();
+
+        if entry_fee < 0 || !(2..=MAX_ENTRANTS).contains(&max_entrants) {
+            return Err(Error::InvalidConfig);
+        }
+        if format == Format::Swiss(0) {
+            return Err(Error::InvalidConfig);
+        }
+        if prize_bps.len() > max_entrants || prize_bps.iter().any(|bps| bps > MAX_BPS) {
+            return Err(Error::InvalidConfig);
+        }
+        if prize_bps.iter().sum::<u32>() > MAX_BPS {
+            return Err(Error::InvalidConfig);
+        }
+
+        let hub = game_common::hub_client(&env);
+        if !hub.is_game(&game_id) {
+            return Err(Error::GameNotRegistered);
+        }
+
+        let tournament = Tournament {
+            organizer: organizer.clone(),
+            game_id: game_id.clone(),
+            token: hub.get_token(),
+            format,
+            entry_fee,
+            max_entrants,
+            prize_bps,
+            status: TournamentStatus::Registration,
+            round: 0,
+            entrants: Vec::new(&env),
+            matches: Vec::new(&env),
+            bye: None,
+            standings: Vec::new(&env),
+        };
+
+        let tournament_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TournamentCount)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::TournamentCount, &(tournament_id + 1));
+        Self::save(&env, tournament_id, &tournament);
+
+        TournamentCreated {
+            tournament_id,
+            organizer,
+            game_id,
+        }
+        .publish(&env);
+
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_TTL_THRESHOLD, TOURNAMENT_TTL_LEDGERS);
+
+        Ok(tournament_id)
+    }
+
+    /// Close registration and pair the first round.
+    ///
+    /// **Auth:** Requires authorization from the organizer.
+    pub fn start_tournament(env: Env, tournament_id: u32) -> Result<(), Error> {
+        let mut tournament = Self::load(&env, tournament_id)?;
+        tournament.organizer.require_auth();
+
+        if tournament.status != TournamentStatus::Registration {
+            return Err(Error::RegistrationClosed);
+        }
+        if tournament.entrants.len() < 2 {
+            return Err(Error::NotEnoughEntrants);
+        }
+
+        tournament.status = TournamentStatus::InProgress;
+        let field = Self::players(&env, &tournament.entrants);
+        Self::start_round(&env, tournament_id, &mut tournament, field);
+
+        Self::save(&env, tournament_id, &tournament);
+        Ok(())
+    }
+
+    /// Call off a tournament that has not finished and refund every entry fee.
+    ///
+    /// Matches still open on the game contract are left to end or expire
+    /// there; nothing is staked on them.
+    ///
+    /// **Auth:** Requires authorization from the organizer.
+    pub fn cancel_tournament(env: Env, tournament_id: u32) -> Result<(), Error> {
+        let mut tournament = Self::load(&env, tournament_id)?;
+        tournament.organizer.require_auth();
+
+        if !matches!(
+            tournament.status,
+            TournamentStatus::Registration | TournamentStatus::InProgress
+        ) {
+            return Err(Error::TournamentOver);
+        }
+
+        if tournament.entry_fee > 0 {
+            let token = TokenClient::new(&env, &tournament.token);
+            let contract = env.current_contract_address();
+            for entrant in tournament.entrants.iter() {
+                token.transfer(&contract, &entrant.player, &tournament.entry_fee);
+            }
+        }
+
+        tournament.status = TournamentStatus::Cancelled;
+        tournament.matches = Vec::new(&env);
+        tournament.bye = None;
+        Self::save(&env, tournament_id, &tournament);
+        Ok(())
+    }
+
+    /// Award a match that is past its deadline to one of its players.
+    ///
+    /// The forfeit is recorded like a win, so it can finish the round or the
+    /// tournament. A session still open for the match no longer counts.
+    ///
+    /// **Auth:** Requires authorization from the organizer.
+    ///
+    /// # Arguments
+    /// * `tournament_id` - The tournament
+    /// * `match_index` - Index of the match in the current round
+    /// * `winner` - The player the match is awarded to
+    pub fn forfeit_match(
+        env: Env,
+        tournament_id: u32,
+        match_index: u32,
+        winner: Address,
+    ) -> Result<(), Error> {
+        let mut tournament = Self::load(&env, tournament_id)?;
+        tournament.organizer.require_auth();
+
+        if tournament.status != TournamentStatus::InProgress {
+            return Err(Error::NotInProgress);
+        }
+        let game_match = tournament
+            .matches
+            .get(match_index)
+            .ok_or(Error::MatchNotFound)?;
+        if matches!(game_match.status, MatchStatus::Finished(_)) {
+            return Err(Error::MatchAlreadyFinished);
+        }
+        if env.ledger().sequence() <= game_match.deadline {
+            return Err(Error::MatchDeadlineNotPassed);
+        }
+
+        let outcome = if winner == game_match.player1 {
+            GameOutcome::Player1
+        } else if winner == game_match.player2 {
+            GameOutcome::Player2
+        } else {
+            return Err(Error::NotInMatch);
+        };
+
+        MatchForfeited {
+            tournament_id,
+            round: tournament.round,
+            match_index,
+            winner,
+        }
+        .publish(&env);
+
+        Self::record_outcome(&env, tournament_id, &mut tournament, match_index, outcome);
+        Self::save(&env, tournament_id, &tournament);
+        Ok(())
+    }
+
+    // ========================================================================
+    // Player Functions
+    // ========================================================================
+
+    /// Enter a tournament, paying the entry fee into the prize pool.
+    ///
+    /// **Auth:** Requires authorization from `player`.
+    pub fn register(env: Env, tournament_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+
+        let mut tournament = Self::load(&env, tournament_id)?;
+        if tournament.status != TournamentStatus::Registration {
+            return Err(Error::RegistrationClosed);
+        }
+        if Self::entrant_index(&tournament, &player).is_some() {
+            return Err(Error::AlreadyRegistered);
+        }
+        if tournament.entrants.len() >= tournament.max_entrants {
+            return Err(Error::TournamentFull);
+        }
+
+        if tournament.entry_fee > 0 {
+            TokenClient::new(&env, &tournament.token).transfer(
+                &player,
+                env.current_contract_address(),
+                &tournament.entry_fee,
+            );
+        }
+
+        tournament.entrants.push_back(Entrant {
+            player,
+            score: 0,
+            last_round: 0,
+            had_bye: false,
+            opponents: Vec::new(&env),
+        });
+        Self::save(&env, tournament_id, &tournament);
+        Ok(())
+    }
+
+    /// Open the session for a pending match on the tournament's game.
+    ///
+    /// Anyone may call this, but the game contract's `start_game` still
+    /// requires both players to authorize the session (with zero points and,
+    /// for games that take them, their commitment).
+    ///
+    /// # Arguments
+    /// * `tournament_id` - The tournament
+    /// * `match_index` - Index of the match in the current round
+    /// * `commitments` - Player 1's and player 2's commitments, for games
+    ///   whose `start_game` takes them; `None` for every other game
+    pub fn start_match(
+        env: Env,
+        tournament_id: u32,
+        match_index: u32,
+        commitments: Option<(BytesN<32>, BytesN<32>)>,
+    ) -> Result<(), Error> {
+        let mut tournament = Self::load(&env, tournament_id)?;
+        if tournament.status != TournamentStatus::InProgress {
+            return Err(Error::NotInProgress);
+        }
+        let mut game_match = tournament
+            .matches
+            .get(match_index)
+            .ok_or(Error::MatchNotFound)?;
+        if game_match.status != MatchStatus::Pending {
+            return Err(Error::MatchAlreadyStarted);
+        }
+
+        // Skip ids already taken on the hub, e.g. by someone front-running the match
+        let hub = game_common::hub_client(&env);
+        while hub.try_get_session(&game_match.session_id).is_ok() {
+            game_match.attempt += 1;
+            game_match.session_id = Self::session_id_for(
+                &env,
+                tournament_id,
+                tournament.round,
+                match_index,
+                game_match.attempt,
+            );
+        }
+
+        game_match.status = MatchStatus::Started;
+        tournament.matches.set(match_index, game_match.clone());
+        Self::save(&env, tournament_id, &tournament);
+
+        game_common::start_game(
+            &env,
+            &tournament.game_id,
+            game_match.session_id,
+            &game_match.player1,
+            &game_match.player2,
+            0,
+            &commitments,
+        );
+        Ok(())
+    }
+
+    /// Record the result of a started match from its hub session.
+    ///
+    /// Finishing the last match of a round pairs the next round, or finishes
+    /// the tournament and pays out the pool. Anyone may call this.
+    ///
+    /// # Arguments
+    /// * `tournament_id` - The tournament
+    /// * `match_index` - Index of the match in the current round
+    pub fn report_result(env: Env, tournament_id: u32, match_index: u32) -> Result<(), Error> {
+        let mut tournament = Self::load(&env, tournament_id)?;
+        if tournament.status != TournamentStatus::InProgress {
+            return Err(Error::NotInProgress);
+        }
+        let mut game_match = tournament
+            .matches
+            .get(match_index)
+            .ok_or(Error::MatchNotFound)?;
+        if game_match.status != MatchStatus::Started {
+            return Err(Error::MatchNotStarted);
+        }
+
+        let session = game_common::hub_client(&env).get_session(&game_match.session_id);
+        let SessionStatus::Ended(outcome) = session.status else {
+            return Err(Error::SessionNotEnded);
+        };
+
+        let replay = match outcome {
+            GameOutcome::Cancelled => true,
+            GameOutcome::Draw => tournament.format == Format::SingleElimination,
+            GameOutcome::Player1 | GameOutcome::Player2 => false,
+        };
+        if replay {
+            game_match.attempt += 1;
+            game_match.session_id = Self::session_id_for(
+                &env,
+                tournament_id,
+                tournament.round,
+                match_index,
+                game_match.attempt,
+            );
+            game_match.status = MatchStatus::Pending;
+            game_match.deadline = env.ledger().sequence() + MATCH_DEADLINE_LEDGERS;
+            tournament.matches.set(match_index, game_match);
+            Self::save(&env, tournament_id, &tournament);
+            return Ok(());
+        }
+
+        Self::record_outcome(&env, tournament_id, &mut tournament, match_index, outcome);
+        Self::save(&env, tournament_id, &tournament);
+        Ok(())
+    }
+
+    // ========================================================================
+    // Query Functions
+    // ========================================================================
+
+    /// Get a tournament with its entrants, current round and standings.
+    pub fn get_tournament(env: Env, tournament_id: u32) -> Result<Tournament, Error> {
+        Self::load(&env, tournament_id)
+    }
+
+    // ========================================================================
+    // Bracket Management
+    // ========================================================================
+
+    /// Score a decided match, then pair the next round or finish the
+    /// tournament if it was the last of its round.
+    fn record_outcome(
+        env: &Env,
+        tournament_id: u32,
+        tournament: &mut Tournament,
+        match_index: u32,
+        outcome: GameOutcome,
+    ) {
+        let mut game_match = tournament.matches.get_unchecked(match_index);
+        match outcome {
+            GameOutcome::Player1 => Self::add_score(tournament, &game_match.player1, WIN_POINTS),
+            GameOutcome::Player2 => Self::add_score(tournament, &game_match.player2, WIN_POINTS),
+            _ => {
+                Self::add_score(tournament, &game_match.player1, DRAW_POINTS);
+                Self::add_score(tournament, &game_match.player2, DRAW_POINTS);
+            }
+        }
+        game_match.status = MatchStatus::Finished(outcome);
+        tournament.matches.set(match_index, game_match);
+
+        let round_over = tournament
+            .matches
+            .iter()
+            .all(|m| matches!(m.status, MatchStatus::Finished(_)));
+        if round_over {
+            Self::advance(env, tournament_id, tournament);
+        }
+    }
+
+    /// Pair the next round, or finish the tournament once it is decided.
+    fn advance(env: &Env, tournament_id: u32, tournament: &mut Tournament) {
+        match tournament.format {
+            Format::SingleElimination => {
+                // Last round's bye goes first so the next bye falls to someone else
+                let mut winners: Vec<Address> = Vec::new(env);
+                if let Some(bye) = tournament.bye.clone() {
+                    winners.push_back(bye);
+                }
+                for game_match in tournament.matches.iter() {
+                    match game_match.status {
+                        MatchStatus::Finished(GameOutcome::Player1) => {
+                            winners.push_back(game_match.player1)
+                        }
+                        _ => winners.push_back(game_match.player2),
+                    }
+                }
+
+                if winners.len() == 1 {
+                    let champion = winners.get_unchecked(0);
+                    let index = Self::entrant_index(tournament, &champion).unwrap();
+                    let mut entrant = tournament.entrants.get_unchecked(index);
+                    entrant.last_round = tournament.round + 1;
+                    tournament.entrants.set(index, entrant);
+                    Self::finish(env, tournament_id, tournament);
+                } else {
+                    Self::start_round(env, tournament_id, tournament, winners);
+                }
+            }
+            Format::Swiss(rounds) => {
+                if tournament.round < rounds {
+                    let field = Self::players(env, &Self::ranked(env, tournament));
+                    Self::start_round(env, tournament_id, tournament, field);
+                } else {
+                    Self::finish(env, tournament_id, tournament);
+                }
+            }
+        }
+    }
+
+    /// Pair `field` for a new round. Single elimination pairs neighbours in
+    /// order; Swiss expects `field` ranked and avoids rematches where it can.
+    fn start_round(
+        env: &Env,
+        tournament_id: u32,
+        tournament: &mut Tournament,
+        field: Vec<Address>,
+    ) {
+        tournament.round += 1;
+        let round = tournament.round;
+        let (pairs, bye) = match tournament.format {
+            Format::SingleElimination => Self::elimination_pairings(env, field),
+            Format::Swiss(_) => Self::swiss_pairings(env, tournament, field),
+        };
+
+        let deadline = env.ledger().sequence() + MATCH_DEADLINE_LEDGERS;
+        let mut matches = Vec::new(env);
+        for (player1, player2) in pairs.iter() {
+            Self::record_pairing(tournament, &player1, &player2);
+            Self::record_pairing(tournament, &player2, &player1);
+            matches.push_back(Match {
+                session_id: Self::session_id_for(env, tournament_id, round, matches.len(), 0),
+                player1,
+                player2,
+                attempt: 0,
+                status: MatchStatus::Pending,
+                deadline,
+            });
+        }
+
+        if let Some(player) = &bye {
+            let index = Self::entrant_index(tournament, player).unwrap();
+            let mut entrant = tournament.entrants.get_unchecked(index);
+            entrant.last_round = round;
+            entrant.had_bye = true;
+            entrant.score += WIN_POINTS;
+            tournament.entrants.set(index, entrant);
+        }
+
+        tournament.matches = matches;
+        tournament.bye = bye;
+
+        RoundStarted {
+            tournament_id,
+            round,
+        }
+        .publish(env);
+    }
+
+    /// Pair players in order; the odd one out at the end gets the bye.
+    fn elimination_pairings(
+        env: &Env,
+        mut field: Vec<Address>,
+    ) -> (Vec<(Address, Address)>, Option<Address>) {
+        let bye = if field.len() % 2 == 1 {
+            field.pop_back()
+        } else {
+            None
+        };
+
+        let mut pairs = Vec::new(env);
+        for i in (0..field.len()).step_by(2) {
+            pairs.push_back((field.get_unchecked(i), field.get_unchecked(i + 1)));
+        }
+        (pairs, bye)
+    }
+
+    /// Give the bye to the lowest-ranked player who has not had one, then pair
+    /// each player from the top with the highest-ranked player they have not
+    /// met yet (or the next player, if they have met everyone left).
+    fn swiss_pairings(
+        env: &Env,
+        tournament: &Tournament,
+        mut field: Vec<Address>,
+    ) -> (Vec<(Address, Address)>, Option<Address>) {
+        let mut bye = None;
+        if field.len() % 2 == 1 {
+            let last = field.len() - 1;
+            let index = (0..field.len())
+                .rev()
+                .find(|&i| !Self::entrant(tournament, &field.get_unchecked(i)).had_bye)
+                .unwrap_or(last);
+            bye = Some(field.get_unchecked(index));
+            field.remove(index);
+        }
+
+        let mut pairs = Vec::new(env);
+        while let Some(player) = field.pop_front() {
+            let opponents = Self::entrant(tournament, &player).opponents;
+            let index = (0..field.len())
+                .find(|&i| !opponents.contains(field.get_unchecked(i)))
+                .unwrap_or(0);
+            pairs.push_back((player, field.get_unchecked(index)));
+            field.remove(index);
+        }
+        (pairs, bye)
+    }
+
+    /// Rank the tournament's players for pairing or prizes, keeping
+    /// registration order between equals.
+    fn ranked(env: &Env, tournament: &Tournament) -> Vec<Entrant> {
+        let key = |entrant: &Entrant| match tournament.format {
+            Format::SingleElimination => entrant.last_round,
+            Format::Swiss(_) => entrant.score,
+        };
+
+        let mut remaining = tournament.entrants.clone();
+        let mut ranked = Vec::new(env);
+        while !remaining.is_empty() {
+            let mut best = 0;
+            for i in 1..remaining.len() {
+                if key(&remaining.get_unchecked(i)) > key(&remaining.get_unchecked(best)) {
+                    best = i;
+                }
+            }
+            ranked.push_back(remaining.get_unchecked(best));
+            remaining.remove(best);
+        }
+        ranked
+    }
+
+    /// Record the final standings and pay each prize place its share of the pool.
+    fn finish(env: &Env, tournament_id: u32, tournament: &mut Tournament) {
+        tournament.standings = Self::players(env, &Self::ranked(env, tournament));
+        tournament.status = TournamentStatus::Finished;
+        tournament.matches = Vec::new(env);
+        tournament.bye = None;
+
+        let winner = tournament.standings.get_unchecked(0);
+        let pool = tournament.entry_fee * tournament.entrants.len() as i128;
+        if pool > 0 {
+            let token = TokenClient::new(env, &tournament.token);
+            let contract = env.current_contract_address();
+            let mut paid = 0;
+            for (place, bps) in tournament.prize_bps.iter().enumerate() {
+                let prize = pool * bps as i128 / MAX_BPS as i128;
+                if prize > 0 {
+                    let player = tournament.standings.get_unchecked(place as u32);
+                    token.transfer(&contract, &player, &prize);
+                    paid += prize;
+                }
+            }
+            if pool > paid {
+                token.transfer(&contract, &winner, &(pool - paid));
+            }
+        }
+
+        TournamentFinished {
+            tournament_id,
+            winner,
+        }
+        .publish(env);
+    }
+
+    fn players(env: &Env, entrants: &Vec<Entrant>) -> Vec<Address> {
+        let mut players = Vec::new(env);
+        for entrant in entrants.iter() {
+            players.push_back(entrant.player);
+        }
+        players
+    }
+
+    fn record_pairing(tournament: &mut Tournament, player: &Address, opponent: &Address) {
+        let index = Self::entrant_index(tournament, player).unwrap();
+        let mut entrant = tournament.entrants.get_unchecked(index);
+        entrant.last_round = tournament.round;
+        entrant.opponents.push_back(opponent.clone());
+        tournament.entrants.set(index, entrant);
+    }
+
+    fn add_score(tournament: &mut Tournament, player: &Address, points: u32) {
+        let index = Self::entrant_index(tournament, player).unwrap();
+        let mut entrant = tournament.entrants.get_unchecked(index);
+        entrant.score += points;
+        tournament.entrants.set(index, entrant);
+    }
+
+    fn entrant(tournament: &Tournament, player: &Address) -> Entrant {
+        let index = Self::entrant_index(tournament, player).unwrap();
+        tournament.entrants.get_unchecked(index)
+    }
+
+    fn entrant_index(tournament: &Tournament, player: &Address) -> Option<u32> {
+        tournament
+            .entrants
+            .iter()
+            .position(|e| e.player == *player)
+            .map(|i| i as u32)
+    }
+
+    /// Hub session id for a match, derived from this contract, the match's
+    /// position and the attempt so that ids are unlikely to collide with
+    /// sessions opened elsewhere.
+    fn session_id_for(
+        env: &Env,
+        tournament_id: u32,
+        round: u32,
+        match_index: u32,
+        attempt: u32,
+    ) -> u32 {
+        let mut data = Bytes::new(env);
+        data.append(&env.current_contract_address().to_xdr(env));
+        for value in [tournament_id, round, match_index, attempt] {
+            data.extend_from_array(&value.to_be_bytes());
+        }
+        let hash = env.crypto().keccak256(&data).to_array();
+        u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]])
+    }
+
+    fn load(env: &Env, tournament_id: u32) -> Result<Tournament, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Tournament(tournament_id))
+            .ok_or(Error::TournamentNotFound)
+    }
+
+    fn save(env: &Env, tournament_id: u32, tournament: &Tournament) {
+        let key = DataKey::Tournament(tournament_id);
+        env.storage().persistent().set(&key, tournament);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, TOURNAMENT_TTL_LEDGERS, TOURNAMENT_TTL_LEDGERS);
+    }
+
+
+    // ========================================================================
+    // Admin Functions
+    // ========================================================================
+
+    /// Get the current admin address
+    ///
+    /// # Returns
+    /// * `Address` - The admin address
+    pub fn get_admin(env: Env) -> Address {
+        game_common::get_admin(&env)
+    }
+
+    /// Get the admin proposed by `propose_admin`, if it has not accepted yet
+    ///
+    /// # Returns
+    /// * `Option<Address>` - The pending admin address
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        game_common::get_pending_admin(&env)
+    }
+
+    /// Propose a new admin address
+    ///
+    /// The current admin keeps control until the new admin calls
+    /// `accept_admin`, so a mistyped address cannot lock the contract.
+    ///
+    /// # Arguments
+    /// * `new_admin` - The proposed admin address
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        game_common::propose_admin(&env, &new_admin);
+    }
+
+    /// Accept a pending admin proposal (must be authorized by the pending admin)
+    pub fn accept_admin(env: Env) {
+        game_common::accept_admin(&env);
+    }
+
+    /// Withdraw a pending admin proposal
+    pub fn cancel_admin_proposal(env: Env) {
+        game_common::cancel_admin_proposal(&env);
+    }
+
+    /// Get the current GameHub contract address
+    ///
+    /// # Returns
+    /// * `Address` - The GameHub contract address
+    pub fn get_hub(env: Env) -> Address {
+        game_common::get_hub(&env)
+    }
+
+    /// Set a new GameHub contract address
+    ///
+    /// # Arguments
+    /// * `new_hub` - The new GameHub contract address
+    pub fn set_hub(env: Env, new_hub: Address) {
+        game_common::set_hub(&env, &new_hub);
+    }
+
+    /// Update the contract WASM hash (upgrade contract)
+    ///
+    /// # Arguments
+    /// * `new_wasm_hash` - The hash of the new WASM binary
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        game_common::upgrade(&env, new_wasm_hash);
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,800 @@
+#![cfg(test)]
+
+// Unit tests for tournament registration, pairing and payouts, driven against
+// the real game hub. Match results come from a scripted game contract so each
+// test can choose who wins; integration tests play through dice-duel and
+// open a match on the-resistance.
+
+use crate::{
+    Error, Format, MatchForfeited, MatchStatus, TournamentContract, TournamentContractClient,
+    TournamentFinished, TournamentStatus, DRAW_POINTS, MATCH_DEADLINE_LEDGERS, WIN_POINTS,
+};
+use dice_duel::{DiceDuelContract, DiceDuelContractClient};
+use game_common::{AdminChanged, HubChanged};
+use game_hub::{GameHub, GameHubClient};
+use soroban_sdk::testutils::{Address as _, Events as _, Ledger as _};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{contract, contractimpl, vec, Address, Bytes, BytesN, Env, Event as _, Vec};
+use the_resistance::{TheResistanceContract, TheResistanceContractClient};
+
+// ============================================================================
+// Scripted Game
+// ============================================================================
+
+/// Minimal hub-aware game whose results are reported by the test
+#[contract]
+pub struct ScriptedGame;
+
+#[contractimpl]
+impl ScriptedGame {
+    pub fn __constructor(env: Env, game_hub: Address) {
+        env.storage().instance().set(&0u32, &game_hub);
+    }
+
+    pub fn start_game(
+        env: Env,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+    ) {
+        Self::hub(&env).start_game(
+            &env.current_contract_address(),
+            &session_id,
+            &player1,
+            &player2,
+            &player1_points,
+            &player2_points,
+        );
+    }
+
+    pub fn finish(env: Env, session_id: u32, outcome: game_hub::GameOutcome) {
+        Self::hub(&env).end_game(&session_id, &outcome);
+    }
+
+    fn hub(env: &Env) -> GameHubClient<'_> {
+        let hub: Address = env.storage().instance().get(&0u32).unwrap();
+        GameHubClient::new(env, &hub)
+    }
+}
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+const STARTING_BALANCE: i128 = 1000_0000000;
+const ENTRY_FEE: i128 = 100_0000000;
+
+struct Setup {
+    env: Env,
+    hub: GameHubClient<'static>,
+    token: TokenClient<'static>,
+    game: ScriptedGameClient<'static>,
+    tournaments: TournamentContractClient<'static>,
+    organizer: Address,
+    players: Vec<Address>,
+}
+
+fn setup_test(player_count: u32) -> Setup {
+    let env = Env::default();
+    // Players authorize their sessions and fees as sub-invocations
+    env.mock_all_auths_allowing_non_root_auth();
+
+    env.ledger().set(soroban_sdk::testutils::LedgerInfo {
+        timestamp: 1441065600,
+        protocol_version: 25,
+        sequence_number: 100,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_ttl: u32::MAX / 2,
+        min_persistent_entry_ttl: u32::MAX / 2,
+        max_entry_ttl: u32::MAX / 2,
+    });
+
+    let token_admin = Address::generate(&env);
+    let sac = env.register_stellar_asset_contract_v2(token_admin);
+    let token = TokenClient::new(&env, &sac.address());
+    let token_admin_client = StellarAssetClient::new(&env, &sac.address());
+
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let hub_addr = env.register(GameHub, (&admin, &sac.address(), &treasury, 0u32));
+    let hub = GameHubClient::new(&env, &hub_addr);
+
+    let game_addr = env.register(ScriptedGame, (&hub_addr,));
+    let game = ScriptedGameClient::new(&env, &game_addr);
+    hub.add_game(&game_addr);
+
+    let tournament_addr = env.register(TournamentContract, (&admin, &hub_addr));
+    let tournaments = TournamentContractClient::new(&env, &tournament_addr);
+
+    let organizer = Address::generate(&env);
+    let mut players = Vec::new(&env);
+    for _ in 0..player_count {
+        let player = Address::generate(&env);
+        token_admin_client.mint(&player, &STARTING_BALANCE);
+        players.push_back(player);
+    }
+
+    Setup {
+        env,
+        hub,
+        token,
+        game,
+        tournaments,
+        organizer,
+        players,
+    }
+}
+
+/// Create a tournament on the scripted game and register every player
+fn create_and_fill(s: &Setup, format: Format, prize_bps: Vec<u32>) -> u32 {
+    let tournament_id = s.tournaments.create_tournament(
+        &s.organizer,
+        &s.game.address,
+        &format,
+        &ENTRY_FEE,
+        &s.players.len().max(2),
+        &prize_bps,
+    );
+    for player in s.players.iter() {
+        s.tournaments.register(&tournament_id, &player);
+    }
+    tournament_id
+}
+
+/// Start a match, report `outcome` through the hub and record it
+fn play_match(s: &Setup, tournament_id: u32, match_index: u32, outcome: game_hub::GameOutcome) {
+    s.tournaments.start_match(&tournament_id, &match_index, &None);
+    let session_id = s
+        .tournaments
+        .get_tournament(&tournament_id)
+        .matches
+        .get_unchecked(match_index)
+        .session_id;
+    s.game.finish(&session_id, &outcome);
+    s.tournaments.report_result(&tournament_id, &match_index);
+}
+
+/// The pairings of the current round
+fn pairings(s: &Setup, tournament_id: u32) -> Vec<(Address, Address)> {
+    let mut pairs = Vec::new(&s.env);
+    for m in s.tournaments.get_tournament(&tournament_id).matches.iter() {
+        pairs.push_back((m.player1, m.player2));
+    }
+    pairs
+}
+
+/// Assert that a Result contains a specific tournament error
+fn assert_tournament_error<T, E>(
+    result: &Result<Result<T, E>, Result<Error, soroban_sdk::InvokeError>>,
+    expected_error: Error,
+) {
+    match result {
+        Err(Ok(actual_error)) => {
+            assert_eq!(
+                *actual_error, expected_error,
+                "Expected error {:?} (code {}), but got {:?} (code {})",
+                expected_error, expected_error as u32, actual_error, *actual_error as u32
+            );
+        }
+        Err(Err(_invoke_error)) => {
+            panic!(
+                "Expected contract error {:?} (code {}), but got invocation error",
+                expected_error, expected_error as u32
+            );
+        }
+        Ok(Err(_conv_error)) => {
+            panic!(
+                "Expected contract error {:?} (code {}), but got conversion error",
+                expected_error, expected_error as u32
+            );
+        }
+        Ok(Ok(_)) => {
+            panic!(
+                "Expected error {:?} (code {}), but operation succeeded",
+                expected_error, expected_error as u32
+            );
+        }
+    }
+}
+
+// ============================================================================
+// Registration Tests
+// ============================================================================
+
+#[test]
+fn test_register_pools_entry_fees() {
+    let s = setup_test(3);
+    let tournament_id = create_and_fill(&s, Format::SingleElimination, vec![&s.env, 10_000]);
+
+    let tournament = s.tournaments.get_tournament(&tournament_id);
+    assert_eq!(tournament.status, TournamentStatus::Registration);
+    assert_eq!(tournament.entrants.len(), 3);
+    assert_eq!(tournament.token, s.hub.get_token());
+    assert_eq!(s.token.balance(&s.tournaments.address), 3 * ENTRY_FEE);
+    for player in s.players.iter() {
+        assert_eq!(s.token.balance(&player), STARTING_BALANCE - ENTRY_FEE);
+    }
+}
+
+#[test]
+fn test_register_rejects_duplicate_and_full() {
+    let s = setup_test(2);
+    let tournament_id = create_and_fill(&s, Format::SingleElimination, vec![&s.env]);
+
+    let result = s
+        .tournaments
+        .try_register(&tournament_id, &s.players.get_unchecked(0));
+    assert_tournament_error(&result, Error::AlreadyRegistered);
+
+    let latecomer = Address::generate(&s.env);
+    let result = s.tournaments.try_register(&tournament_id, &latecomer);
+    assert_tournament_error(&result, Error::TournamentFull);
+}
+
+#[test]
+fn test_create_rejects_invalid_config() {
+    let s = setup_test(0);
+    let env = &s.env;
+    let create = |game: &Address, format: Format, fee: i128, max: u32, prizes: Vec<u32>| {
+        s.tournaments
+            .try_create_tournament(&s.organizer, game, &format, &fee, &max, &prizes)
+    };
+
+    let game = s.game.address.clone();
+    let single = Format::SingleElimination;
+    assert_tournament_error(
+        &create(&game, single, -1, 8, vec![env]),
+        Error::InvalidConfig,
+    );
+    assert_tournament_error(
+        &create(&game, single, 0, 1, vec![env]),
+        Error::InvalidConfig,
+    );
+    assert_tournament_error(
+        &create(&game, single, 0, 65, vec![env]),
+        Error::InvalidConfig,
+    );
+    assert_tournament_error(
+        &create(&game, Format::Swiss(0), 0, 8, vec![env]),
+        Error::InvalidConfig,
+    );
+    assert_tournament_error(
+        &create(&game, single, 0, 8, vec![env, 6000, 5000]),
+        Error::InvalidConfig,
+    );
+    assert_tournament_error(
+        &create(&game, single, 0, 2, vec![env, 5000, 3000, 2000]),
+        Error::InvalidConfig,
+    );
+
+    let unregistered = Address::generate(env);
+    assert_tournament_error(
+        &create(&unregistered, single, 0, 8, vec![env]),
+        Error::GameNotRegistered,
+    );
+}
+
+#[test]
+fn test_cancel_refunds_entry_fees() {
+    let s = setup_test(3);
+    let tournament_id = create_and_fill(&s, Format::SingleElimination, vec![&s.env, 10_000]);
+
+    s.tournaments.cancel_tournament(&tournament_id);
+
+    let tournament = s.tournaments.get_tournament(&tournament_id);
+    assert_eq!(tournament.status, TournamentStatus::Cancelled);
+    assert_eq!(s.token.balance(&s.tournaments.address), 0);
+    for player in s.players.iter() {
+        assert_eq!(s.token.balance(&player), STARTING_BALANCE);
+    }
+
+    let latecomer = Address::generate(&s.env);
+    let result = s.tournaments.try_register(&tournament_id, &latecomer);
+    assert_tournament_error(&result, Error::RegistrationClosed);
+    let result = s.tournaments.try_start_tournament(&tournament_id);
+    assert_tournament_error(&result, Error::RegistrationClosed);
+}
+
+#[test]
+fn test_start_needs_two_entrants() {
+    let s = setup_test(1);
+    let tournament_id = create_and_fill(&s, Format::SingleElimination, vec![&s.env]);
+
+    let result = s.tournaments.try_start_tournament(&tournament_id);
+    assert_tournament_error(&result, Error::NotEnoughEntrants);
+
+    let result = s.tournaments.try_get_tournament(&99);
+    assert_tournament_error(&result, Error::TournamentNotFound);
+}
+
+// ============================================================================
+// Single Elimination Tests
+// ============================================================================
+
+#[test]
+fn test_single_elimination_bracket() {
+    let s = setup_test(4);
+    let p = |i| s.players.get_unchecked(i);
+    let tournament_id = create_and_fill(&s, Format::SingleElimination, vec![&s.env, 7000, 3000]);
+    s.tournaments.start_tournament(&tournament_id);
+
+    assert_eq!(
+        pairings(&s, tournament_id),
+        vec![&s.env, (p(0), p(1)), (p(2), p(3))]
+    );
+    play_match(&s, tournament_id, 0, game_hub::GameOutcome::Player1);
+    play_match(&s, tournament_id, 1, game_hub::GameOutcome::Player2);
+
+    // Winners meet in the final
+    let tournament = s.tournaments.get_tournament(&tournament_id);
+    assert_eq!(tournament.round, 2);
+    assert_eq!(pairings(&s, tournament_id), vec![&s.env, (p(0), p(3))]);
+    play_match(&s, tournament_id, 0, game_hub::GameOutcome::Player2);
+
+    let tournament = s.tournaments.get_tournament(&tournament_id);
+    assert_eq!(tournament.status, TournamentStatus::Finished);
+    assert_eq!(tournament.standings, vec![&s.env, p(3), p(0), p(1), p(2)]);
+
+    // 70% / 30% of a 4-entry pool
+    let pool = 4 * ENTRY_FEE;
+    assert_eq!(
+        s.token.balance(&p(3)),
+        STARTING_BALANCE - ENTRY_FEE + pool * 7 / 10
+    );
+    assert_eq!(
+        s.token.balance(&p(0)),
+        STARTING_BALANCE - ENTRY_FEE + pool * 3 / 10
+    );
+    assert_eq!(s.token.balance(&p(1)), STARTING_BALANCE - ENTRY_FEE);
+    assert_eq!(s.token.balance(&s.tournaments.address), 0);
+}
+
+#[test]
+fn test_single_elimination_bye_with_odd_field() {
+    let s = setup_test(3);
+    let p = |i| s.players.get_unchecked(i);
+    let tournament_id = create_and_fill(&s, Format::SingleElimination, vec![&s.env, 5000]);
+    s.tournaments.start_tournament(&tournament_id);
+
+    let tournament = s.tournaments.get_tournament(&tournament_id);
+    assert_eq!(tournament.bye, Some(p(2)));
+    assert_eq!(pairings(&s, tournament_id), vec![&s.env, (p(0), p(1))]);
+    play_match(&s, tournament_id, 0, game_hub::GameOutcome::Player2);
+
+    // The bye player comes first in the next round
+    let tournament = s.tournaments.get_tournament(&tournament_id);
+    assert_eq!(tournament.bye, None);
+    assert_eq!(pairings(&s, tournament_id), vec![&s.env, (p(2), p(1))]);
+    play_match(&s, tournament_id, 0, game_hub::GameOutcome::Player1);
+
+    // Half the pool as first prize, plus the unallocated half
+    let tournament = s.tournaments.get_tournament(&tournament_id);
+    assert_eq!(tournament.standings.get_unchecked(0), p(2));
+    assert_eq!(s.token.balance(&p(2)), STARTING_BALANCE + 2 * ENTRY_FEE);
+    assert_eq!(s.token.balance(&s.tournaments.address), 0);
+}
+
+#[test]
+fn test_draw_is_replayed_in_elimination() {
+    let s = setup_test(2);
+    let tournament_id = create_and_fill(&s, Format::SingleElimination, vec![&s.env]);
+    s.tournaments.start_tournament(&tournament_id);
+
+    let first_session = s
+        .tournaments
+        .get_tournament(&tournament_id)
+        .matches
+        .get_unchecked(0)
+        .session_id;
+    play_match(&s, tournament_id, 0, game_hub::GameOutcome::Draw);
+
+    let replay = s
+        .tournaments
+        .get_tournament(&tournament_id)
+        .matches
+        .get_unchecked(0);
+    assert_eq!(replay.status, MatchStatus::Pending);
+    assert_eq!(replay.attempt, 1);
+    assert_ne!(replay.session_id, first_session);
+
+    play_match(&s, tournament_id, 0, game_hub::GameOutcome::Player1);
+    let tournament = s.tournaments.get_tournament(&tournament_id);
+    assert_eq!(tournament.status, TournamentStatus::Finished);
+    assert_eq!(
+        tournament.standings.get_unchecked(0),
+        s.players.get_unchecked(0)
+    );
+}
+
+#[test]
+fn test_cancelled_match_is_replayed() {
+    let s = setup_test(2);
+    let tournament_id = create_and_fill(&s, Format::Swiss(1), vec![&s.env]);
+    s.tournaments.start_tournament(&tournament_id);
+
+    play_match(&s, tournament_id, 0, game_hub::GameOutcome::Cancelled);
+
+    let tournament = s.tournaments.get_tournament(&tournament_id);
+    assert_eq!(tournament.status, TournamentStatus::InProgress);
+    let replay = tournament.matches.get_unchecked(0);
+    assert_eq!(replay.status, MatchStatus::Pending);
+    assert_eq!(replay.attempt, 1);
+    assert_eq!(tournament.entrants.get_unchecked(0).score, 0);
+}
+
+#[test]
+fn test_match_lifecycle_errors() {
+    let s = setup_test(2);
+    let tournament_id = create_and_fill(&s, Format::SingleElimination, vec![&s.env]);
+
+    let result = s.tournaments.try_start_match(&tournament_id, &0, &None);
+    assert_tournament_error(&result, Error::NotInProgress);
+
+    s.tournaments.start_tournament(&tournament_id);
+    let result = s.tournaments.try_start_match(&tournament_id, &1, &None);
+    assert_tournament_error(&result, Error::MatchNotFound);
+    let result = s.tournaments.try_report_result(&tournament_id, &0);
+    assert_tournament_error(&result, Error::MatchNotStarted);
+
+    s.tournaments.start_match(&tournament_id, &0, &None);
+    let result = s.tournaments.try_start_match(&tournament_id, &0, &None);
+    assert_tournament_error(&result, Error::MatchAlreadyStarted);
+    let result = s.tournaments.try_report_result(&tournament_id, &0);
+    assert_tournament_error(&result, Error::SessionNotEnded);
+}
+
+#[test]
+fn test_taken_session_id_is_skipped() {
+    let s = setup_test(2);
+    let tournament_id = create_and_fill(&s, Format::SingleElimination, vec![&s.env]);
+    s.tournaments.start_tournament(&tournament_id);
+
+    // Someone else opens a session under the match's id first
+    let session_id = s
+        .tournaments
+        .get_tournament(&tournament_id)
+        .matches
+        .get_unchecked(0)
+        .session_id;
+    let other1 = Address::generate(&s.env);
+    let other2 = Address::generate(&s.env);
+    s.game.start_game(&session_id, &other1, &other2, &0, &0);
+
+    s.tournaments.start_match(&tournament_id, &0, &None);
+
+    let started = s
+        .tournaments
+        .get_tournament(&tournament_id)
+        .matches
+        .get_unchecked(0);
+    assert_eq!(started.status, MatchStatus::Started);
+    assert_eq!(started.attempt, 1);
+    let session = s.hub.get_session(&started.session_id);
+    assert_eq!(session.player1, s.players.get_unchecked(0));
+    assert_eq!(session.player2, s.players.get_unchecked(1));
+}
+
+#[test]
+fn test_forfeit_after_match_deadline() {
+    let s = setup_test(2);
+    let tournament_id = create_and_fill(&s, Format::SingleElimination, vec![&s.env, 10_000]);
+    s.tournaments.start_tournament(&tournament_id);
+    let player2 = s.players.get_unchecked(1);
+
+    // player1 never authorizes the session, so the match cannot start
+    let result = s.tournaments.try_forfeit_match(&tournament_id, &0, &player2);
+    assert_tournament_error(&result, Error::MatchDeadlineNotPassed);
+
+    s.env
+        .ledger()
+        .set_sequence_number(100 + MATCH_DEADLINE_LEDGERS + 1);
+    let outsider = Address::generate(&s.env);
+    let result = s.tournaments.try_forfeit_match(&tournament_id, &0, &outsider);
+    assert_tournament_error(&result, Error::NotInMatch);
+
+    s.tournaments.forfeit_match(&tournament_id, &0, &player2);
+    assert_eq!(
+        s.env.events().all().filter_by_contract(&s.tournaments.address),
+        [
+            MatchForfeited {
+                tournament_id,
+                round: 1,
+                match_index: 0,
+                winner: player2.clone(),
+            }
+            .to_xdr(&s.env, &s.tournaments.address),
+            TournamentFinished {
+                tournament_id,
+                winner: player2.clone(),
+            }
+            .to_xdr(&s.env, &s.tournaments.address),
+        ]
+    );
+
+    let tournament = s.tournaments.get_tournament(&tournament_id);
+    assert_eq!(tournament.status, TournamentStatus::Finished);
+    assert_eq!(tournament.standings.get_unchecked(0), player2);
+    assert_eq!(s.token.balance(&player2), STARTING_BALANCE + ENTRY_FEE);
+
+    let result = s.tournaments.try_forfeit_match(&tournament_id, &0, &player2);
+    assert_tournament_error(&result, Error::NotInProgress);
+}
+
+#[test]
+fn test_forfeit_started_match() {
+    let s = setup_test(4);
+    let tournament_id = create_and_fill(&s, Format::SingleElimination, vec![&s.env]);
+    s.tournaments.start_tournament(&tournament_id);
+    play_match(&s, tournament_id, 0, game_hub::GameOutcome::Player1);
+
+    // The second match starts, then one player stops playing
+    s.tournaments.start_match(&tournament_id, &1, &None);
+    s.env
+        .ledger()
+        .set_sequence_number(100 + MATCH_DEADLINE_LEDGERS + 1);
+    let result = s
+        .tournaments
+        .try_forfeit_match(&tournament_id, &0, &s.players.get_unchecked(0));
+    assert_tournament_error(&result, Error::MatchAlreadyFinished);
+
+    s.tournaments
+        .forfeit_match(&tournament_id, &1, &s.players.get_unchecked(3));
+
+    // The final is paired with a fresh deadline
+    let tournament = s.tournaments.get_tournament(&tournament_id);
+    assert_eq!(tournament.round, 2);
+    let last = s.players.get_unchecked(3);
+    assert_eq!(pairings(&s, tournament_id), vec![&s.env, (s.players.get_unchecked(0), last)]);
+    assert_eq!(
+        tournament.matches.get_unchecked(0).deadline,
+        100 + 2 * MATCH_DEADLINE_LEDGERS + 1
+    );
+}
+
+#[test]
+fn test_cancel_in_progress_refunds_entry_fees() {
+    let s = setup_test(4);
+    let tournament_id = create_and_fill(&s, Format::SingleElimination, vec![&s.env, 10_000]);
+    s.tournaments.start_tournament(&tournament_id);
+    play_match(&s, tournament_id, 0, game_hub::GameOutcome::Player1);
+    s.tournaments.start_match(&tournament_id, &1, &None);
+
+    s.tournaments.cancel_tournament(&tournament_id);
+
+    let tournament = s.tournaments.get_tournament(&tournament_id);
+    assert_eq!(tournament.status, TournamentStatus::Cancelled);
+    assert!(tournament.matches.is_empty());
+    assert_eq!(s.token.balance(&s.tournaments.address), 0);
+    for player in s.players.iter() {
+        assert_eq!(s.token.balance(&player), STARTING_BALANCE);
+    }
+
+    let result = s.tournaments.try_start_match(&tournament_id, &1, &None);
+    assert_tournament_error(&result, Error::NotInProgress);
+    let result = s.tournaments.try_cancel_tournament(&tournament_id);
+    assert_tournament_error(&result, Error::TournamentOver);
+}
+
+// ============================================================================
+// Swiss Tests
+// ============================================================================
+
+#[test]
+fn test_swiss_pairs_by_score_without_rematches() {
+    let s = setup_test(4);
+    let p = |i| s.players.get_unchecked(i);
+    let tournament_id = create_and_fill(&s, Format::Swiss(3), vec![&s.env, 10_000]);
+    s.tournaments.start_tournament(&tournament_id);
+
+    assert_eq!(
+        pairings(&s, tournament_id),
+        vec![&s.env, (p(0), p(1)), (p(2), p(3))]
+    );
+    play_match(&s, tournament_id, 0, game_hub::GameOutcome::Player1);
+    play_match(&s, tournament_id, 1, game_hub::GameOutcome::Player1);
+
+    // Leaders meet, as do the two players without a win
+    assert_eq!(
+        pairings(&s, tournament_id),
+        vec![&s.env, (p(0), p(2)), (p(1), p(3))]
+    );
+    play_match(&s, tournament_id, 0, game_hub::GameOutcome::Draw);
+    play_match(&s, tournament_id, 1, game_hub::GameOutcome::Player2);
+
+    // p0 and p2 have met, so each takes the next opponent they have not played
+    assert_eq!(
+        pairings(&s, tournament_id),
+        vec![&s.env, (p(0), p(3)), (p(2), p(1))]
+    );
+    play_match(&s, tournament_id, 0, game_hub::GameOutcome::Player1);
+    play_match(&s, tournament_id, 1, game_hub::GameOutcome::Player1);
+
+    let tournament = s.tournaments.get_tournament(&tournament_id);
+    assert_eq!(tournament.status, TournamentStatus::Finished);
+    assert_eq!(tournament.round, 3);
+    let scores: Vec<u32> = {
+        let mut scores = Vec::new(&s.env);
+        for entrant in tournament.entrants.iter() {
+            scores.push_back(entrant.score);
+        }
+        scores
+    };
+    assert_eq!(
+        scores,
+        vec![
+            &s.env,
+            2 * WIN_POINTS + DRAW_POINTS,
+            0,
+            2 * WIN_POINTS + DRAW_POINTS,
+            WIN_POINTS
+        ]
+    );
+    // Ties keep registration order
+    assert_eq!(tournament.standings, vec![&s.env, p(0), p(2), p(3), p(1)]);
+    assert_eq!(s.token.balance(&p(0)), STARTING_BALANCE + 3 * ENTRY_FEE);
+}
+
+#[test]
+fn test_swiss_bye_rotates() {
+    let s = setup_test(3);
+    let tournament_id = create_and_fill(&s, Format::Swiss(3), vec![&s.env]);
+    s.tournaments.start_tournament(&tournament_id);
+
+    let mut byes = Vec::new(&s.env);
+    for _ in 0..3 {
+        let tournament = s.tournaments.get_tournament(&tournament_id);
+        let bye = tournament.bye.unwrap();
+        assert!(!byes.contains(&bye));
+        byes.push_back(bye);
+        play_match(&s, tournament_id, 0, game_hub::GameOutcome::Draw);
+    }
+
+    // Every player had one bye and two draws
+    let tournament = s.tournaments.get_tournament(&tournament_id);
+    assert_eq!(tournament.status, TournamentStatus::Finished);
+    for entrant in tournament.entrants.iter() {
+        assert!(entrant.had_bye);
+        assert_eq!(entrant.score, WIN_POINTS + 2 * DRAW_POINTS);
+    }
+}
+
+// ============================================================================
+// Integration Tests
+// ============================================================================
+
+#[test]
+fn test_tournament_on_dice_duel() {
+    let s = setup_test(2);
+    let admin = Address::generate(&s.env);
+    let dice_addr = s.env.register(DiceDuelContract, (&admin, &s.hub.address));
+    let dice = DiceDuelContractClient::new(&s.env, &dice_addr);
+    s.hub.add_game(&dice_addr);
+
+    let tournament_id = s.tournaments.create_tournament(
+        &s.organizer,
+        &dice_addr,
+        &Format::SingleElimination,
+        &ENTRY_FEE,
+        &2,
+        &vec![&s.env, 10_000],
+    );
+    for player in s.players.iter() {
+        s.tournaments.register(&tournament_id, &player);
+    }
+    s.tournaments.start_tournament(&tournament_id);
+
+    // Roll until the dice give a decisive result; draws are replayed
+    loop {
+        s.tournaments.start_match(&tournament_id, &0, &None);
+        let game_match = s
+            .tournaments
+            .get_tournament(&tournament_id)
+            .matches
+            .get_unchecked(0);
+        dice.roll(&game_match.session_id, &game_match.player1);
+        dice.roll(&game_match.session_id, &game_match.player2);
+        dice.reveal_winner(&game_match.session_id);
+        s.tournaments.report_result(&tournament_id, &0);
+
+        if s.tournaments.get_tournament(&tournament_id).status == TournamentStatus::Finished {
+            break;
+        }
+    }
+
+    let tournament = s.tournaments.get_tournament(&tournament_id);
+    let winner = tournament.standings.get_unchecked(0);
+    let loser = tournament.standings.get_unchecked(1);
+    assert_eq!(s.token.balance(&winner), STARTING_BALANCE + ENTRY_FEE);
+    assert_eq!(s.token.balance(&loser), STARTING_BALANCE - ENTRY_FEE);
+
+    // The hub recorded the final as a win for the champion
+    assert_eq!(s.hub.get_stats(&winner).wins, 1);
+    assert_eq!(s.hub.get_stats(&loser).losses, 1);
+}
+
+#[test]
+fn test_tournament_match_on_the_resistance_forwards_commitments() {
+    let s = setup_test(2);
+    let admin = Address::generate(&s.env);
+    let dummy_vk = Bytes::from_array(&s.env, &[0u8; 64]);
+    let resistance_addr = s.env.register(TheResistanceContract, (&admin, &s.hub.address, dummy_vk));
+    let resistance = TheResistanceContractClient::new(&s.env, &resistance_addr);
+    s.hub.add_game(&resistance_addr);
+
+    let tournament_id = s.tournaments.create_tournament(
+        &s.organizer,
+        &resistance_addr,
+        &Format::SingleElimination,
+        &ENTRY_FEE,
+        &2,
+        &vec![&s.env, 10_000],
+    );
+    for player in s.players.iter() {
+        s.tournaments.register(&tournament_id, &player);
+    }
+    s.tournaments.start_tournament(&tournament_id);
+
+    // The Resistance's start_game takes the players' base commitments
+    let result = s.tournaments.try_start_match(&tournament_id, &0, &None);
+    assert!(result.is_err());
+
+    let commitment1 = BytesN::from_array(&s.env, &[1u8; 32]);
+    let commitment2 = BytesN::from_array(&s.env, &[2u8; 32]);
+    s.tournaments.start_match(
+        &tournament_id,
+        &0,
+        &Some((commitment1.clone(), commitment2.clone())),
+    );
+
+    let game_match = s
+        .tournaments
+        .get_tournament(&tournament_id)
+        .matches
+        .get_unchecked(0);
+    assert_eq!(game_match.status, MatchStatus::Started);
+    let game = resistance.get_game(&game_match.session_id);
+    assert_eq!(game.player1, game_match.player1);
+    assert_eq!(game.player1_commitment, commitment1);
+    assert_eq!(game.player2_commitment, commitment2);
+    assert_eq!(s.hub.get_session(&game_match.session_id).game_id, resistance_addr);
+}
+
+#[test]
+fn test_two_step_admin_transfer_and_hub_change() {
+    let s = setup_test(0);
+    let admin = s.tournaments.get_admin();
+    let new_admin = Address::generate(&s.env);
+
+    // The proposal only takes effect once the new admin accepts
+    s.tournaments.propose_admin(&new_admin);
+    assert_eq!(s.tournaments.get_admin(), admin);
+    s.tournaments.accept_admin();
+    assert_eq!(s.env.auths()[0].0, new_admin);
+    assert_eq!(
+        s.env.events().all(),
+        [AdminChanged {
+            previous_admin: admin,
+            new_admin: new_admin.clone(),
+        }
+        .to_xdr(&s.env, &s.tournaments.address)]
+    );
+    assert_eq!(s.tournaments.get_admin(), new_admin);
+    assert_eq!(s.tournaments.get_pending_admin(), None);
+
+    // Hub changes are authorized by the new admin and logged
+    let new_hub = Address::generate(&s.env);
+    s.tournaments.set_hub(&new_hub);
+    assert_eq!(s.env.auths()[0].0, new_admin);
+    assert_eq!(
+        s.env.events().all(),
+        [HubChanged {
+            previous_hub: s.hub.address.clone(),
+            new_hub: new_hub.clone(),
+        }
+        .to_xdr(&s.env, &s.tournaments.address)]
+    );
+    assert_eq!(s.tournaments.get_hub(), new_hub);
+}
@@ -5,32 +5,19 @@
 //! A two-player card game where players try to get as close to 21 as possible without going over.
 //! Each player is dealt 2 cards and can choose to "hit" (draw another card) or "stick" (end their turn).
 //! Cards are valued 1-13 where Ace=1, 2-10=face value, Jack/Queen/King=10.
+//! Equal hands are a draw.
 //!
 //! **Game Hub Integration:**
 //! This game is Game Hub-aware and enforces all games to be played through the
 //! Game Hub contract. Games cannot be started or completed without points involvement.
 
+use game_common::GameError;
 use soroban_sdk::{
-    Address, Bytes, BytesN, Env, IntoVal, contract, contractclient, contracterror,
+    Address, Bytes, BytesN, Env, IntoVal, contract, contracterror,
     contractimpl, contracttype, vec
 };
 
-// Import GameHub contract interface
-// This allows us to call into the GameHub contract
-#[contractclient(name = "GameHubClient")]
-pub trait GameHub {
-    fn start_game(
-        env: Env,
-        game_id: Address,
-        session_id: u32,
-        player1: Address,
-        player2: Address,
-        player1_points: i128,
-        player2_points: i128,
-    );
-
-    fn end_game(env: Env, session_id: u32, player1_won: bool);
-}
+pub use game_common::{GameOutcome, TtlConfig};
 
 // ============================================================================
 // Errors
@@ -47,10 +34,26 @@ pub enum Error {
     PlayerBusted = 5,
     BothPlayersNotStuck = 6,
     OpponentNotStuck = 7,
-    Draw = 8,
+    // 8 (Draw) and 10 (RoundOverflow) were retired when ties became a
+    // `GameOutcome::Draw`; their codes are not reused
     SelfPlay = 9,
-    RoundOverflow = 10,
     InvalidHandData = 11,
+    SessionAlreadyExists = 12,
+    ContractPaused = 13,
+    UnsupportedStorageVersion = 14,
+    InvalidTtl = 15,
+}
+
+impl From<GameError> for Error {
+    fn from(error: GameError) -> Self {
+        match error {
+            GameError::GameNotFound => Error::GameNotFound,
+            GameError::SessionAlreadyExists => Error::SessionAlreadyExists,
+            GameError::ContractPaused => Error::ContractPaused,
+            GameError::UnsupportedStorageVersion => Error::UnsupportedStorageVersion,
+            GameError::InvalidTtl => Error::InvalidTtl,
+        }
+    }
 }
 
 // ============================================================================
@@ -80,31 +83,86 @@ pub struct Game {
     pub player1_stuck: bool,
     pub player2_stuck: bool,
     pub winner: Option<Address>,
+    pub is_draw: bool,        // Set when both hands ended equal (`winner` stays `None`)
+    pub player1_abort: bool,  // Both abort flags set = game cancelled
+    pub player2_abort: bool,
+    pub round: u32,
+}
+
+/// `Game` as stored before storage versioning, without the draw and abort
+/// flags. These records are converted when loaded.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyGame {
+    pub player1: Address,
+    pub player2: Address,
+    pub player1_points: i128,
+    pub player2_points: i128,
+    pub player1_hand: Bytes,
+    pub player2_hand: Bytes,
+    pub player1_stuck: bool,
+    pub player2_stuck: bool,
+    pub winner: Option<Address>,
     pub round: u32,
 }
 
+/// Storage schema version written by this release. Bump it and add a
+/// `VersionedGame` variant whenever the stored `Game` layout changes.
+pub const STORAGE_VERSION: u32 = 1;
+
+/// `Game` as stored. A schema change adds a variant for the new layout and
+/// converts older ones in `into_current`, so live sessions still decode after
+/// `upgrade`.
 #[contracttype]
-#[derive(Clone)]
-pub enum DataKey {
-    Game(u32),
-    GameHubAddress,
-    Admin,
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum VersionedGame {
+    V1(Game),
 }
 
-// ============================================================================
-// Storage TTL Management
-// ============================================================================
-// TTL (Time To Live) ensures game data doesn't expire unexpectedly
-// Games are stored in temporary storage with a minimum 30-day retention
+impl VersionedGame {
+    fn into_current(self) -> Game {
+        match self {
+            VersionedGame::V1(game) => game,
+        }
+    }
+}
 
-/// TTL for game storage (30 days in ledgers, ~5 seconds per ledger)
-/// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
-const GAME_TTL_LEDGERS: u32 = 518_400;
+// Games saved before storage versioning are a bare `LegacyGame`
+impl From<LegacyGame> for VersionedGame {
+    fn from(game: LegacyGame) -> Self {
+        VersionedGame::V1(Game {
+            player1: game.player1,
+            player2: game.player2,
+            player1_points: game.player1_points,
+            player2_points: game.player2_points,
+            player1_hand: game.player1_hand,
+            player2_hand: game.player2_hand,
+            player1_stuck: game.player1_stuck,
+            player2_stuck: game.player2_stuck,
+            winner: game.winner,
+            is_draw: false,
+            player1_abort: false,
+            player2_abort: false,
+            round: game.round,
+        })
+    }
+}
 
 // ============================================================================
 // Helper Functions
 // ============================================================================
 
+/// Load a game, whichever schema version it was saved with
+fn load_game(env: &Env, session_id: u32) -> Result<Game, Error> {
+    let stored = game_common::load_versioned_game::<VersionedGame, LegacyGame>(env, session_id)?;
+    Ok(stored.into_current())
+}
+
+/// Save a game in the current schema version
+fn save_game(env: &Env, session_id: u32, game: &Game) {
+    game_common::save_game(env, session_id, &VersionedGame::V1(game.clone()));
+}
+
 /// Convert card number to point value.
 /// Cards 1-13 where: Ace=1, 2-10=face value, Jack/Queen/King=10
 fn card_value(card: u32) -> u32 {
@@ -134,6 +192,17 @@ fn deal_card(env: &Env, seed: BytesN<32>) -> u8 {
     env.prng().gen_range::<u64>(1..=13) as u8
 }
 
+/// Outcome of a finished game, or `None` while it is still in progress
+fn outcome_of(game: &Game) -> Option<GameOutcome> {
+    match &game.winner {
+        Some(winner) if *winner == game.player1 => Some(GameOutcome::Player1),
+        Some(_) => Some(GameOutcome::Player2),
+        None if game.player1_abort && game.player2_abort => Some(GameOutcome::Cancelled),
+        None if game.is_draw => Some(GameOutcome::Draw),
+        None => None,
+    }
+}
+
 // ============================================================================
 // Contract Definition
 // ============================================================================
@@ -150,10 +219,8 @@ impl TwentyOneContract {
     /// * `game_hub` - Address of the GameHub contract
     pub fn __constructor(env: Env, admin: Address, game_hub: Address) {
         // Store admin and GameHub address
-        env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage()
-            .instance()
-            .set(&DataKey::GameHubAddress, &game_hub);
+        game_common::init(&env, &admin, &game_hub);
+        game_common::set_storage_version(&env, STORAGE_VERSION);
     }
 
     /// Start a new game between two players with points.
@@ -177,24 +244,23 @@ impl TwentyOneContract {
         player1_points: i128,
         player2_points: i128,
     ) -> Result<(), Error> {
+        // No new games while paused
+        game_common::ensure_not_paused(&env)?;
+
         // Prevent self-play: Player 1 and Player 2 must be different
         if player1 == player2 {
             return Err(Error::SelfPlay);
         }
 
+        // Reject a reused session ID so a live game can never be overwritten
+        game_common::ensure_new_game(&env, session_id)?;
+
         // Require authentication from both players (they consent to committing points)
         player1.require_auth_for_args(vec![&env, session_id.into_val(&env), player1_points.into_val(&env)]);
         player2.require_auth_for_args(vec![&env, session_id.into_val(&env), player2_points.into_val(&env)]);
 
-        // Get GameHub address
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
-
         // Create GameHub client
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+        let game_hub = game_common::hub_client(&env);
 
         // Call the Game Hub to start the session and lock points
         // This requires THIS contract's authorization (env.current_contract_address())
@@ -254,17 +320,14 @@ impl TwentyOneContract {
             player1_stuck: false,
             player2_stuck: false,
             winner: None,
+            is_draw: false,
+            player1_abort: false,
+            player2_abort: false,
             round: 1,
         };
 
         // Store game in temporary storage with 30-day TTL
-        let game_key = DataKey::Game(session_id);
-        env.storage().temporary().set(&game_key, &game);
-
-        // Set TTL to ensure game is retained for at least 30 days
-        env.storage()
-            .temporary()
-            .extend_ttl(&game_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+        save_game(&env, session_id, &game);
 
         // Event emitted by GameHub contract (GameStarted)
 
@@ -278,18 +341,16 @@ impl TwentyOneContract {
     /// * `session_id` - The session ID of the game
     /// * `player` - Address of the player drawing a card
     pub fn hit(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        // No moves while paused
+        game_common::ensure_not_paused(&env)?;
+
         player.require_auth();
 
         // Get game from temporary storage
-        let key = DataKey::Game(session_id);
-        let mut game: Game = env
-            .storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::GameNotFound)?;
-
-        // Check game is still active (no winner yet)
-        if game.winner.is_some() {
+        let mut game: Game = load_game(&env, session_id)?;
+
+        // Check game is still active (not won, drawn or aborted)
+        if outcome_of(&game).is_some() {
             return Err(Error::GameAlreadyEnded);
         }
 
@@ -335,11 +396,11 @@ impl TwentyOneContract {
             if hand_value > 21 {
                 // Player 1 busted, player 2 wins
                 // Call GameHub FIRST (before setting winner)
-                Self::end_game_with_hub(&env, session_id, false)?;
+                Self::end_game_with_hub(&env, session_id, GameOutcome::Player2)?;
 
                 // Only set winner AFTER GameHub succeeds
                 game.winner = Some(game.player2.clone());
-                env.storage().temporary().set(&key, &game);
+                save_game(&env, session_id, &game);
 
                 // Return Ok - caller should check game.winner to see if game ended
                 return Ok(());
@@ -352,11 +413,11 @@ impl TwentyOneContract {
             if hand_value > 21 {
                 // Player 2 busted, player 1 wins
                 // Call GameHub FIRST (before setting winner)
-                Self::end_game_with_hub(&env, session_id, true)?;
+                Self::end_game_with_hub(&env, session_id, GameOutcome::Player1)?;
 
                 // Only set winner AFTER GameHub succeeds
                 game.winner = Some(game.player1.clone());
-                env.storage().temporary().set(&key, &game);
+                save_game(&env, session_id, &game);
 
                 // Return Ok - caller should check game.winner to see if game ended
                 return Ok(());
@@ -364,7 +425,7 @@ impl TwentyOneContract {
         }
 
         // Store updated game
-        env.storage().temporary().set(&key, &game);
+        save_game(&env, session_id, &game);
 
         Ok(())
     }
@@ -376,18 +437,16 @@ impl TwentyOneContract {
     /// * `session_id` - The session ID of the game
     /// * `player` - Address of the player sticking
     pub fn stick(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        // No moves while paused
+        game_common::ensure_not_paused(&env)?;
+
         player.require_auth();
 
         // Get game from temporary storage
-        let key = DataKey::Game(session_id);
-        let mut game: Game = env
-            .storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::GameNotFound)?;
-
-        // Check game is still active (no winner yet)
-        if game.winner.is_some() {
+        let mut game: Game = load_game(&env, session_id)?;
+
+        // Check game is still active (not won, drawn or aborted)
+        if outcome_of(&game).is_some() {
             return Err(Error::GameAlreadyEnded);
         }
 
@@ -407,33 +466,70 @@ impl TwentyOneContract {
         }
 
         // Store updated game
-        env.storage().temporary().set(&key, &game);
+        save_game(&env, session_id, &game);
 
         Ok(())
     }
 
+    /// Agree to abandon the game without a result.
+    /// Once both players have agreed, the GameHub session is cancelled and
+    /// both stakes are refunded.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player agreeing to abort
+    ///
+    /// # Returns
+    /// * `bool` - True if this call cancelled the game
+    pub fn abort_game(env: Env, session_id: u32, player: Address) -> Result<bool, Error> {
+        player.require_auth();
+
+        // Get game from temporary storage
+        let mut game: Game = load_game(&env, session_id)?;
+
+        if outcome_of(&game).is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+
+        if player == game.player1 {
+            game.player1_abort = true;
+        } else if player == game.player2 {
+            game.player2_abort = true;
+        } else {
+            return Err(Error::NotPlayer);
+        }
+
+        let cancelled = game.player1_abort && game.player2_abort;
+        if cancelled {
+            // Refund both stakes through the GameHub, unless it already has
+            game_common::cancel_session(&env, session_id);
+        }
+
+        save_game(&env, session_id, &game);
+
+        Ok(cancelled)
+    }
+
     /// Reveal the winner of the game and submit outcome to GameHub.
     /// Can only be called after both players have stuck.
-    /// This calculates hand values, determines the winner (closest to 21),
-    /// and handles draws by dealing new hands.
+    /// This calculates hand values and determines the winner (closest to 21);
+    /// equal hands are reported to the GameHub as a draw.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
     ///
     /// # Returns
-    /// * `Address` - Address of the winning player
-    pub fn reveal_winner(env: Env, session_id: u32) -> Result<Address, Error> {
+    /// * `GameOutcome` - Which player won, or `Draw` if the hands are equal
+    pub fn reveal_winner(env: Env, session_id: u32) -> Result<GameOutcome, Error> {
+        // No moves while paused
+        game_common::ensure_not_paused(&env)?;
+
         // Get game from temporary storage
-        let key = DataKey::Game(session_id);
-        let mut game: Game = env
-            .storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::GameNotFound)?;
-
-        // Check if game already ended (has a winner)
-        if let Some(winner) = &game.winner {
-            return Ok(winner.clone());
+        let mut game: Game = load_game(&env, session_id)?;
+
+        // Check if game already ended (has a winner or is drawn)
+        if let Some(outcome) = outcome_of(&game) {
+            return Ok(outcome);
         }
 
         // Check both players have stuck
@@ -445,67 +541,30 @@ impl TwentyOneContract {
         let player1_value = calculate_hand_value(&game.player1_hand)?;
         let player2_value = calculate_hand_value(&game.player2_hand)?;
 
-        // Determine winner (closest to 21 without going over)
+        // Determine outcome (closest to 21 without going over)
         // Note: Bust conditions are already handled in hit(), so values should be <= 21
-        let winner = if player1_value > player2_value {
+        let outcome = if player1_value > player2_value {
             // Player 1 is closer to 21
-            game.player1.clone()
+            GameOutcome::Player1
         } else if player2_value > player1_value {
             // Player 2 is closer to 21
-            game.player2.clone()
+            GameOutcome::Player2
         } else {
-            // Draw - deal new hands and continue
-            game.round = game.round.checked_add(1).ok_or(Error::RoundOverflow)?;
-
-            // Reset stuck flags
-            game.player1_stuck = false;
-            game.player2_stuck = false;
-
-            // Clear hands
-            game.player1_hand = Bytes::new(&env);
-            game.player2_hand = Bytes::new(&env);
-
-            // Deal new hands (2 cards each)
-            let mut seed_bytes = Bytes::new(&env);
-            seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
-            seed_bytes.append(&game.player1.to_string().to_bytes());
-            seed_bytes.append(&game.player2.to_string().to_bytes());
-            seed_bytes.append(&Bytes::from_array(&env, &game.round.to_be_bytes()));
-            let base_seed = env.crypto().keccak256(&seed_bytes);
-
-            // Deal 2 cards to player1
-            for i in 0..2 {
-                let mut card_seed_bytes = Bytes::new(&env);
-                card_seed_bytes.append(&Bytes::from(base_seed.clone()));
-                card_seed_bytes.append(&Bytes::from_array(&env, &[i, 1])); // [card_index, player]
-                let card_seed = env.crypto().keccak256(&card_seed_bytes);
-                game.player1_hand.push_back(deal_card(&env, card_seed.into()));
-            }
-
-            // Deal 2 cards to player2
-            for i in 0..2 {
-                let mut card_seed_bytes = Bytes::new(&env);
-                card_seed_bytes.append(&Bytes::from(base_seed.clone()));
-                card_seed_bytes.append(&Bytes::from_array(&env, &[i, 2])); // [card_index, player]
-                let card_seed = env.crypto().keccak256(&card_seed_bytes);
-                game.player2_hand.push_back(deal_card(&env, card_seed.into()));
-            }
-
-            // Store updated game and return error to indicate draw
-            env.storage().temporary().set(&key, &game);
-
-            return Err(Error::Draw);
+            GameOutcome::Draw
         };
 
-        // Call GameHub FIRST (before setting winner)
-        let player1_won = winner == game.player1;
-        Self::end_game_with_hub(&env, session_id, player1_won)?;
+        // Call GameHub FIRST (before recording the outcome)
+        Self::end_game_with_hub(&env, session_id, outcome)?;
 
-        // Only update game with winner AFTER GameHub succeeds
-        game.winner = Some(winner.clone());
-        env.storage().temporary().set(&key, &game);
+        // Only update game with outcome AFTER GameHub succeeds
+        match outcome {
+            GameOutcome::Player1 => game.winner = Some(game.player1.clone()),
+            GameOutcome::Player2 => game.winner = Some(game.player2.clone()),
+            _ => game.is_draw = true,
+        }
+        save_game(&env, session_id, &game);
 
-        Ok(winner)
+        Ok(outcome)
     }
 
     /// Get game information.
@@ -516,11 +575,16 @@ impl TwentyOneContract {
     /// # Returns
     /// * `Game` - The game state (includes hands and winner after game ends)
     pub fn get_game(env: Env, session_id: u32) -> Result<Game, Error> {
-        let key = DataKey::Game(session_id);
-        env.storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::GameNotFound)
+        load_game(&env, session_id)
+    }
+
+    /// Keep a game alive for another full game TTL.
+    /// Anyone may call this, e.g. spectators following an interesting game.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn bump_game(env: Env, session_id: u32) -> Result<(), Error> {
+        Ok(game_common::bump_game(&env, session_id)?)
     }
 
     /// Get the current hand value for a player.
@@ -532,12 +596,7 @@ impl TwentyOneContract {
     /// # Returns
     /// * `u32` - The total value of the player's hand
     pub fn get_hand_value(env: Env, session_id: u32, player: Address) -> Result<u32, Error> {
-        let key = DataKey::Game(session_id);
-        let game: Game = env
-            .storage()
-            .temporary()
-            .get(&key)
-            .ok_or(Error::GameNotFound)?;
+        let game: Game = load_game(&env, session_id)?;
 
         if player == game.player1 {
             calculate_hand_value(&game.player1_hand)
@@ -553,21 +612,14 @@ impl TwentyOneContract {
     // ========================================================================
 
     /// Helper to end game with the Game Hub
-    fn end_game_with_hub(env: &Env, session_id: u32, player1_won: bool) -> Result<(), Error> {
-        // Get GameHub address
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
-
+    fn end_game_with_hub(env: &Env, session_id: u32, outcome: GameOutcome) -> Result<(), Error> {
         // Create GameHub client
-        let game_hub = GameHubClient::new(env, &game_hub_addr);
+        let game_hub = game_common::hub_client(env);
 
         // Call the Game Hub to end the session
         // This unlocks points and updates standings
         // Event emitted by the Game Hub contract (GameEnded)
-        game_hub.end_game(&session_id, &player1_won);
+        game_hub.end_game(&session_id, &outcome);
 
         Ok(())
     }
@@ -581,25 +633,57 @@ impl TwentyOneContract {
     /// # Returns
     /// * `Address` - The admin address
     pub fn get_admin(env: Env) -> Address {
-        env.storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set")
+        game_common::get_admin(&env)
     }
 
-    /// Set a new admin address
+    /// Get the admin proposed by `propose_admin`, if it has not accepted yet
+    ///
+    /// # Returns
+    /// * `Option<Address>` - The pending admin address
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        game_common::get_pending_admin(&env)
+    }
+
+    /// Propose a new admin address
+    ///
+    /// The current admin keeps control until the new admin calls
+    /// `accept_admin`, so a mistyped address cannot lock the contract.
     ///
     /// # Arguments
-    /// * `new_admin` - The new admin address
-    pub fn set_admin(env: Env, new_admin: Address) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
-
-        env.storage().instance().set(&DataKey::Admin, &new_admin);
+    /// * `new_admin` - The proposed admin address
+    pub fn propose_admin(env: Env, new_admin: Address) {
+        game_common::propose_admin(&env, &new_admin);
+    }
+
+    /// Accept a pending admin proposal (must be authorized by the pending admin)
+    pub fn accept_admin(env: Env) {
+        game_common::accept_admin(&env);
+    }
+
+    /// Withdraw a pending admin proposal
+    pub fn cancel_admin_proposal(env: Env) {
+        game_common::cancel_admin_proposal(&env);
+    }
+
+    /// Check whether the contract is paused
+    ///
+    /// # Returns
+    /// * `bool` - True while new games and moves are blocked
+    pub fn is_paused(env: Env) -> bool {
+        game_common::is_paused(&env)
+    }
+
+    /// Emergency stop: block new games and moves
+    ///
+    /// Queries and `abort_game` keep working, and the GameHub can still
+    /// cancel and refund open sessions.
+    pub fn pause(env: Env) {
+        game_common::pause(&env);
+    }
+
+    /// Allow new games and moves again
+    pub fn unpause(env: Env) {
+        game_common::unpause(&env);
     }
 
     /// Get the current GameHub contract address
@@ -607,10 +691,7 @@ impl TwentyOneContract {
     /// # Returns
     /// * `Address` - The GameHub contract address
     pub fn get_hub(env: Env) -> Address {
-        env.storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set")
+        game_common::get_hub(&env)
     }
 
     /// Set a new GameHub contract address
@@ -618,16 +699,44 @@ impl TwentyOneContract {
     /// # Arguments
     /// * `new_hub` - The new GameHub contract address
     pub fn set_hub(env: Env, new_hub: Address) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
-
-        env.storage()
-            .instance()
-            .set(&DataKey::GameHubAddress, &new_hub);
+        game_common::set_hub(&env, &new_hub);
+    }
+
+    /// Get the storage TTL policy
+    ///
+    /// # Returns
+    /// * `TtlConfig` - Game and instance TTLs in ledgers
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        game_common::get_ttl_config(&env)
+    }
+
+    /// Set the storage TTL policy
+    ///
+    /// # Arguments
+    /// * `config` - Game and instance TTLs in ledgers (non-zero, at most the
+    ///   network maximum)
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        Ok(game_common::set_ttl_config(&env, &config)?)
+    }
+
+    /// Get the schema version of the stored data
+    ///
+    /// # Returns
+    /// * `u32` - The storage version (0 if deployed before versioning)
+    pub fn get_storage_version(env: Env) -> u32 {
+        game_common::get_storage_version(&env)
+    }
+
+    /// Bring stored data up to this release's schema version (call after
+    /// `upgrade`)
+    ///
+    /// Games saved by earlier releases are converted as they are loaded, so
+    /// live sessions keep working before and after the migration.
+    ///
+    /// # Returns
+    /// * `u32` - The storage version the data was at before migrating
+    pub fn migrate(env: Env) -> Result<u32, Error> {
+        Ok(game_common::migrate(&env, STORAGE_VERSION)?)
     }
 
     /// Update the contract WASM hash (upgrade contract)
@@ -635,14 +744,7 @@ impl TwentyOneContract {
     /// # Arguments
     /// * `new_wasm_hash` - The hash of the new WASM binary
     pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
-        let admin: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::Admin)
-            .expect("Admin not set");
-        admin.require_auth();
-
-        env.deployer().update_current_contract_wasm(new_wasm_hash);
+        game_common::upgrade(&env, new_wasm_hash);
     }
 }
 
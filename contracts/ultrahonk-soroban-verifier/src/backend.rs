@@ -0,0 +1,54 @@
+//! Crypto primitives the verifier is generic over: Keccak-256 for the
+//! transcript, and BN254 G1 arithmetic and pairings for the batch opening.
+//!
+//! On chain they are Soroban host functions, reached through [`Env`]. The
+//! `std` feature adds [`crate::native::NativeBackend`], which computes them in
+//! pure Rust so proofs can be checked without a Soroban environment.
+
+use crate::{
+    ec::{g1_msm, helpers::negate, pairing_check},
+    error::VerifyError,
+    field::Fr,
+    hash::hash32,
+    types::G1Point,
+};
+use soroban_sdk::{crypto::bn254::Bn254G1Affine, Bytes, Env};
+
+pub trait Backend {
+    /// G1 point in the backend's own representation.
+    type G1;
+
+    /// Keccak-256 of `data`.
+    fn keccak256(&self, data: &[u8]) -> [u8; 32];
+
+    /// ∑ sᵢ·Cᵢ
+    fn g1_msm(&self, coms: &[G1Point], scalars: &[Fr]) -> Result<Self::G1, VerifyError>;
+
+    /// −P
+    fn g1_negate(&self, pt: &G1Point) -> Result<Self::G1, VerifyError>;
+
+    /// e(P0, [1]₂) · e(P1, [x]₂) == 1
+    fn pairing_check(&self, p0: &Self::G1, p1: &Self::G1) -> bool;
+}
+
+/// Soroban host functions. Invalid points trap in the host rather than
+/// returning an error.
+impl Backend for Env {
+    type G1 = Bn254G1Affine;
+
+    fn keccak256(&self, data: &[u8]) -> [u8; 32] {
+        hash32(&Bytes::from_slice(self, data))
+    }
+
+    fn g1_msm(&self, coms: &[G1Point], scalars: &[Fr]) -> Result<Self::G1, VerifyError> {
+        g1_msm(self, coms, scalars)
+    }
+
+    fn g1_negate(&self, pt: &G1Point) -> Result<Self::G1, VerifyError> {
+        Ok(negate(self, pt))
+    }
+
+    fn pairing_check(&self, p0: &Self::G1, p1: &Self::G1) -> bool {
+        pairing_check(self, p0, p1)
+    }
+}
@@ -0,0 +1,175 @@
+//! Verify a bb proof off chain and report what was checked.
+//!
+//! Usage: ultrahonk_verify <proof> <vk> <public_inputs> [--json]
+//!
+//! Prints the VK header, the public inputs as field elements and the result,
+//! naming the stage that rejected the proof. ZK proofs are told apart by
+//! length. Exits with 1 unless the proof verifies.
+
+use std::{env, fs, process};
+use ultrahonk_soroban_verifier::{
+    field::Fr, types::PAIRING_POINTS_SIZE, NativeVerifier, VerifyError,
+};
+
+const USAGE: &str = "usage: ultrahonk_verify <proof> <vk> <public_inputs> [--json]";
+
+fn fail(msg: &str) -> ! {
+    eprintln!("ultrahonk_verify: {msg}");
+    process::exit(1);
+}
+
+/// A 32-byte public input; bb writes them big-endian, reduced mod r.
+struct PublicInput {
+    hex: String,
+    value: String,
+    canonical: bool,
+}
+
+fn decode_public_inputs(bytes: &[u8]) -> Vec<PublicInput> {
+    bytes
+        .chunks_exact(32)
+        .map(|chunk| {
+            let raw: [u8; 32] = chunk.try_into().unwrap();
+            let fr = Fr::from_bytes(&raw);
+            PublicInput {
+                hex: format!("0x{}", hex::encode(raw)),
+                value: fr.0.to_string(),
+                canonical: fr.to_bytes() == raw,
+            }
+        })
+        .collect()
+}
+
+/// VK header values.
+struct Header {
+    circuit_size: u64,
+    log_circuit_size: u64,
+    public_inputs_size: u64,
+}
+
+struct Report {
+    header: Option<Header>,
+    proof_bytes: usize,
+    zk: bool,
+    public_inputs: Vec<PublicInput>,
+    result: Result<(), VerifyError>,
+}
+
+fn run(proof: &[u8], vk: &[u8], public_inputs: &[u8]) -> Report {
+    let mut report = Report {
+        header: None,
+        proof_bytes: proof.len(),
+        zk: false,
+        public_inputs: decode_public_inputs(public_inputs),
+        result: Ok(()),
+    };
+    let verifier = match NativeVerifier::new(vk) {
+        Ok(verifier) => verifier,
+        Err(e) => {
+            report.result = Err(e);
+            return report;
+        }
+    };
+
+    let vk = verifier.get_vk();
+    report.header = Some(Header {
+        circuit_size: vk.circuit_size,
+        log_circuit_size: vk.log_circuit_size,
+        public_inputs_size: vk.public_inputs_size,
+    });
+    report.zk = verifier.is_zk_proof(proof.len());
+    report.result = verifier.verify_auto(proof, public_inputs);
+    report
+}
+
+fn print_text(report: &Report) {
+    if let Some(h) = &report.header {
+        println!("circuit size   {}", h.circuit_size);
+        println!("log n          {}", h.log_circuit_size);
+        println!(
+            "public inputs  {} ({} + {PAIRING_POINTS_SIZE} pairing point fields)",
+            h.public_inputs_size,
+            h.public_inputs_size.saturating_sub(PAIRING_POINTS_SIZE as u64)
+        );
+    }
+    let kind = if report.zk { "zk" } else { "plain" };
+    println!("proof          {} bytes, {kind}", report.proof_bytes);
+    for (i, pi) in report.public_inputs.iter().enumerate() {
+        let note = if pi.canonical { "" } else { " (not reduced mod r)" };
+        println!("input {i:<8} {} = {}{note}", pi.hex, pi.value);
+    }
+    match &report.result {
+        Ok(()) => println!("verified"),
+        Err(e) => println!("failed at {}: {e:?}", e.stage()),
+    }
+}
+
+fn json_str(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn print_json(report: &Report) {
+    let mut fields = Vec::new();
+    fields.push(format!(
+        "\"vk\":{}",
+        report.header.as_ref().map_or("null".into(), |h| format!(
+            "{{\"circuit_size\":{},\"log_circuit_size\":{},\"public_inputs_size\":{}}}",
+            h.circuit_size, h.log_circuit_size, h.public_inputs_size
+        ))
+    ));
+    fields.push(format!("\"proof\":{{\"bytes\":{},\"zk\":{}}}", report.proof_bytes, report.zk));
+    let inputs: Vec<String> = report
+        .public_inputs
+        .iter()
+        .map(|pi| {
+            format!(
+                "{{\"hex\":{},\"value\":{},\"canonical\":{}}}",
+                json_str(&pi.hex),
+                json_str(&pi.value),
+                pi.canonical
+            )
+        })
+        .collect();
+    fields.push(format!("\"public_inputs\":[{}]", inputs.join(",")));
+    fields.push(format!("\"verified\":{}", report.result.is_ok()));
+    match &report.result {
+        Ok(()) => fields.push("\"stage\":null,\"error\":null".into()),
+        Err(e) => fields.push(format!(
+            "\"stage\":{},\"error\":{}",
+            json_str(e.stage()),
+            json_str(&format!("{e:?}"))
+        )),
+    }
+    println!("{{{}}}", fields.join(","));
+}
+
+fn main() {
+    let mut paths = Vec::new();
+    let mut json = false;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
+            }
+            _ if paths.len() < 3 && !arg.starts_with('-') => paths.push(arg),
+            _ => fail(USAGE),
+        }
+    }
+    let paths: [String; 3] = paths.try_into().unwrap_or_else(|_| fail(USAGE));
+    let [proof, vk, public_inputs] =
+        paths.map(|path| fs::read(&path).unwrap_or_else(|e| fail(&format!("{path}: {e}"))));
+
+    let report = run(&proof, &vk, &public_inputs);
+    if json {
+        print_json(&report);
+    } else {
+        print_text(&report);
+    }
+    if report.result.is_err() {
+        process::exit(1);
+    }
+}
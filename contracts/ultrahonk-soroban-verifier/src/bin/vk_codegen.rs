@@ -0,0 +1,55 @@
+//! Generate a verifier contract module for one circuit from its bb `vk`.
+//!
+//! Usage: vk_codegen <vk> [--name <Contract>] [-o <out.rs>]
+//!
+//! Output goes to stdout unless `-o` is set.
+
+use std::{env, fs, process};
+use ultrahonk_soroban_verifier::codegen::generate_verifier_module;
+
+const USAGE: &str = "usage: vk_codegen <vk> [--name <Contract>] [-o <out.rs>]";
+
+fn fail(msg: &str) -> ! {
+    eprintln!("vk_codegen: {msg}");
+    process::exit(1);
+}
+
+fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+fn main() {
+    let mut vk_path = None;
+    let mut name = String::from("CircuitVerifier");
+    let mut out_path = None;
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().unwrap_or_else(|| fail(USAGE));
+        match arg.as_str() {
+            "--name" => name = value(),
+            "-o" => out_path = Some(value()),
+            "-h" | "--help" => {
+                println!("{USAGE}");
+                return;
+            }
+            _ if vk_path.is_none() && !arg.starts_with('-') => vk_path = Some(arg),
+            _ => fail(USAGE),
+        }
+    }
+    let vk_path = vk_path.unwrap_or_else(|| fail(USAGE));
+    if !is_identifier(&name) {
+        fail(&format!("{name} is not a valid contract name"));
+    }
+
+    let vk = fs::read(&vk_path).unwrap_or_else(|e| fail(&format!("{vk_path}: {e}")));
+    let module = generate_verifier_module(&vk, &name)
+        .unwrap_or_else(|e| fail(&format!("{vk_path}: {e:?}")));
+
+    match out_path {
+        Some(path) => fs::write(&path, module).unwrap_or_else(|e| fail(&format!("{path}: {e}"))),
+        None => print!("{module}"),
+    }
+}
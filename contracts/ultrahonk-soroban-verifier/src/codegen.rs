@@ -0,0 +1,203 @@
+//! Verifier-per-circuit code generator.
+//!
+//! Turns a bb `vk` file into a Rust module that embeds the VK as constants and
+//! exposes a `#[contract]` with `verify(proof, public_inputs)`, so a circuit can
+//! be deployed as its own verifier without storing or parsing VK bytes on chain.
+
+use crate::{
+    error::VerifyError,
+    types::{G1Point, VerificationKey, PAIRING_POINTS_SIZE},
+    utils::parse_vk,
+};
+
+#[cfg(not(feature = "std"))]
+use alloc::{format, string::String};
+
+/// VK commitment field names, in the order they are stored.
+const VK_COMMITMENTS: [&str; 27] = [
+    "qm",
+    "qc",
+    "ql",
+    "qr",
+    "qo",
+    "q4",
+    "q_lookup",
+    "q_arith",
+    "q_delta_range",
+    "q_elliptic",
+    "q_aux",
+    "q_poseidon2_external",
+    "q_poseidon2_internal",
+    "s1",
+    "s2",
+    "s3",
+    "s4",
+    "id1",
+    "id2",
+    "id3",
+    "id4",
+    "t1",
+    "t2",
+    "t3",
+    "t4",
+    "lagrange_first",
+    "lagrange_last",
+];
+
+fn commitments(vk: &VerificationKey) -> [&G1Point; 27] {
+    [
+        &vk.qm,
+        &vk.qc,
+        &vk.ql,
+        &vk.qr,
+        &vk.qo,
+        &vk.q4,
+        &vk.q_lookup,
+        &vk.q_arith,
+        &vk.q_delta_range,
+        &vk.q_elliptic,
+        &vk.q_aux,
+        &vk.q_poseidon2_external,
+        &vk.q_poseidon2_internal,
+        &vk.s1,
+        &vk.s2,
+        &vk.s3,
+        &vk.s4,
+        &vk.id1,
+        &vk.id2,
+        &vk.id3,
+        &vk.id4,
+        &vk.t1,
+        &vk.t2,
+        &vk.t3,
+        &vk.t4,
+        &vk.lagrange_first,
+        &vk.lagrange_last,
+    ]
+}
+
+/// Array literal body, `per_line` bytes per line.
+fn push_bytes(out: &mut String, bytes: &[u8], indent: &str, per_line: usize) {
+    for row in bytes.chunks(per_line) {
+        out.push_str(indent);
+        for (i, b) in row.iter().enumerate() {
+            if i > 0 {
+                out.push(' ');
+            }
+            out.push_str(&format!("0x{b:02x},"));
+        }
+        out.push('\n');
+    }
+}
+
+const HEADER: &str = "\
+//! Verifier contract for a single circuit, generated by `vk_codegen` from a
+//! bb v0.87.0 verification key. Do not edit by hand.
+
+use soroban_sdk::{contract, contracterror, contractimpl, Bytes, Env};
+use ultrahonk_soroban_verifier::{
+    types::{G1Point, VerificationKey, CONST_PROOF_SIZE_LOG_N, PAIRING_POINTS_SIZE},
+    UltraHonkVerifier, VerifyError,
+};
+
+";
+
+const CHECKS_AND_CONTRACT: &str = "
+// The checks `load_vk_from_bytes` runs, done when this module compiles
+const _: () = assert!(VK_BYTES.len() == ultrahonk_soroban_verifier::VK_BYTES);
+const _: () = assert!(
+    VK.log_circuit_size >= 1 && VK.log_circuit_size <= CONST_PROOF_SIZE_LOG_N as u64
+);
+const _: () = assert!(VK.circuit_size == 1u64 << VK.log_circuit_size);
+const _: () = assert!(VK.public_inputs_size >= PAIRING_POINTS_SIZE as u64);
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    InvalidProof = 1,
+    PublicInputsMismatch = 2,
+    SumcheckFailed = 3,
+    ShpleminiFailed = 4,
+    PairingFailed = 5,
+}
+
+impl From<VerifyError> for Error {
+    fn from(error: VerifyError) -> Self {
+        match error {
+            // VK errors cannot occur: the VK is checked above
+            VerifyError::ProofLength { .. }
+            | VerifyError::VkLength { .. }
+            | VerifyError::InvalidVkHeader
+            | VerifyError::InvalidPoint => Error::InvalidProof,
+            VerifyError::PublicInputsNotAligned { .. } | VerifyError::PublicInputsCount { .. } => {
+                Error::PublicInputsMismatch
+            }
+            VerifyError::PublicInputDeltaDenominatorZero
+            | VerifyError::SumcheckRound { .. }
+            | VerifyError::SumcheckDenominatorZero { .. }
+            | VerifyError::SumcheckFinalMismatch => Error::SumcheckFailed,
+            VerifyError::GeminiDenominatorZero
+            | VerifyError::ShplonkDenominatorZero
+            | VerifyError::LibraInconsistent
+            | VerifyError::MsmLength => Error::ShpleminiFailed,
+            VerifyError::PairingFailed => Error::PairingFailed,
+        }
+    }
+}
+
+#[contract]
+pub struct __CONTRACT__;
+
+#[contractimpl]
+impl __CONTRACT__ {
+    /// Verify a proof against the embedded VK. ZK proofs are told apart by
+    /// length.
+    pub fn verify(env: Env, proof: Bytes, public_inputs: Bytes) -> Result<(), Error> {
+        let verifier = UltraHonkVerifier::new_with_vk(&env, VK);
+        verifier.verify_auto(&proof, &public_inputs)?;
+        Ok(())
+    }
+}
+";
+
+/// Emit a Rust module embedding `vk` as constants, with a `#[contract]` named
+/// `contract_name` exposing `verify(proof, public_inputs)`.
+///
+/// The VK goes through the same parser as `load_vk_from_bytes`, so a VK the
+/// verifier would reject never produces a module. `contract_name` must be a
+/// Rust identifier.
+pub fn generate_verifier_module(vk: &[u8], contract_name: &str) -> Result<String, VerifyError> {
+    let parsed = parse_vk(vk)?;
+    if parsed.public_inputs_size < PAIRING_POINTS_SIZE as u64 {
+        return Err(VerifyError::InvalidVkHeader);
+    }
+
+    let mut out = String::new();
+    out.push_str(HEADER);
+
+    out.push_str("/// The VK as written by `bb write_vk`.\n");
+    out.push_str(&format!("pub const VK_BYTES: [u8; {}] = [\n", vk.len()));
+    push_bytes(&mut out, vk, "    ", 16);
+    out.push_str("];\n\n");
+
+    out.push_str("/// `VK_BYTES`, parsed by the generator.\n");
+    out.push_str("pub const VK: VerificationKey = VerificationKey {\n");
+    out.push_str(&format!("    circuit_size: {},\n", parsed.circuit_size));
+    out.push_str(&format!("    log_circuit_size: {},\n", parsed.log_circuit_size));
+    out.push_str(&format!("    public_inputs_size: {},\n", parsed.public_inputs_size));
+    for (name, point) in VK_COMMITMENTS.iter().zip(commitments(&parsed)) {
+        out.push_str(&format!("    {name}: G1Point {{\n"));
+        out.push_str("        x: [\n");
+        push_bytes(&mut out, &point.x, "            ", 8);
+        out.push_str("        ],\n");
+        out.push_str("        y: [\n");
+        push_bytes(&mut out, &point.y, "            ", 8);
+        out.push_str("        ],\n");
+        out.push_str("    },\n");
+    }
+    out.push_str("};\n");
+
+    out.push_str(&CHECKS_AND_CONTRACT.replace("__CONTRACT__", contract_name));
+    Ok(out)
+}
@@ -1,10 +1,10 @@
-use crate::{field::Fr, types::G1Point};
+use crate::{error::VerifyError, field::Fr, types::G1Point};
 use soroban_sdk::{
     crypto::bn254::{Bn254G1Affine, Bn254G2Affine, Fr as Bn254Fr},
     BytesN, Env, Vec,
 };
 
-const RHS_G2_BYTES: [u8; 128] = [
+pub(crate) const RHS_G2_BYTES: [u8; 128] = [
     0x19, 0x8e, 0x93, 0x93, 0x92, 0x0d, 0x48, 0x3a, 0x72, 0x60, 0xbf, 0xb7, 0x31, 0xfb, 0x5d, 0x25,
     0xf1, 0xaa, 0x49, 0x33, 0x35, 0xa9, 0xe7, 0x12, 0x97, 0xe4, 0x85, 0xb7, 0xae, 0xf3, 0x12, 0xc2,
     0x18, 0x00, 0xde, 0xef, 0x12, 0x1f, 0x1e, 0x76, 0x42, 0x6a, 0x00, 0x66, 0x5e, 0x5c, 0x44, 0x79,
@@ -15,7 +15,7 @@ const RHS_G2_BYTES: [u8; 128] = [
     0xe3, 0xd1, 0xe7, 0x69, 0x0c, 0x43, 0xd3, 0x7b, 0x4c, 0xe6, 0xcc, 0x01, 0x66, 0xfa, 0x7d, 0xaa,
 ];
 
-const LHS_G2_BYTES: [u8; 128] = [
+pub(crate) const LHS_G2_BYTES: [u8; 128] = [
     0x26, 0x0e, 0x01, 0xb2, 0x51, 0xf6, 0xf1, 0xc7, 0xe7, 0xff, 0x4e, 0x58, 0x07, 0x91, 0xde, 0xe8,
     0xea, 0x51, 0xd8, 0x7a, 0x35, 0x8e, 0x03, 0x8b, 0x4e, 0xfe, 0x30, 0xfa, 0xc0, 0x93, 0x83, 0xc1,
     0x01, 0x18, 0xc4, 0xd5, 0xb8, 0x37, 0xbc, 0xc2, 0xbc, 0x89, 0xb5, 0xb3, 0x98, 0xb5, 0x97, 0x4e,
@@ -47,23 +47,34 @@ pub fn lhs_g2_affine(env: &Env) -> Bn254G2Affine {
 }
 
 /// Multi-scalar multiplication on G1: ∑ sᵢ·Cᵢ
+///
+/// There is no BN254 MSM host function, so each term costs a `g1_mul` and a
+/// `g1_add` host call. Terms with a zero scalar or a point at infinity (unused
+/// VK selectors, dummy fold rounds) are skipped, unit scalars skip the
+/// multiplication and the first term seeds the accumulator.
 #[inline(always)]
-pub fn g1_msm(env: &Env, coms: &[G1Point], scalars: &[Fr]) -> Result<Bn254G1Affine, &'static str> {
+pub fn g1_msm(env: &Env, coms: &[G1Point], scalars: &[Fr]) -> Result<Bn254G1Affine, VerifyError> {
     if coms.len() != scalars.len() {
-        return Err("msm len mismatch");
+        return Err(VerifyError::MsmLength);
     }
     let bn = env.crypto().bn254();
-    let mut acc = Bn254G1Affine::from_array(env, &G1Point::infinity().to_bytes());
+    let mut acc: Option<Bn254G1Affine> = None;
     for (c, s) in coms.iter().zip(scalars.iter()) {
-        if s.is_zero() {
+        if s.is_zero() || c.is_infinity() {
             continue;
         }
         let p = g1_from_point(env, c);
-        let scalar = fr_to_bn254(env, s);
-        let term = bn.g1_mul(&p, &scalar);
-        acc = bn.g1_add(&acc, &term);
+        let term = if *s == Fr::one() {
+            p
+        } else {
+            bn.g1_mul(&p, &fr_to_bn254(env, s))
+        };
+        acc = Some(match acc {
+            Some(acc) => bn.g1_add(&acc, &term),
+            None => term,
+        });
     }
-    Ok(acc)
+    Ok(acc.unwrap_or_else(|| g1_from_point(env, &G1Point::infinity())))
 }
 
 /// Pairing product check e(P0, rhs_g2) * e(P1, lhs_g2) == 1
@@ -0,0 +1,60 @@
+//! Verification errors
+
+/// The specific reason parsing or verification failed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifyError {
+    /// Proof bytes do not have the expected length.
+    ProofLength { expected: usize, actual: usize },
+    /// VK bytes do not match any supported layout.
+    VkLength { actual: usize },
+    /// VK header values are out of range.
+    InvalidVkHeader,
+    /// Public inputs are not a whole number of 32-byte fields.
+    PublicInputsNotAligned { len: usize },
+    /// Number of public inputs differs from the VK.
+    PublicInputsCount { expected: u64, actual: u64 },
+    /// The public input delta denominator is zero.
+    PublicInputDeltaDenominatorZero,
+    /// The univariate of sumcheck round `round` does not sum to the target.
+    SumcheckRound { round: usize },
+    /// Barycentric evaluation in sumcheck round `round` hit a zero denominator.
+    SumcheckDenominatorZero { round: usize },
+    /// The final relation evaluation does not match the sumcheck target.
+    SumcheckFinalMismatch,
+    /// The Gemini challenge or a fold denominator is zero.
+    GeminiDenominatorZero,
+    /// A Shplonk denominator (z ∓ r^{2^i}, or z - g·r for Libra) is zero.
+    ShplonkDenominatorZero,
+    /// Libra evaluations do not match the claimed Libra evaluation.
+    LibraInconsistent,
+    /// MSM commitments and scalars differ in length.
+    MsmLength,
+    /// A commitment is not a BN254 G1 point. Only the `std` backend reports
+    /// this; on chain the host traps instead.
+    InvalidPoint,
+    /// The final pairing check failed.
+    PairingFailed,
+}
+
+impl VerifyError {
+    /// The verification stage that failed, for reporting.
+    pub fn stage(&self) -> &'static str {
+        match self {
+            VerifyError::ProofLength { .. } => "proof",
+            VerifyError::VkLength { .. } | VerifyError::InvalidVkHeader => "vk",
+            VerifyError::PublicInputsNotAligned { .. } | VerifyError::PublicInputsCount { .. } => {
+                "public inputs"
+            }
+            VerifyError::PublicInputDeltaDenominatorZero => "public input delta",
+            VerifyError::SumcheckRound { .. }
+            | VerifyError::SumcheckDenominatorZero { .. }
+            | VerifyError::SumcheckFinalMismatch => "sumcheck",
+            VerifyError::GeminiDenominatorZero
+            | VerifyError::ShplonkDenominatorZero
+            | VerifyError::LibraInconsistent
+            | VerifyError::MsmLength
+            | VerifyError::InvalidPoint => "shplemini",
+            VerifyError::PairingFailed => "pairing",
+        }
+    }
+}
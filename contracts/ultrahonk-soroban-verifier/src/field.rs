@@ -47,6 +47,11 @@ impl Fr {
         Fr(ArkFr::from_le_bytes_mod_order(&tmp))
     }
 
+    /// Reduce big-endian bytes of any length mod r.
+    pub fn from_be_bytes_mod_order(bytes: &[u8]) -> Self {
+        Fr(ArkFr::from_be_bytes_mod_order(bytes))
+    }
+
     /// Convert to 32-byte big-endian representation.
     #[inline(always)]
     pub fn to_bytes(&self) -> [u8; 32] {
@@ -0,0 +1,55 @@
+//! Proof and VK byte layouts emitted by bb v0.87.0 (Nargo 1.0.0-beta.9).
+//!
+//! G1 coordinates are split into (lo136, hi) limbs, so a point takes four
+//! fields in the proof and in the transcript. The VK header is four big-endian
+//! u64 words: circuit size, log circuit size, public inputs size and public
+//! inputs offset.
+
+use crate::types::{
+    BATCHED_RELATION_PARTIAL_LENGTH, CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ENTITIES,
+    PAIRING_POINTS_SIZE,
+};
+
+/// G1 commitments carried by a proof outside of the Gemini folds
+/// (w1..w4, lookup counts/tags/inverses, z_perm, shplonk_q, kzg_quotient).
+const PROOF_POINTS: usize = 10;
+/// Extra G1 commitments in a ZK proof (three Libra commitments and the
+/// Gemini masking polynomial).
+const ZK_EXTRA_POINTS: usize = 4;
+/// Extra scalars in a ZK proof (Libra sum and evaluation, Gemini masking
+/// evaluation, four Libra polynomial evaluations).
+const ZK_EXTRA_FIELDS: usize = 7;
+/// Commitments stored in a VK after its header.
+const VK_POINTS: usize = 27;
+
+/// Number of 32-byte fields used to encode one G1 point.
+pub const POINT_FIELDS: usize = 4;
+
+/// Number of 32-byte fields in a proof.
+pub const PROOF_FIELDS: usize = PAIRING_POINTS_SIZE
+    + (PROOF_POINTS + CONST_PROOF_SIZE_LOG_N - 1) * POINT_FIELDS
+    + CONST_PROOF_SIZE_LOG_N * BATCHED_RELATION_PARTIAL_LENGTH
+    + NUMBER_OF_ENTITIES
+    + CONST_PROOF_SIZE_LOG_N;
+
+/// Length of a proof in bytes.
+pub const PROOF_BYTES: usize = PROOF_FIELDS * 32;
+
+/// Number of 32-byte fields in a ZK proof. Sumcheck univariates carry one
+/// extra coefficient per round.
+pub const ZK_PROOF_FIELDS: usize =
+    PROOF_FIELDS + ZK_EXTRA_POINTS * POINT_FIELDS + ZK_EXTRA_FIELDS + CONST_PROOF_SIZE_LOG_N;
+
+/// Length of a ZK proof in bytes.
+pub const ZK_PROOF_BYTES: usize = ZK_PROOF_FIELDS * 32;
+
+/// Length of the VK header in bytes.
+pub const VK_HEADER_BYTES: usize = 4 * 8;
+
+/// Length of a VK in bytes.
+pub const VK_BYTES: usize = VK_HEADER_BYTES + VK_POINTS * 64;
+
+/// Whether `len` is the length of a proof, ZK or not.
+pub fn is_proof_len(len: usize) -> bool {
+    len == PROOF_BYTES || len == ZK_PROOF_BYTES
+}
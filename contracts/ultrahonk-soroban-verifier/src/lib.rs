@@ -3,10 +3,17 @@
 #[cfg(not(feature = "std"))]
 extern crate alloc;
 
+pub mod backend;
+pub mod codegen;
 pub mod debug;
 pub mod ec;
+pub mod error;
 pub mod field;
+pub mod format;
 pub mod hash;
+#[cfg(feature = "std")]
+pub mod native;
+pub mod public_inputs;
 pub mod relations;
 pub mod shplemini;
 pub mod sumcheck;
@@ -14,7 +21,10 @@ pub mod transcript;
 pub mod types;
 pub mod utils;
 pub mod verifier;
-pub const PROOF_FIELDS: usize = 456;
-pub const PROOF_BYTES: usize = PROOF_FIELDS * 32;
 
+pub use error::VerifyError;
+pub use format::{PROOF_BYTES, PROOF_FIELDS, VK_BYTES, ZK_PROOF_BYTES};
+#[cfg(feature = "std")]
+pub use native::NativeVerifier;
+pub use public_inputs::{PublicInputsBuilder, PublicInputsReader};
 pub use verifier::UltraHonkVerifier;
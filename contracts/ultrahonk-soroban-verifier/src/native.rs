@@ -0,0 +1,147 @@
+//! Off-chain verification (`std` only).
+//!
+//! Runs the same transcript, sumcheck and Shplemini code as
+//! [`crate::UltraHonkVerifier`] over plain byte slices, with `sha3` Keccak-256
+//! and `ark-bn254` arithmetic in place of the Soroban host functions. Useful
+//! for rejecting a bad proof before paying for a transaction.
+
+use crate::{
+    backend::Backend,
+    ec::{LHS_G2_BYTES, RHS_G2_BYTES},
+    error::VerifyError,
+    field::Fr,
+    types::{G1Point, VerificationKey},
+    utils::parse_vk,
+    verifier::{is_zk_proof, verify_proof, verify_zk_proof},
+};
+use ark_bn254::{Bn254, Fq, Fq2, G1Affine, G1Projective, G2Affine};
+use ark_ec::{pairing::Pairing, AffineRepr, CurveGroup, VariableBaseMSM};
+use ark_ff::{BigInteger, PrimeField, Zero};
+use sha3::{Digest, Keccak256};
+
+/// Keccak-256 and BN254 arithmetic computed in-process.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NativeBackend;
+
+/// Canonical big-endian base field element.
+fn fq_from_be(bytes: &[u8]) -> Option<Fq> {
+    let fq = Fq::from_be_bytes_mod_order(bytes);
+    (fq.into_bigint().to_bytes_be() == bytes).then_some(fq)
+}
+
+/// Decode a commitment, rejecting points off the curve (G1 has cofactor 1,
+/// so that is also the subgroup check the host does).
+fn g1_affine(pt: &G1Point) -> Result<G1Affine, VerifyError> {
+    if pt.is_infinity() {
+        return Ok(G1Affine::zero());
+    }
+    let (Some(x), Some(y)) = (fq_from_be(&pt.x), fq_from_be(&pt.y)) else {
+        return Err(VerifyError::InvalidPoint);
+    };
+    let p = G1Affine::new_unchecked(x, y);
+    if p.is_on_curve() {
+        Ok(p)
+    } else {
+        Err(VerifyError::InvalidPoint)
+    }
+}
+
+/// G2 constants are stored as the host expects them: x.c1, x.c0, y.c1, y.c0.
+fn g2_affine(bytes: &[u8; 128]) -> G2Affine {
+    let fq = |i: usize| fq_from_be(&bytes[i * 32..(i + 1) * 32]).expect("canonical G2 constant");
+    G2Affine::new_unchecked(Fq2::new(fq(1), fq(0)), Fq2::new(fq(3), fq(2)))
+}
+
+impl Backend for NativeBackend {
+    type G1 = G1Affine;
+
+    fn keccak256(&self, data: &[u8]) -> [u8; 32] {
+        Keccak256::digest(data).into()
+    }
+
+    /// Terms the host skips (zero scalar, point at infinity) are skipped here
+    /// too, so their points are not decoded.
+    fn g1_msm(&self, coms: &[G1Point], scalars: &[Fr]) -> Result<Self::G1, VerifyError> {
+        if coms.len() != scalars.len() {
+            return Err(VerifyError::MsmLength);
+        }
+        let mut bases = Vec::with_capacity(coms.len());
+        let mut exps = Vec::with_capacity(coms.len());
+        for (c, s) in coms.iter().zip(scalars) {
+            if s.is_zero() || c.is_infinity() {
+                continue;
+            }
+            bases.push(g1_affine(c)?);
+            exps.push(s.0);
+        }
+        let sum = G1Projective::msm(&bases, &exps).map_err(|_| VerifyError::MsmLength)?;
+        Ok(sum.into_affine())
+    }
+
+    fn g1_negate(&self, pt: &G1Point) -> Result<Self::G1, VerifyError> {
+        Ok(-g1_affine(pt)?)
+    }
+
+    fn pairing_check(&self, p0: &Self::G1, p1: &Self::G1) -> bool {
+        let g2s = [g2_affine(&RHS_G2_BYTES), g2_affine(&LHS_G2_BYTES)];
+        Bn254::multi_pairing([*p0, *p1], g2s).is_zero()
+    }
+}
+
+/// [`crate::UltraHonkVerifier`] for `std` hosts: takes `&[u8]` and needs no
+/// Soroban `Env`.
+pub struct NativeVerifier {
+    vk: VerificationKey,
+}
+
+impl NativeVerifier {
+    /// Build from VK bytes.
+    pub fn new(vk_bytes: &[u8]) -> Result<Self, VerifyError> {
+        Ok(Self::new_with_vk(parse_vk(vk_bytes)?))
+    }
+
+    pub fn new_with_vk(vk: VerificationKey) -> Self {
+        Self { vk }
+    }
+
+    /// The parsed VK.
+    pub fn get_vk(&self) -> &VerificationKey {
+        &self.vk
+    }
+
+    /// Verify a proof.
+    pub fn verify(
+        &self,
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+    ) -> Result<(), VerifyError> {
+        verify_proof(&NativeBackend, &self.vk, proof_bytes, public_inputs_bytes)
+    }
+
+    /// Verify a ZK proof (`bb prove --zk`).
+    pub fn verify_zk(
+        &self,
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+    ) -> Result<(), VerifyError> {
+        verify_zk_proof(&NativeBackend, &self.vk, proof_bytes, public_inputs_bytes)
+    }
+
+    /// Whether a proof of `len` bytes is a ZK proof.
+    pub fn is_zk_proof(&self, len: usize) -> bool {
+        is_zk_proof(len)
+    }
+
+    /// Verify a plain or ZK proof; ZK proofs are told apart by length.
+    pub fn verify_auto(
+        &self,
+        proof_bytes: &[u8],
+        public_inputs_bytes: &[u8],
+    ) -> Result<(), VerifyError> {
+        if self.is_zk_proof(proof_bytes.len()) {
+            self.verify_zk(proof_bytes, public_inputs_bytes)
+        } else {
+            self.verify(proof_bytes, public_inputs_bytes)
+        }
+    }
+}
@@ -0,0 +1,209 @@
+//! Encoding and decoding of public inputs.
+//!
+//! bb's `public_inputs` file is one 32-byte big-endian field element per
+//! input, in the order the circuit declares its `pub` parameters, followed by
+//! the circuit's return values. [`PublicInputsBuilder`] writes that layout for
+//! `verify`; [`PublicInputsReader`] reads it back into typed values.
+
+use crate::field::Fr;
+use soroban_sdk::{Bytes, BytesN, Env};
+
+/// Builds the public inputs for a proof, one field element per `push_*`.
+pub struct PublicInputsBuilder {
+    bytes: Bytes,
+}
+
+impl PublicInputsBuilder {
+    pub fn new(env: &Env) -> Self {
+        Self {
+            bytes: Bytes::new(env),
+        }
+    }
+
+    /// A field element.
+    pub fn push_fr(self, value: Fr) -> Self {
+        self.push_field_bytes(&value.to_bytes())
+    }
+
+    /// A `u32` (Noir `u32` or `Field`), left-padded.
+    pub fn push_u32(self, value: u32) -> Self {
+        self.push_u64(value as u64)
+    }
+
+    /// A `u64` (Noir `u64` or `Field`), left-padded.
+    pub fn push_u64(self, value: u64) -> Self {
+        let mut word = [0u8; 32];
+        word[24..].copy_from_slice(&value.to_be_bytes());
+        self.push_field_bytes(&word)
+    }
+
+    /// A `bool`, as 0 or 1.
+    pub fn push_bool(self, value: bool) -> Self {
+        self.push_u64(value as u64)
+    }
+
+    /// A field element given as 32 big-endian bytes, e.g. a Poseidon2
+    /// commitment. Pushed as-is, so it must already be below r.
+    pub fn push_bytes32(self, value: &BytesN<32>) -> Self {
+        self.push_field_bytes(&value.to_array())
+    }
+
+    /// Big-endian bytes of any length reduced mod r, like Noir's
+    /// `Field::from_be_bytes`. Use this for hashes and other values that may
+    /// exceed the field.
+    pub fn push_reduced(self, bytes: &[u8]) -> Self {
+        self.push_fr(Fr::from_be_bytes_mod_order(bytes))
+    }
+
+    fn push_field_bytes(mut self, word: &[u8; 32]) -> Self {
+        self.bytes.extend_from_array(word);
+        self
+    }
+
+    /// Number of field elements pushed so far.
+    pub fn len(&self) -> u32 {
+        self.bytes.len() / 32
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// The encoded public inputs, ready for `verify`.
+    pub fn build(self) -> Bytes {
+        self.bytes
+    }
+}
+
+/// Why a public input could not be decoded. `index` counts fields from the
+/// start of the file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PublicInputError {
+    /// The input is not a whole number of 32-byte fields.
+    NotAligned { len: usize },
+    /// Fewer fields than the circuit declares.
+    Missing { index: usize },
+    /// A field is not below r.
+    NotCanonical { index: usize },
+    /// A field does not fit the requested integer type, or a `bool` is not 0
+    /// or 1.
+    OutOfRange { index: usize },
+    /// Fields left over after the last value was read.
+    Trailing { count: usize },
+}
+
+/// Reads typed values from public inputs in bb's layout.
+#[derive(Clone, Debug)]
+pub struct PublicInputsReader<'a> {
+    bytes: &'a [u8],
+    /// Index of the first field in `bytes`, for error reporting
+    start: usize,
+    pos: usize,
+}
+
+impl<'a> PublicInputsReader<'a> {
+    /// Read a whole `public_inputs` file.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, PublicInputError> {
+        if !bytes.len().is_multiple_of(32) {
+            return Err(PublicInputError::NotAligned { len: bytes.len() });
+        }
+        Ok(Self {
+            bytes,
+            start: 0,
+            pos: 0,
+        })
+    }
+
+    /// Split a `public_inputs` file into the circuit's `pub` parameters and
+    /// its last `return_values` fields.
+    pub fn split_return_values(
+        bytes: &'a [u8],
+        return_values: usize,
+    ) -> Result<(Self, Self), PublicInputError> {
+        let reader = Self::new(bytes)?;
+        let fields = reader.len();
+        let params = fields
+            .checked_sub(return_values)
+            .ok_or(PublicInputError::Missing { index: fields })?;
+        let (head, tail) = bytes.split_at(params * 32);
+        let returns = Self {
+            bytes: tail,
+            start: params,
+            pos: 0,
+        };
+        Ok((Self::new(head)?, returns))
+    }
+
+    /// Fields not read yet.
+    pub fn len(&self) -> usize {
+        self.bytes.len() / 32 - self.pos
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The next field as raw big-endian bytes.
+    pub fn next_field_bytes(&mut self) -> Result<[u8; 32], PublicInputError> {
+        let index = self.start + self.pos;
+        let offset = self.pos * 32;
+        let word = self
+            .bytes
+            .get(offset..offset + 32)
+            .ok_or(PublicInputError::Missing { index })?;
+        self.pos += 1;
+        Ok(word.try_into().unwrap())
+    }
+
+    /// The next field as an element of Fr.
+    pub fn next_fr(&mut self) -> Result<Fr, PublicInputError> {
+        let index = self.start + self.pos;
+        let word = self.next_field_bytes()?;
+        let value = Fr::from_bytes(&word);
+        if value.to_bytes() != word {
+            return Err(PublicInputError::NotCanonical { index });
+        }
+        Ok(value)
+    }
+
+    /// The next field as a `u32`.
+    pub fn next_u32(&mut self) -> Result<u32, PublicInputError> {
+        let index = self.start + self.pos;
+        let value = self.next_u64()?;
+        u32::try_from(value).map_err(|_| PublicInputError::OutOfRange { index })
+    }
+
+    /// The next field as a `u64`.
+    pub fn next_u64(&mut self) -> Result<u64, PublicInputError> {
+        let index = self.start + self.pos;
+        let word = self.next_field_bytes()?;
+        if word[..24].iter().any(|b| *b != 0) {
+            return Err(PublicInputError::OutOfRange { index });
+        }
+        Ok(u64::from_be_bytes(word[24..].try_into().unwrap()))
+    }
+
+    /// The next field as a `bool`.
+    pub fn next_bool(&mut self) -> Result<bool, PublicInputError> {
+        let index = self.start + self.pos;
+        match self.next_u64()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(PublicInputError::OutOfRange { index }),
+        }
+    }
+
+    /// The next field as 32 big-endian bytes, checked to be below r.
+    pub fn next_bytes32(&mut self, env: &Env) -> Result<BytesN<32>, PublicInputError> {
+        let value = self.next_fr()?;
+        Ok(BytesN::from_array(env, &value.to_bytes()))
+    }
+
+    /// Check that every field was read.
+    pub fn finish(self) -> Result<(), PublicInputError> {
+        match self.len() {
+            0 => Ok(()),
+            count => Err(PublicInputError::Trailing { count }),
+        }
+    }
+}
@@ -1,8 +1,12 @@
 //! Sum-check verifier
 use crate::{
+    error::VerifyError,
     field::Fr,
     relations::accumulate_relation_evaluations,
-    types::{Transcript, VerificationKey, BATCHED_RELATION_PARTIAL_LENGTH},
+    types::{
+        Transcript, VerificationKey, ZkProof, ZkTranscript, BATCHED_RELATION_PARTIAL_LENGTH,
+        ZK_BATCHED_RELATION_PARTIAL_LENGTH,
+    },
 };
 
 const BARY_BYTES: [[u8; 32]; BATCHED_RELATION_PARTIAL_LENGTH] = [
@@ -48,6 +52,55 @@ const BARY_BYTES: [[u8; 32]; BATCHED_RELATION_PARTIAL_LENGTH] = [
     ],
 ];
 
+/// Barycentric denominators ∏_{j≠i} (i - j) over the ZK domain {0, …, 8}.
+const ZK_BARY_BYTES: [[u8; 32]; ZK_BATCHED_RELATION_PARTIAL_LENGTH] = [
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x9d, 0x80,
+    ],
+    [
+        0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58,
+        0x5d, 0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xef, 0xff,
+        0xec, 0x51,
+    ],
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x05, 0xa0,
+    ],
+    [
+        0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58,
+        0x5d, 0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xef, 0xff,
+        0xfd, 0x31,
+    ],
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x02, 0x40,
+    ],
+    [
+        0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58,
+        0x5d, 0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xef, 0xff,
+        0xfd, 0x31,
+    ],
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x05, 0xa0,
+    ],
+    [
+        0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58,
+        0x5d, 0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xef, 0xff,
+        0xec, 0x51,
+    ],
+    [
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x9d, 0x80,
+    ],
+];
+
 /// Check if the sum of two univariates equals the target value
 #[inline(always)]
 fn check_sum(round_univariate: &[Fr], round_target: Fr) -> bool {
@@ -57,27 +110,28 @@ fn check_sum(round_univariate: &[Fr], round_target: Fr) -> bool {
 
 /// Calculate next target value for the sum-check
 #[inline(always)]
-fn compute_next_target_sum(
-    round_univariate: &[Fr],
+fn compute_next_target_sum<const N: usize>(
+    round_univariate: &[Fr; N],
     round_challenge: Fr,
-) -> Result<Fr, &'static str> {
+    bary_bytes: &[[u8; 32]; N],
+) -> Option<Fr> {
     // B(χ) = ∏ (χ - i)
     let mut b_poly = Fr::one();
-    for i in 0..BATCHED_RELATION_PARTIAL_LENGTH {
+    for i in 0..N {
         b_poly = b_poly * (round_challenge - Fr::from_u64(i as u64));
     }
 
     // Σ u_i / (BARY[i] * (χ - i))
     let mut acc = Fr::zero();
-    for i in 0..BATCHED_RELATION_PARTIAL_LENGTH {
-        let bary_val = Fr::from_bytes(&BARY_BYTES[i]);
+    for i in 0..N {
+        let bary_val = Fr::from_bytes(&bary_bytes[i]);
 
         let denom = bary_val * (round_challenge - Fr::from_u64(i as u64));
-        let inv = denom.inverse().ok_or("denom zero")?;
+        let inv = denom.inverse()?;
         acc = acc + (round_univariate[i] * inv);
     }
 
-    Ok(b_poly * acc)
+    Some(b_poly * acc)
 }
 
 #[inline(always)]
@@ -93,7 +147,7 @@ pub fn verify_sumcheck(
     proof: &crate::types::Proof,
     tp: &Transcript,
     vk: &VerificationKey,
-) -> Result<(), &'static str> {
+) -> Result<(), VerifyError> {
     let log_n = vk.log_circuit_size as usize;
     let mut round_target = Fr::zero();
     let mut pow_partial_evaluation = Fr::one();
@@ -103,11 +157,12 @@ pub fn verify_sumcheck(
         let round_univariate = &proof.sumcheck_univariates[round];
 
         if !check_sum(round_univariate, round_target) {
-            return Err("round failed");
+            return Err(VerifyError::SumcheckRound { round });
         }
 
         let round_challenge = tp.sumcheck_u_challenges[round];
-        round_target = compute_next_target_sum(round_univariate, round_challenge)?;
+        round_target = compute_next_target_sum(round_univariate, round_challenge, &BARY_BYTES)
+            .ok_or(VerifyError::SumcheckDenominatorZero { round })?;
         pow_partial_evaluation = partially_evaluate_pow(
             tp.gate_challenges[round],
             pow_partial_evaluation,
@@ -137,6 +192,68 @@ pub fn verify_sumcheck(
             hex::encode((grand_honk_relation_sum - round_target).to_bytes())
         );
         crate::trace!("======================================");
-        Err("sumcheck final mismatch")
+        Err(VerifyError::SumcheckFinalMismatch)
+    }
+}
+
+/// ZK sum-check. The initial target is the Libra sum scaled by the Libra
+/// challenge, and the final relation value is corrected by the row-disabling
+/// polynomial and the Libra evaluation.
+pub fn verify_zk_sumcheck(
+    proof: &ZkProof,
+    tp: &ZkTranscript,
+    vk: &VerificationKey,
+) -> Result<(), VerifyError> {
+    let log_n = vk.log_circuit_size as usize;
+    let t = &tp.base;
+    let mut round_target = proof.libra_sum * tp.libra_challenge;
+    let mut pow_partial_evaluation = Fr::one();
+
+    // 1) Each round sum check and next target/pow calculation
+    for round in 0..log_n {
+        let round_univariate = &proof.sumcheck_univariates[round];
+
+        if !check_sum(round_univariate, round_target) {
+            return Err(VerifyError::SumcheckRound { round });
+        }
+
+        let round_challenge = t.sumcheck_u_challenges[round];
+        round_target =
+            compute_next_target_sum(round_univariate, round_challenge, &ZK_BARY_BYTES)
+                .ok_or(VerifyError::SumcheckDenominatorZero { round })?;
+        pow_partial_evaluation = partially_evaluate_pow(
+            t.gate_challenges[round],
+            pow_partial_evaluation,
+            round_challenge,
+        );
+    }
+
+    // 2) Final relation summation
+    let grand_honk_relation_sum = accumulate_relation_evaluations(
+        &proof.sumcheck_evaluations,
+        &t.rel_params,
+        &t.alphas,
+        pow_partial_evaluation,
+    );
+
+    // 3) Row-disabling polynomial 1 - ∏_{i=2}^{log_n-1} u_i, plus Libra
+    let mut disabled_rows = Fr::one();
+    for &u in t.sumcheck_u_challenges.iter().take(log_n).skip(2) {
+        disabled_rows = disabled_rows * u;
+    }
+    let grand_honk_relation_sum = grand_honk_relation_sum * (Fr::one() - disabled_rows)
+        + proof.libra_evaluation * tp.libra_challenge;
+
+    if grand_honk_relation_sum == round_target {
+        Ok(())
+    } else {
+        crate::trace!("===== ZK SUMCHECK FINAL CHECK FAILED =====");
+        crate::trace!(
+            "grand_relation = 0x{}",
+            hex::encode(grand_honk_relation_sum.to_bytes())
+        );
+        crate::trace!("target = 0x{}", hex::encode(round_target.to_bytes()));
+        crate::trace!("=========================================");
+        Err(VerifyError::SumcheckFinalMismatch)
     }
 }
@@ -2,16 +2,64 @@
 
 use crate::trace;
 use crate::{
+    backend::Backend,
     field::Fr,
-    hash::hash32,
     types::{
-        G1Point, Proof, RelationParameters, Transcript, CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ALPHAS,
+        G1Point, Proof, RelationParameters, Transcript, ZkProof, ZkTranscript,
+        CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ALPHAS, PAIRING_POINTS_SIZE,
     },
     utils::coord_to_halves_be,
 };
-use soroban_sdk::{Bytes, Env};
 
-fn push_point(buf: &mut Bytes, pt: &G1Point) {
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Commitments hashed before sumcheck, shared by the ZK and non-ZK proofs.
+struct WitnessCommitments<'a> {
+    pairing_point_object: &'a [Fr; PAIRING_POINTS_SIZE],
+    w1: &'a G1Point,
+    w2: &'a G1Point,
+    w3: &'a G1Point,
+    w4: &'a G1Point,
+    lookup_read_counts: &'a G1Point,
+    lookup_read_tags: &'a G1Point,
+    lookup_inverses: &'a G1Point,
+    z_perm: &'a G1Point,
+}
+
+impl<'a> From<&'a Proof> for WitnessCommitments<'a> {
+    fn from(proof: &'a Proof) -> Self {
+        WitnessCommitments {
+            pairing_point_object: &proof.pairing_point_object,
+            w1: &proof.w1,
+            w2: &proof.w2,
+            w3: &proof.w3,
+            w4: &proof.w4,
+            lookup_read_counts: &proof.lookup_read_counts,
+            lookup_read_tags: &proof.lookup_read_tags,
+            lookup_inverses: &proof.lookup_inverses,
+            z_perm: &proof.z_perm,
+        }
+    }
+}
+
+impl<'a> From<&'a ZkProof> for WitnessCommitments<'a> {
+    fn from(proof: &'a ZkProof) -> Self {
+        WitnessCommitments {
+            pairing_point_object: &proof.pairing_point_object,
+            w1: &proof.w1,
+            w2: &proof.w2,
+            w3: &proof.w3,
+            w4: &proof.w4,
+            lookup_read_counts: &proof.lookup_read_counts,
+            lookup_read_tags: &proof.lookup_read_tags,
+            lookup_inverses: &proof.lookup_inverses,
+            z_perm: &proof.z_perm,
+        }
+    }
+}
+
+fn push_point(buf: &mut Vec<u8>, pt: &G1Point) {
     // Serialize a coordinate into two bn254::Fr limbs (lo136, hi<=118)
     let (x_lo, x_hi) = coord_to_halves_be(&pt.x);
     let (y_lo, y_hi) = coord_to_halves_be(&pt.y);
@@ -31,8 +79,8 @@ fn split_challenge(challenge: Fr) -> (Fr, Fr) {
 }
 
 #[inline(always)]
-fn hash_to_fr(bytes: &Bytes) -> Fr {
-    Fr::from_bytes(&hash32(bytes))
+fn hash_to_fr<B: Backend>(backend: &B, bytes: &[u8]) -> Fr {
+    Fr::from_bytes(&backend.keccak256(bytes))
 }
 
 fn u64_to_be32(x: u64) -> [u8; 32] {
@@ -41,65 +89,60 @@ fn u64_to_be32(x: u64) -> [u8; 32] {
     out
 }
 
-fn generate_eta_challenge(
-    env: &Env,
-    proof: &Proof,
-    public_inputs: &Bytes,
+fn generate_eta_challenge<B: Backend>(
+    backend: &B,
+    proof: &WitnessCommitments,
+    public_inputs: &[u8],
     circuit_size: u64,
     public_inputs_size: u64,
     pub_inputs_offset: u64,
 ) -> (Fr, Fr, Fr, Fr) {
-    let mut data = Bytes::new(env);
+    let mut data = Vec::new();
     data.extend_from_slice(&u64_to_be32(circuit_size));
     data.extend_from_slice(&u64_to_be32(public_inputs_size));
     data.extend_from_slice(&u64_to_be32(pub_inputs_offset));
-    data.append(public_inputs);
-    for fr in &proof.pairing_point_object {
+    data.extend_from_slice(public_inputs);
+    for fr in proof.pairing_point_object {
         data.extend_from_slice(&fr.to_bytes());
     }
-    for w in &[&proof.w1, &proof.w2, &proof.w3] {
+    for w in [proof.w1, proof.w2, proof.w3] {
         push_point(&mut data, w);
     }
 
-    let previous_challenge = hash_to_fr(&data);
+    let previous_challenge = hash_to_fr(backend, &data);
     let (eta, eta_two) = split_challenge(previous_challenge);
-    let prev_bytes = Bytes::from_array(env, &previous_challenge.to_bytes());
-    let previous_challenge = hash_to_fr(&prev_bytes);
+    let previous_challenge = hash_to_fr(backend, &previous_challenge.to_bytes());
     let (eta_three, _) = split_challenge(previous_challenge);
 
     (eta, eta_two, eta_three, previous_challenge)
 }
 
-fn generate_beta_and_gamma_challenges(
-    env: &Env,
+fn generate_beta_and_gamma_challenges<B: Backend>(
+    backend: &B,
     previous_challenge: Fr,
-    proof: &Proof,
+    proof: &WitnessCommitments,
 ) -> (Fr, Fr, Fr) {
-    let mut data = Bytes::new(env);
+    let mut data = Vec::new();
     data.extend_from_slice(&previous_challenge.to_bytes());
-    for w in &[
-        &proof.lookup_read_counts,
-        &proof.lookup_read_tags,
-        &proof.w4,
-    ] {
+    for w in [proof.lookup_read_counts, proof.lookup_read_tags, proof.w4] {
         push_point(&mut data, w);
     }
-    let next_previous_challenge = hash_to_fr(&data);
+    let next_previous_challenge = hash_to_fr(backend, &data);
     let (beta, gamma) = split_challenge(next_previous_challenge);
     (beta, gamma, next_previous_challenge)
 }
 
-fn generate_alpha_challenges(
-    env: &Env,
+fn generate_alpha_challenges<B: Backend>(
+    backend: &B,
     previous_challenge: Fr,
-    proof: &Proof,
+    proof: &WitnessCommitments,
 ) -> ([Fr; NUMBER_OF_ALPHAS], Fr) {
-    let mut data = Bytes::new(env);
+    let mut data = Vec::new();
     data.extend_from_slice(&previous_challenge.to_bytes());
-    for w in &[&proof.lookup_inverses, &proof.z_perm] {
+    for w in [proof.lookup_inverses, proof.z_perm] {
         push_point(&mut data, w);
     }
-    let mut next_previous_challenge = hash_to_fr(&data);
+    let mut next_previous_challenge = hash_to_fr(backend, &data);
 
     let mut alphas = [Fr::zero(); NUMBER_OF_ALPHAS];
     let (a0, a1) = split_challenge(next_previous_challenge);
@@ -107,16 +150,14 @@ fn generate_alpha_challenges(
     alphas[1] = a1;
 
     for i in 1..(NUMBER_OF_ALPHAS / 2) {
-        let next_bytes = Bytes::from_array(env, &next_previous_challenge.to_bytes());
-        next_previous_challenge = hash_to_fr(&next_bytes);
+        next_previous_challenge = hash_to_fr(backend, &next_previous_challenge.to_bytes());
         let (lo, hi) = split_challenge(next_previous_challenge);
         alphas[2 * i] = lo;
         alphas[2 * i + 1] = hi;
     }
 
     if (NUMBER_OF_ALPHAS & 1) == 1 && NUMBER_OF_ALPHAS > 2 {
-        let next_bytes = Bytes::from_array(env, &next_previous_challenge.to_bytes());
-        next_previous_challenge = hash_to_fr(&next_bytes);
+        next_previous_challenge = hash_to_fr(backend, &next_previous_challenge.to_bytes());
         let (last, _) = split_challenge(next_previous_challenge);
         alphas[NUMBER_OF_ALPHAS - 1] = last;
     }
@@ -124,16 +165,16 @@ fn generate_alpha_challenges(
     (alphas, next_previous_challenge)
 }
 
-fn generate_relation_parameters_challenges(
-    env: &Env,
-    proof: &Proof,
-    public_inputs: &Bytes,
+fn generate_relation_parameters_challenges<B: Backend>(
+    backend: &B,
+    proof: &WitnessCommitments,
+    public_inputs: &[u8],
     circuit_size: u64,
     public_inputs_size: u64,
     pub_inputs_offset: u64,
 ) -> (RelationParameters, Fr) {
     let (eta, eta_two, eta_three, previous_challenge) = generate_eta_challenge(
-        env,
+        backend,
         proof,
         public_inputs,
         circuit_size,
@@ -141,7 +182,7 @@ fn generate_relation_parameters_challenges(
         pub_inputs_offset,
     );
     let (beta, gamma, next_previous_challenge) =
-        generate_beta_and_gamma_challenges(env, previous_challenge, proof);
+        generate_beta_and_gamma_challenges(backend, previous_challenge, proof);
     let rp = RelationParameters {
         eta,
         eta_two,
@@ -153,93 +194,146 @@ fn generate_relation_parameters_challenges(
     (rp, next_previous_challenge)
 }
 
-fn generate_gate_challenges(
-    env: &Env,
+fn generate_gate_challenges<B: Backend>(
+    backend: &B,
     previous_challenge: Fr,
 ) -> ([Fr; CONST_PROOF_SIZE_LOG_N], Fr) {
     let mut next_previous_challenge = previous_challenge;
     let mut gate_challenges = [Fr::zero(); CONST_PROOF_SIZE_LOG_N];
-    for i in 0..CONST_PROOF_SIZE_LOG_N {
-        let next_bytes = Bytes::from_array(env, &next_previous_challenge.to_bytes());
-        next_previous_challenge = hash_to_fr(&next_bytes);
-        gate_challenges[i] = split_challenge(next_previous_challenge).0;
+    for challenge in gate_challenges.iter_mut() {
+        next_previous_challenge = hash_to_fr(backend, &next_previous_challenge.to_bytes());
+        *challenge = split_challenge(next_previous_challenge).0;
     }
     (gate_challenges, next_previous_challenge)
 }
 
-fn generate_sumcheck_challenges(
-    env: &Env,
-    proof: &Proof,
+fn generate_sumcheck_challenges<const N: usize, B: Backend>(
+    backend: &B,
+    sumcheck_univariates: &[[Fr; N]; CONST_PROOF_SIZE_LOG_N],
     previous_challenge: Fr,
 ) -> ([Fr; CONST_PROOF_SIZE_LOG_N], Fr) {
     let mut next_previous_challenge = previous_challenge;
     let mut sumcheck_challenges = [Fr::zero(); CONST_PROOF_SIZE_LOG_N];
-    for r in 0..CONST_PROOF_SIZE_LOG_N {
-        let mut data = Bytes::new(env);
+    for (r, univariate) in sumcheck_univariates.iter().enumerate() {
+        let mut data = Vec::new();
         data.extend_from_slice(&next_previous_challenge.to_bytes());
-        for &c in proof.sumcheck_univariates[r].iter() {
+        for &c in univariate.iter() {
             data.extend_from_slice(&c.to_bytes());
         }
-        next_previous_challenge = hash_to_fr(&data);
+        next_previous_challenge = hash_to_fr(backend, &data);
         sumcheck_challenges[r] = split_challenge(next_previous_challenge).0;
     }
     (sumcheck_challenges, next_previous_challenge)
 }
 
-fn generate_rho_challenge(env: &Env, proof: &Proof, previous_challenge: Fr) -> (Fr, Fr) {
-    let mut data = Bytes::new(env);
+fn generate_rho_challenge<B: Backend>(
+    backend: &B,
+    proof: &Proof,
+    previous_challenge: Fr,
+) -> (Fr, Fr) {
+    let mut data = Vec::new();
     data.extend_from_slice(&previous_challenge.to_bytes());
     for &e in proof.sumcheck_evaluations.iter() {
         data.extend_from_slice(&e.to_bytes());
     }
-    let next_previous_challenge = hash_to_fr(&data);
+    let next_previous_challenge = hash_to_fr(backend, &data);
     let rho = split_challenge(next_previous_challenge).0;
     (rho, next_previous_challenge)
 }
 
-fn generate_gemini_r_challenge(env: &Env, proof: &Proof, previous_challenge: Fr) -> (Fr, Fr) {
-    let mut data = Bytes::new(env);
+fn generate_gemini_r_challenge<B: Backend>(
+    backend: &B,
+    gemini_fold_comms: &[G1Point],
+    previous_challenge: Fr,
+) -> (Fr, Fr) {
+    let mut data = Vec::new();
     data.extend_from_slice(&previous_challenge.to_bytes());
-    for pt in proof.gemini_fold_comms.iter() {
+    for pt in gemini_fold_comms.iter() {
         push_point(&mut data, pt);
     }
-    let next_previous_challenge = hash_to_fr(&data);
+    let next_previous_challenge = hash_to_fr(backend, &data);
     let gemini_r = split_challenge(next_previous_challenge).0;
     (gemini_r, next_previous_challenge)
 }
 
-fn generate_shplonk_nu_challenge(env: &Env, proof: &Proof, previous_challenge: Fr) -> (Fr, Fr) {
-    let mut data = Bytes::new(env);
+/// `libra_poly_evals` is empty for non-ZK proofs.
+fn generate_shplonk_nu_challenge<B: Backend>(
+    backend: &B,
+    gemini_a_evaluations: &[Fr],
+    libra_poly_evals: &[Fr],
+    previous_challenge: Fr,
+) -> (Fr, Fr) {
+    let mut data = Vec::new();
     data.extend_from_slice(&previous_challenge.to_bytes());
-    for &a in proof.gemini_a_evaluations.iter() {
+    for &a in gemini_a_evaluations.iter().chain(libra_poly_evals) {
         data.extend_from_slice(&a.to_bytes());
     }
-    let next_previous_challenge = hash_to_fr(&data);
+    let next_previous_challenge = hash_to_fr(backend, &data);
     let shplonk_nu = split_challenge(next_previous_challenge).0;
     (shplonk_nu, next_previous_challenge)
 }
 
-fn generate_shplonk_z_challenge(env: &Env, proof: &Proof, previous_challenge: Fr) -> (Fr, Fr) {
-    let mut data = Bytes::new(env);
+fn generate_shplonk_z_challenge<B: Backend>(
+    backend: &B,
+    shplonk_q: &G1Point,
+    previous_challenge: Fr,
+) -> (Fr, Fr) {
+    let mut data = Vec::new();
     data.extend_from_slice(&previous_challenge.to_bytes());
-    push_point(&mut data, &proof.shplonk_q);
-    let next_previous_challenge = hash_to_fr(&data);
+    push_point(&mut data, shplonk_q);
+    let next_previous_challenge = hash_to_fr(backend, &data);
     let shplonk_z = split_challenge(next_previous_challenge).0;
     (shplonk_z, next_previous_challenge)
 }
 
-pub fn generate_transcript(
-    env: &Env,
+fn generate_libra_challenge<B: Backend>(
+    backend: &B,
+    proof: &ZkProof,
+    previous_challenge: Fr,
+) -> (Fr, Fr) {
+    let mut data = Vec::new();
+    data.extend_from_slice(&previous_challenge.to_bytes());
+    push_point(&mut data, &proof.libra_commitments[0]);
+    data.extend_from_slice(&proof.libra_sum.to_bytes());
+    let next_previous_challenge = hash_to_fr(backend, &data);
+    let libra_challenge = split_challenge(next_previous_challenge).0;
+    (libra_challenge, next_previous_challenge)
+}
+
+fn generate_zk_rho_challenge<B: Backend>(
+    backend: &B,
+    proof: &ZkProof,
+    previous_challenge: Fr,
+) -> (Fr, Fr) {
+    let mut data = Vec::new();
+    data.extend_from_slice(&previous_challenge.to_bytes());
+    for &e in proof.sumcheck_evaluations.iter() {
+        data.extend_from_slice(&e.to_bytes());
+    }
+    data.extend_from_slice(&proof.libra_evaluation.to_bytes());
+    push_point(&mut data, &proof.libra_commitments[1]);
+    push_point(&mut data, &proof.libra_commitments[2]);
+    push_point(&mut data, &proof.gemini_masking_poly);
+    data.extend_from_slice(&proof.gemini_masking_eval.to_bytes());
+    let next_previous_challenge = hash_to_fr(backend, &data);
+    let rho = split_challenge(next_previous_challenge).0;
+    (rho, next_previous_challenge)
+}
+
+pub fn generate_transcript<B: Backend>(
+    backend: &B,
     proof: &Proof,
-    public_inputs: &Bytes,
+    public_inputs: &[u8],
     circuit_size: u64,
     public_inputs_size: u64,
     pub_inputs_offset: u64,
 ) -> Transcript {
+    let witness = WitnessCommitments::from(proof);
+
     // 1) eta/beta/gamma
     let (rp, previous_challenge) = generate_relation_parameters_challenges(
-        env,
-        proof,
+        backend,
+        &witness,
         public_inputs,
         circuit_size,
         public_inputs_size,
@@ -247,29 +341,34 @@ pub fn generate_transcript(
     );
 
     // 2) alphas
-    let (alphas, previous_challenge) = generate_alpha_challenges(env, previous_challenge, proof);
+    let (alphas, previous_challenge) =
+        generate_alpha_challenges(backend, previous_challenge, &witness);
 
     // 3) gate challenges
-    let (gate_chals, previous_challenge) = generate_gate_challenges(env, previous_challenge);
+    let (gate_chals, previous_challenge) = generate_gate_challenges(backend, previous_challenge);
 
     // 4) sumcheck challenges
     let (u_chals, previous_challenge) =
-        generate_sumcheck_challenges(env, proof, previous_challenge);
+        generate_sumcheck_challenges(backend, &proof.sumcheck_univariates, previous_challenge);
 
     // 5) rho
-    let (rho, previous_challenge) = generate_rho_challenge(env, proof, previous_challenge);
+    let (rho, previous_challenge) = generate_rho_challenge(backend, proof, previous_challenge);
 
     // 6) gemini_r
     let (gemini_r, previous_challenge) =
-        generate_gemini_r_challenge(env, proof, previous_challenge);
+        generate_gemini_r_challenge(backend, &proof.gemini_fold_comms, previous_challenge);
 
     // 7) shplonk_nu
-    let (shplonk_nu, previous_challenge) =
-        generate_shplonk_nu_challenge(env, proof, previous_challenge);
+    let (shplonk_nu, previous_challenge) = generate_shplonk_nu_challenge(
+        backend,
+        &proof.gemini_a_evaluations,
+        &[],
+        previous_challenge,
+    );
 
     // 8) shplonk_z
     let (shplonk_z, _previous_challenge) =
-        generate_shplonk_z_challenge(env, proof, previous_challenge);
+        generate_shplonk_z_challenge(backend, &proof.shplonk_q, previous_challenge);
 
     trace!("===== TRANSCRIPT PARAMETERS =====");
     trace!("eta = 0x{}", hex::encode(rp.eta.to_bytes()));
@@ -297,3 +396,83 @@ pub fn generate_transcript(
         shplonk_z,
     }
 }
+
+/// ZK transcript: the Libra concatenation commitment and sum are absorbed
+/// after the gate challenges, and the remaining Libra and Gemini masking data
+/// is absorbed into the rho and shplonk_nu rounds.
+pub fn generate_zk_transcript<B: Backend>(
+    backend: &B,
+    proof: &ZkProof,
+    public_inputs: &[u8],
+    circuit_size: u64,
+    public_inputs_size: u64,
+    pub_inputs_offset: u64,
+) -> ZkTranscript {
+    let witness = WitnessCommitments::from(proof);
+
+    // 1) eta/beta/gamma
+    let (rp, previous_challenge) = generate_relation_parameters_challenges(
+        backend,
+        &witness,
+        public_inputs,
+        circuit_size,
+        public_inputs_size,
+        pub_inputs_offset,
+    );
+
+    // 2) alphas
+    let (alphas, previous_challenge) =
+        generate_alpha_challenges(backend, previous_challenge, &witness);
+
+    // 3) gate challenges
+    let (gate_chals, previous_challenge) = generate_gate_challenges(backend, previous_challenge);
+
+    // 4) Libra challenge
+    let (libra_challenge, previous_challenge) =
+        generate_libra_challenge(backend, proof, previous_challenge);
+
+    // 5) sumcheck challenges
+    let (u_chals, previous_challenge) =
+        generate_sumcheck_challenges(backend, &proof.sumcheck_univariates, previous_challenge);
+
+    // 6) rho
+    let (rho, previous_challenge) = generate_zk_rho_challenge(backend, proof, previous_challenge);
+
+    // 7) gemini_r
+    let (gemini_r, previous_challenge) =
+        generate_gemini_r_challenge(backend, &proof.gemini_fold_comms, previous_challenge);
+
+    // 8) shplonk_nu
+    let (shplonk_nu, previous_challenge) = generate_shplonk_nu_challenge(
+        backend,
+        &proof.gemini_a_evaluations,
+        &proof.libra_poly_evals,
+        previous_challenge,
+    );
+
+    // 9) shplonk_z
+    let (shplonk_z, _previous_challenge) =
+        generate_shplonk_z_challenge(backend, &proof.shplonk_q, previous_challenge);
+
+    trace!("===== ZK TRANSCRIPT PARAMETERS =====");
+    trace!("libra_challenge = 0x{}", hex::encode(libra_challenge.to_bytes()));
+    trace!("rho = 0x{}", hex::encode(rho.to_bytes()));
+    trace!("gemini_r = 0x{}", hex::encode(gemini_r.to_bytes()));
+    trace!("shplonk_nu = 0x{}", hex::encode(shplonk_nu.to_bytes()));
+    trace!("shplonk_z = 0x{}", hex::encode(shplonk_z.to_bytes()));
+    trace!("====================================");
+
+    ZkTranscript {
+        base: Transcript {
+            rel_params: rp,
+            alphas,
+            gate_challenges: gate_chals,
+            sumcheck_u_challenges: u_chals,
+            rho,
+            gemini_r,
+            shplonk_nu,
+            shplonk_z,
+        },
+        libra_challenge,
+    }
+}
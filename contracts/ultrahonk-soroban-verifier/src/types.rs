@@ -9,6 +9,13 @@ pub const NUMBER_TO_BE_SHIFTED: usize = 5;
 pub const PAIRING_POINTS_SIZE: usize = 16;
 pub const NUMBER_OF_ALPHAS: usize = NUMBER_OF_SUBRELATIONS - 1;
 
+// ZK flavour (Libra masking)
+pub const ZK_BATCHED_RELATION_PARTIAL_LENGTH: usize = 9;
+pub const NUM_LIBRA_COMMITMENTS: usize = 3;
+pub const NUM_LIBRA_EVALUATIONS: usize = 4;
+pub const LIBRA_UNIVARIATES_LENGTH: usize = 9;
+pub const SUBGROUP_SIZE: usize = 256;
+
 /// Wire indices for the Ultra Honk protocol.
 #[derive(Copy, Clone, Debug)]
 pub enum Wire {
@@ -94,6 +101,10 @@ impl G1Point {
         }
     }
 
+    pub fn is_infinity(&self) -> bool {
+        self.x == [0u8; 32] && self.y == [0u8; 32]
+    }
+
     pub fn generator() -> Self {
         let mut x = [0u8; 32];
         let mut y = [0u8; 32];
@@ -110,7 +121,7 @@ impl Default for G1Point {
 }
 
 /// The verification key structure
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct VerificationKey {
     pub circuit_size: u64,
     pub log_circuit_size: u64,
@@ -174,6 +185,44 @@ pub struct Proof {
     pub kzg_quotient: G1Point,
 }
 
+/// The ZK Proof structure.
+///
+/// Same witness commitments as [`Proof`], plus the Libra masking data for
+/// sumcheck and the Gemini masking polynomial for Shplemini.
+#[derive(Clone, Debug)]
+pub struct ZkProof {
+    // Pairing point object (16 Fr elements)
+    pub pairing_point_object: [Fr; PAIRING_POINTS_SIZE],
+    // Wire commitments
+    pub w1: G1Point,
+    pub w2: G1Point,
+    pub w3: G1Point,
+    pub w4: G1Point,
+    // Lookup helpers
+    pub lookup_read_counts: G1Point,
+    pub lookup_read_tags: G1Point,
+    pub lookup_inverses: G1Point,
+    pub z_perm: G1Point,
+    // Libra: [concatenation, grand sum, quotient]
+    pub libra_commitments: [G1Point; NUM_LIBRA_COMMITMENTS],
+    pub libra_sum: Fr,
+    // Sumcheck polynomials
+    pub sumcheck_univariates: [[Fr; ZK_BATCHED_RELATION_PARTIAL_LENGTH]; CONST_PROOF_SIZE_LOG_N],
+    pub sumcheck_evaluations: [Fr; NUMBER_OF_ENTITIES],
+    pub libra_evaluation: Fr,
+    // Gemini masking polynomial
+    pub gemini_masking_poly: G1Point,
+    pub gemini_masking_eval: Fr,
+    // Gemini fold commitments
+    pub gemini_fold_comms: [G1Point; CONST_PROOF_SIZE_LOG_N - 1],
+    pub gemini_a_evaluations: [Fr; CONST_PROOF_SIZE_LOG_N],
+    // Libra: [concatenation(r), grand sum(g·r), grand sum(r), quotient(r)]
+    pub libra_poly_evals: [Fr; NUM_LIBRA_EVALUATIONS],
+    // Shplonk
+    pub shplonk_q: G1Point,
+    pub kzg_quotient: G1Point,
+}
+
 /// Relation parameters (η, η₂, η₃, β, γ, public_inputs_delta).
 #[derive(Clone, Debug)]
 pub struct RelationParameters {
@@ -197,3 +246,10 @@ pub struct Transcript {
     pub shplonk_nu: Fr,
     pub shplonk_z: Fr,
 }
+
+/// The ZK transcript: the non-ZK challenges plus the Libra challenge.
+#[derive(Clone, Debug)]
+pub struct ZkTranscript {
+    pub base: Transcript,
+    pub libra_challenge: Fr,
+}
@@ -1,14 +1,19 @@
-//! Utilities for loading Proof and VerificationKey, plus byte↔field/point conversion.
+//! Utilities for loading Proof, ZkProof and VerificationKey, plus byte↔field/point conversion.
 
+use crate::error::VerifyError;
 use crate::field::Fr;
+use crate::format::{PROOF_BYTES, VK_BYTES, ZK_PROOF_BYTES};
 use crate::types::{
-    G1Point, Proof, VerificationKey, BATCHED_RELATION_PARTIAL_LENGTH, CONST_PROOF_SIZE_LOG_N,
-    NUMBER_OF_ENTITIES, PAIRING_POINTS_SIZE,
+    G1Point, Proof, VerificationKey, ZkProof, BATCHED_RELATION_PARTIAL_LENGTH,
+    CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ENTITIES, NUM_LIBRA_EVALUATIONS, PAIRING_POINTS_SIZE,
+    ZK_BATCHED_RELATION_PARTIAL_LENGTH,
 };
-use crate::PROOF_BYTES;
 use core::array;
 use soroban_sdk::Bytes;
 
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
 /// Convert a 32-byte big-endian array into an Fr.
 fn bytes32_to_fr(bytes: &[u8; 32]) -> Fr {
     Fr::from_bytes(bytes)
@@ -23,11 +28,17 @@ pub fn coord_to_halves_be(coord: &[u8; 32]) -> ([u8; 32], [u8; 32]) {
     (low, high)
 }
 
-fn read_bytes<const N: usize>(bytes: &Bytes, idx: &mut u32) -> [u8; N] {
+fn read_bytes<const N: usize>(bytes: &[u8], idx: &mut usize) -> [u8; N] {
     let mut out = [0u8; N];
-    let end = *idx + N as u32;
-    bytes.slice(*idx..end).copy_into_slice(&mut out);
-    *idx = end;
+    out.copy_from_slice(&bytes[*idx..*idx + N]);
+    *idx += N;
+    out
+}
+
+/// Copy `Bytes` out of the host in a single call.
+pub fn bytes_to_vec(bytes: &Bytes) -> Vec<u8> {
+    let mut out = vec![0u8; bytes.len() as usize];
+    bytes.copy_into_slice(&mut out);
     out
 }
 
@@ -38,26 +49,40 @@ fn combine_limbs(lo: &[u8; 32], hi: &[u8; 32]) -> [u8; 32] {
     out
 }
 
+/// Read one G1 commitment from a proof.
+fn read_proof_point(bytes: &[u8], cur: &mut usize) -> G1Point {
+    let x0 = read_bytes::<32>(bytes, cur);
+    let x1 = read_bytes::<32>(bytes, cur);
+    let y0 = read_bytes::<32>(bytes, cur);
+    let y1 = read_bytes::<32>(bytes, cur);
+    let x = combine_limbs(&x0, &x1);
+    let y = combine_limbs(&y0, &y1);
+    G1Point { x, y }
+}
+
+fn check_proof_len(proof_bytes: &[u8], expected: usize) -> Result<(), VerifyError> {
+    let actual = proof_bytes.len();
+    if actual != expected {
+        return Err(VerifyError::ProofLength { expected, actual });
+    }
+    Ok(())
+}
+
 /// Load a Proof from a byte array.
 ///
 /// Note (bb v0.87.0): G1 coordinates are encoded as two limbs per coordinate
 /// using the (lo136, hi<=118) split and stored in the order (x_lo, x_hi, y_lo, y_hi).
-pub fn load_proof(proof_bytes: &Bytes) -> Proof {
-    assert_eq!(proof_bytes.len() as usize, PROOF_BYTES, "proof bytes len");
-    let mut boundary = 0u32;
-
-    fn bytes_to_g1_proof_point(bytes: &Bytes, cur: &mut u32) -> G1Point {
-        let x0 = read_bytes::<32>(bytes, cur);
-        let x1 = read_bytes::<32>(bytes, cur);
-        let y0 = read_bytes::<32>(bytes, cur);
-        let y1 = read_bytes::<32>(bytes, cur);
-        let x = combine_limbs(&x0, &x1);
-        let y = combine_limbs(&y0, &y1);
-        G1Point { x, y }
-    }
+pub fn load_proof(proof_bytes: &Bytes) -> Result<Proof, VerifyError> {
+    parse_proof(&bytes_to_vec(proof_bytes))
+}
+
+/// Parse a Proof from a plain byte slice; see [`load_proof`].
+pub fn parse_proof(proof_bytes: &[u8]) -> Result<Proof, VerifyError> {
+    check_proof_len(proof_bytes, PROOF_BYTES)?;
+    let mut boundary = 0usize;
 
     // Helper: bytesToFr (read next 32 bytes as Fr)
-    fn bytes_to_fr(bytes: &Bytes, cur: &mut u32) -> Fr {
+    fn bytes_to_fr(bytes: &[u8], cur: &mut usize) -> Fr {
         let arr = read_bytes::<32>(bytes, cur);
         bytes32_to_fr(&arr)
     }
@@ -67,27 +92,27 @@ pub fn load_proof(proof_bytes: &Bytes) -> Proof {
         array::from_fn(|_| bytes_to_fr(proof_bytes, &mut boundary));
 
     // 1) w1, w2, w3
-    let w1 = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
-    let w2 = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
-    let w3 = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
+    let w1 = read_proof_point(proof_bytes, &mut boundary);
+    let w2 = read_proof_point(proof_bytes, &mut boundary);
+    let w3 = read_proof_point(proof_bytes, &mut boundary);
 
     // 2) lookup_read_counts, lookup_read_tags
-    let lookup_read_counts = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
-    let lookup_read_tags = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
+    let lookup_read_counts = read_proof_point(proof_bytes, &mut boundary);
+    let lookup_read_tags = read_proof_point(proof_bytes, &mut boundary);
 
     // 3) w4
-    let w4 = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
+    let w4 = read_proof_point(proof_bytes, &mut boundary);
 
     // 4) lookup_inverses, z_perm
-    let lookup_inverses = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
-    let z_perm = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
+    let lookup_inverses = read_proof_point(proof_bytes, &mut boundary);
+    let z_perm = read_proof_point(proof_bytes, &mut boundary);
 
     // 5) sumcheck_univariates
     let mut sumcheck_univariates =
         [[Fr::zero(); BATCHED_RELATION_PARTIAL_LENGTH]; CONST_PROOF_SIZE_LOG_N];
-    for r in 0..CONST_PROOF_SIZE_LOG_N {
-        for i in 0..BATCHED_RELATION_PARTIAL_LENGTH {
-            sumcheck_univariates[r][i] = bytes_to_fr(proof_bytes, &mut boundary);
+    for round in sumcheck_univariates.iter_mut() {
+        for coeff in round.iter_mut() {
+            *coeff = bytes_to_fr(proof_bytes, &mut boundary);
         }
     }
 
@@ -97,17 +122,17 @@ pub fn load_proof(proof_bytes: &Bytes) -> Proof {
 
     // 7) gemini_fold_comms
     let gemini_fold_comms: [G1Point; CONST_PROOF_SIZE_LOG_N - 1] =
-        array::from_fn(|_| bytes_to_g1_proof_point(proof_bytes, &mut boundary));
+        array::from_fn(|_| read_proof_point(proof_bytes, &mut boundary));
 
     // 8) gemini_a_evaluations
     let gemini_a_evaluations: [Fr; CONST_PROOF_SIZE_LOG_N] =
         array::from_fn(|_| bytes_to_fr(proof_bytes, &mut boundary));
 
     // 9) shplonk_q, kzg_quotient
-    let shplonk_q = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
-    let kzg_quotient = bytes_to_g1_proof_point(proof_bytes, &mut boundary);
+    let shplonk_q = read_proof_point(proof_bytes, &mut boundary);
+    let kzg_quotient = read_proof_point(proof_bytes, &mut boundary);
 
-    Proof {
+    Ok(Proof {
         pairing_point_object,
         w1,
         w2,
@@ -123,63 +148,177 @@ pub fn load_proof(proof_bytes: &Bytes) -> Proof {
         gemini_a_evaluations,
         shplonk_q,
         kzg_quotient,
+    })
+}
+
+/// Load a ZK Proof from a byte array.
+///
+/// Elements follow the transcript order: the Libra concatenation commitment and
+/// sum precede the sumcheck rounds, the Libra evaluation, grand sum and quotient
+/// commitments and the Gemini masking data follow the sumcheck evaluations, and
+/// the four Libra polynomial evaluations follow the Gemini evaluations.
+pub fn load_zk_proof(proof_bytes: &Bytes) -> Result<ZkProof, VerifyError> {
+    parse_zk_proof(&bytes_to_vec(proof_bytes))
+}
+
+/// Parse a ZK Proof from a plain byte slice; see [`load_zk_proof`].
+pub fn parse_zk_proof(proof_bytes: &[u8]) -> Result<ZkProof, VerifyError> {
+    check_proof_len(proof_bytes, ZK_PROOF_BYTES)?;
+    let mut boundary = 0usize;
+
+    let point = |cur: &mut usize| read_proof_point(proof_bytes, cur);
+    let fr = |cur: &mut usize| bytes32_to_fr(&read_bytes::<32>(proof_bytes, cur));
+
+    // 0) pairing point object
+    let pairing_point_object: [Fr; PAIRING_POINTS_SIZE] = array::from_fn(|_| fr(&mut boundary));
+
+    // 1) w1, w2, w3
+    let w1 = point(&mut boundary);
+    let w2 = point(&mut boundary);
+    let w3 = point(&mut boundary);
+
+    // 2) lookup_read_counts, lookup_read_tags
+    let lookup_read_counts = point(&mut boundary);
+    let lookup_read_tags = point(&mut boundary);
+
+    // 3) w4
+    let w4 = point(&mut boundary);
+
+    // 4) lookup_inverses, z_perm
+    let lookup_inverses = point(&mut boundary);
+    let z_perm = point(&mut boundary);
+
+    // 5) Libra concatenation commitment and sum
+    let libra_concatenation = point(&mut boundary);
+    let libra_sum = fr(&mut boundary);
+
+    // 6) sumcheck_univariates
+    let mut sumcheck_univariates =
+        [[Fr::zero(); ZK_BATCHED_RELATION_PARTIAL_LENGTH]; CONST_PROOF_SIZE_LOG_N];
+    for round in sumcheck_univariates.iter_mut() {
+        for coeff in round.iter_mut() {
+            *coeff = fr(&mut boundary);
+        }
     }
+
+    // 7) sumcheck_evaluations, Libra evaluation
+    let sumcheck_evaluations: [Fr; NUMBER_OF_ENTITIES] = array::from_fn(|_| fr(&mut boundary));
+    let libra_evaluation = fr(&mut boundary);
+
+    // 8) Libra grand sum and quotient commitments
+    let libra_grand_sum = point(&mut boundary);
+    let libra_quotient = point(&mut boundary);
+
+    // 9) Gemini masking polynomial
+    let gemini_masking_poly = point(&mut boundary);
+    let gemini_masking_eval = fr(&mut boundary);
+
+    // 10) gemini_fold_comms
+    let gemini_fold_comms: [G1Point; CONST_PROOF_SIZE_LOG_N - 1] =
+        array::from_fn(|_| point(&mut boundary));
+
+    // 11) gemini_a_evaluations, Libra polynomial evaluations
+    let gemini_a_evaluations: [Fr; CONST_PROOF_SIZE_LOG_N] = array::from_fn(|_| fr(&mut boundary));
+    let libra_poly_evals: [Fr; NUM_LIBRA_EVALUATIONS] = array::from_fn(|_| fr(&mut boundary));
+
+    // 12) shplonk_q, kzg_quotient
+    let shplonk_q = point(&mut boundary);
+    let kzg_quotient = point(&mut boundary);
+
+    Ok(ZkProof {
+        pairing_point_object,
+        w1,
+        w2,
+        w3,
+        w4,
+        lookup_read_counts,
+        lookup_read_tags,
+        lookup_inverses,
+        z_perm,
+        libra_commitments: [libra_concatenation, libra_grand_sum, libra_quotient],
+        libra_sum,
+        sumcheck_univariates,
+        sumcheck_evaluations,
+        libra_evaluation,
+        gemini_masking_poly,
+        gemini_masking_eval,
+        gemini_fold_comms,
+        gemini_a_evaluations,
+        libra_poly_evals,
+        shplonk_q,
+        kzg_quotient,
+    })
 }
 
 /// Load a VerificationKey.
-pub fn load_vk_from_bytes(bytes: &Bytes) -> Option<VerificationKey> {
-    const HEADER_WORDS: usize = 4;
-    const NUM_POINTS: usize = 27;
-    const EXPECTED_LEN: usize = HEADER_WORDS * 8 + NUM_POINTS * 64;
-    if bytes.len() as usize != EXPECTED_LEN {
-        return None;
+pub fn load_vk_from_bytes(bytes: &Bytes) -> Result<VerificationKey, VerifyError> {
+    let actual = bytes.len() as usize;
+    if actual != VK_BYTES {
+        return Err(VerifyError::VkLength { actual });
+    }
+    let mut buf = [0u8; VK_BYTES];
+    bytes.copy_into_slice(&mut buf);
+    parse_vk(&buf)
+}
+
+/// Parse a VerificationKey from a plain byte slice. [`load_vk_from_bytes`]
+/// and the VK code generator share this.
+pub fn parse_vk(bytes: &[u8]) -> Result<VerificationKey, VerifyError> {
+    let actual = bytes.len();
+    if actual != VK_BYTES {
+        return Err(VerifyError::VkLength { actual });
     }
 
-    fn read_u64(bytes: &Bytes, idx: &mut u32) -> u64 {
+    fn read_u64(bytes: &[u8], idx: &mut usize) -> u64 {
         u64::from_be_bytes(read_bytes::<8>(bytes, idx))
     }
-    fn read_point(bytes: &Bytes, idx: &mut u32) -> Option<G1Point> {
+    fn read_point(bytes: &[u8], idx: &mut usize) -> G1Point {
         let x = read_bytes::<32>(bytes, idx);
         let y = read_bytes::<32>(bytes, idx);
         // Curve, subgroup checks are executed in the Soroban host.
-        Some(G1Point { x, y })
+        G1Point { x, y }
     }
 
-    let mut idx = 0u32;
+    let mut idx = 0usize;
     let circuit_size = read_u64(bytes, &mut idx);
     let log_circuit_size = read_u64(bytes, &mut idx);
     let public_inputs_size = read_u64(bytes, &mut idx);
     let _pub_inputs_offset = read_u64(bytes, &mut idx);
+    // Proofs are padded to CONST_PROOF_SIZE_LOG_N rounds, so larger circuits
+    // cannot be verified
+    if log_circuit_size == 0 || log_circuit_size > CONST_PROOF_SIZE_LOG_N as u64 {
+        return Err(VerifyError::InvalidVkHeader);
+    }
+
+    let qm = read_point(bytes, &mut idx);
+    let qc = read_point(bytes, &mut idx);
+    let ql = read_point(bytes, &mut idx);
+    let qr = read_point(bytes, &mut idx);
+    let qo = read_point(bytes, &mut idx);
+    let q4 = read_point(bytes, &mut idx);
+    let q_lookup = read_point(bytes, &mut idx);
+    let q_arith = read_point(bytes, &mut idx);
+    let q_delta_range = read_point(bytes, &mut idx);
+    let q_elliptic = read_point(bytes, &mut idx);
+    let q_aux = read_point(bytes, &mut idx);
+    let q_poseidon2_external = read_point(bytes, &mut idx);
+    let q_poseidon2_internal = read_point(bytes, &mut idx);
+    let s1 = read_point(bytes, &mut idx);
+    let s2 = read_point(bytes, &mut idx);
+    let s3 = read_point(bytes, &mut idx);
+    let s4 = read_point(bytes, &mut idx);
+    let id1 = read_point(bytes, &mut idx);
+    let id2 = read_point(bytes, &mut idx);
+    let id3 = read_point(bytes, &mut idx);
+    let id4 = read_point(bytes, &mut idx);
+    let t1 = read_point(bytes, &mut idx);
+    let t2 = read_point(bytes, &mut idx);
+    let t3 = read_point(bytes, &mut idx);
+    let t4 = read_point(bytes, &mut idx);
+    let lagrange_first = read_point(bytes, &mut idx);
+    let lagrange_last = read_point(bytes, &mut idx);
 
-    let qm = read_point(bytes, &mut idx)?;
-    let qc = read_point(bytes, &mut idx)?;
-    let ql = read_point(bytes, &mut idx)?;
-    let qr = read_point(bytes, &mut idx)?;
-    let qo = read_point(bytes, &mut idx)?;
-    let q4 = read_point(bytes, &mut idx)?;
-    let q_lookup = read_point(bytes, &mut idx)?;
-    let q_arith = read_point(bytes, &mut idx)?;
-    let q_delta_range = read_point(bytes, &mut idx)?;
-    let q_elliptic = read_point(bytes, &mut idx)?;
-    let q_aux = read_point(bytes, &mut idx)?;
-    let q_poseidon2_external = read_point(bytes, &mut idx)?;
-    let q_poseidon2_internal = read_point(bytes, &mut idx)?;
-    let s1 = read_point(bytes, &mut idx)?;
-    let s2 = read_point(bytes, &mut idx)?;
-    let s3 = read_point(bytes, &mut idx)?;
-    let s4 = read_point(bytes, &mut idx)?;
-    let id1 = read_point(bytes, &mut idx)?;
-    let id2 = read_point(bytes, &mut idx)?;
-    let id3 = read_point(bytes, &mut idx)?;
-    let id4 = read_point(bytes, &mut idx)?;
-    let t1 = read_point(bytes, &mut idx)?;
-    let t2 = read_point(bytes, &mut idx)?;
-    let t3 = read_point(bytes, &mut idx)?;
-    let t4 = read_point(bytes, &mut idx)?;
-    let lagrange_first = read_point(bytes, &mut idx)?;
-    let lagrange_last = read_point(bytes, &mut idx)?;
-
-    Some(VerificationKey {
+    Ok(VerificationKey {
         circuit_size,
         log_circuit_size,
         public_inputs_size,
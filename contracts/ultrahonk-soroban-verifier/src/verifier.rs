@@ -1,138 +1,239 @@
 //! UltraHonk verifier
 
+pub use crate::error::VerifyError;
 use crate::{
+    backend::Backend,
     field::Fr,
-    shplemini::verify_shplemini,
-    sumcheck::verify_sumcheck,
-    transcript::generate_transcript,
-    types::PAIRING_POINTS_SIZE,
-    utils::{load_proof, load_vk_from_bytes},
+    format::ZK_PROOF_BYTES,
+    shplemini::{verify_shplemini, verify_zk_shplemini},
+    sumcheck::{verify_sumcheck, verify_zk_sumcheck},
+    transcript::{generate_transcript, generate_zk_transcript},
+    types::{VerificationKey, PAIRING_POINTS_SIZE},
+    utils::{bytes_to_vec, load_vk_from_bytes, parse_proof, parse_zk_proof},
 };
 use soroban_sdk::{Bytes, Env};
 
-/// Error type describing the specific reason verification failed.
-#[derive(Debug)]
-pub enum VerifyError {
-    InvalidInput(&'static str),
-    SumcheckFailed(&'static str),
-    ShplonkFailed(&'static str),
-}
-
 pub struct UltraHonkVerifier {
     env: Env,
-    vk: crate::types::VerificationKey,
+    vk: VerificationKey,
 }
 
 impl UltraHonkVerifier {
-    pub fn new_with_vk(env: &Env, vk: crate::types::VerificationKey) -> Self {
+    pub fn new_with_vk(env: &Env, vk: VerificationKey) -> Self {
         Self {
             env: env.clone(),
             vk,
         }
     }
 
+    /// Build from VK bytes.
     pub fn new(env: &Env, vk_bytes: &Bytes) -> Result<Self, VerifyError> {
-        load_vk_from_bytes(vk_bytes)
-            .map(|vk| Self::new_with_vk(env, vk))
-            .ok_or(VerifyError::InvalidInput("vk parse error"))
+        let vk = load_vk_from_bytes(vk_bytes)?;
+        Ok(Self::new_with_vk(env, vk))
     }
 
     /// Expose a reference to the parsed VK for debugging/inspection.
-    pub fn get_vk(&self) -> &crate::types::VerificationKey {
+    pub fn get_vk(&self) -> &VerificationKey {
         &self.vk
     }
 
-    /// Top-level verify
+    /// Top-level verify.
     pub fn verify(
         &self,
         proof_bytes: &Bytes,
         public_inputs_bytes: &Bytes,
     ) -> Result<(), VerifyError> {
-        // 1) parse proof
-        let proof = load_proof(proof_bytes);
-
-        // 2) sanity on public inputs (length and VK metadata if present)
-        if public_inputs_bytes.len() % 32 != 0 {
-            return Err(VerifyError::InvalidInput(
-                "public inputs must be 32-byte aligned",
-            ));
-        }
-        let provided = (public_inputs_bytes.len() / 32) as u64;
-        let expected = self
-            .vk
-            .public_inputs_size
-            .checked_sub(PAIRING_POINTS_SIZE as u64)
-            .ok_or(VerifyError::InvalidInput("vk inputs < 16"))?;
-        if expected != provided {
-            return Err(VerifyError::InvalidInput("public inputs mismatch"));
-        }
+        verify_proof(
+            &self.env,
+            &self.vk,
+            &bytes_to_vec(proof_bytes),
+            &bytes_to_vec(public_inputs_bytes),
+        )
+    }
 
-        // 3) Fiat–Shamir transcript
-        let pis_total = provided + PAIRING_POINTS_SIZE as u64;
-        let pub_inputs_offset = 1;
-        let mut t = generate_transcript(
+    /// Top-level verify for ZK proofs (`bb prove --zk`).
+    pub fn verify_zk(
+        &self,
+        proof_bytes: &Bytes,
+        public_inputs_bytes: &Bytes,
+    ) -> Result<(), VerifyError> {
+        verify_zk_proof(
             &self.env,
-            &proof,
-            public_inputs_bytes,
-            self.vk.circuit_size,
-            pis_total,
-            pub_inputs_offset,
-        );
-
-        // 4) Public delta
-        t.rel_params.public_inputs_delta = Self::compute_public_input_delta(
-            public_inputs_bytes,
-            &proof.pairing_point_object,
-            t.rel_params.beta,
-            t.rel_params.gamma,
-            pub_inputs_offset,
-            self.vk.circuit_size,
+            &self.vk,
+            &bytes_to_vec(proof_bytes),
+            &bytes_to_vec(public_inputs_bytes),
         )
-        .map_err(VerifyError::InvalidInput)?;
+    }
 
-        // 5) Sum-check
-        verify_sumcheck(&proof, &t, &self.vk).map_err(VerifyError::SumcheckFailed)?;
+    /// Whether a proof of `len` bytes is a ZK proof.
+    pub fn is_zk_proof(&self, len: usize) -> bool {
+        is_zk_proof(len)
+    }
 
-        // 6) Shplonk
-        verify_shplemini(&self.env, &proof, &self.vk, &t).map_err(VerifyError::ShplonkFailed)?;
+    /// Verify a plain or ZK proof; ZK proofs are told apart by length.
+    pub fn verify_auto(
+        &self,
+        proof_bytes: &Bytes,
+        public_inputs_bytes: &Bytes,
+    ) -> Result<(), VerifyError> {
+        if self.is_zk_proof(proof_bytes.len() as usize) {
+            self.verify_zk(proof_bytes, public_inputs_bytes)
+        } else {
+            self.verify(proof_bytes, public_inputs_bytes)
+        }
+    }
+}
+
+/// Whether a proof of `len` bytes has the ZK layout.
+pub(crate) fn is_zk_proof(len: usize) -> bool {
+    len == ZK_PROOF_BYTES
+}
+
+/// Verify a proof with the given backend. Both [`UltraHonkVerifier`] and the
+/// `std` [`crate::native::NativeVerifier`] run this.
+pub fn verify_proof<B: Backend>(
+    backend: &B,
+    vk: &VerificationKey,
+    proof_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+) -> Result<(), VerifyError> {
+    // 1) parse proof
+    let proof = parse_proof(proof_bytes)?;
+
+    // 2) sanity on public inputs (length and VK metadata if present)
+    let provided = check_public_inputs(vk, public_inputs_bytes)?;
+
+    // 3) Fiat–Shamir transcript
+    let pis_total = provided + PAIRING_POINTS_SIZE as u64;
+    let pub_inputs_offset = 1;
+    let mut t = generate_transcript(
+        backend,
+        &proof,
+        public_inputs_bytes,
+        vk.circuit_size,
+        pis_total,
+        pub_inputs_offset,
+    );
+
+    // 4) Public delta
+    t.rel_params.public_inputs_delta = compute_public_input_delta(
+        public_inputs_bytes,
+        &proof.pairing_point_object,
+        t.rel_params.beta,
+        t.rel_params.gamma,
+        pub_inputs_offset,
+        vk.circuit_size,
+    )?;
+
+    // 5) Sum-check
+    verify_sumcheck(&proof, &t, vk)?;
+
+    // 6) Shplonk
+    verify_shplemini(backend, &proof, vk, &t)?;
+
+    Ok(())
+}
 
-        Ok(())
+/// Verify a ZK proof with the given backend; see [`verify_proof`].
+pub fn verify_zk_proof<B: Backend>(
+    backend: &B,
+    vk: &VerificationKey,
+    proof_bytes: &[u8],
+    public_inputs_bytes: &[u8],
+) -> Result<(), VerifyError> {
+    // 1) parse proof
+    let proof = parse_zk_proof(proof_bytes)?;
+
+    // 2) sanity on public inputs
+    let provided = check_public_inputs(vk, public_inputs_bytes)?;
+
+    // 3) Fiat–Shamir transcript
+    let pis_total = provided + PAIRING_POINTS_SIZE as u64;
+    let pub_inputs_offset = 1;
+    let mut t = generate_zk_transcript(
+        backend,
+        &proof,
+        public_inputs_bytes,
+        vk.circuit_size,
+        pis_total,
+        pub_inputs_offset,
+    );
+
+    // 4) Public delta
+    t.base.rel_params.public_inputs_delta = compute_public_input_delta(
+        public_inputs_bytes,
+        &proof.pairing_point_object,
+        t.base.rel_params.beta,
+        t.base.rel_params.gamma,
+        pub_inputs_offset,
+        vk.circuit_size,
+    )?;
+
+    // 5) Sum-check
+    verify_zk_sumcheck(&proof, &t, vk)?;
+
+    // 6) Shplonk
+    verify_zk_shplemini(backend, &proof, vk, &t)?;
+
+    Ok(())
+}
+
+/// Check public inputs are 32-byte aligned and match the VK; returns the
+/// number provided (excluding the pairing point object).
+fn check_public_inputs(
+    vk: &VerificationKey,
+    public_inputs_bytes: &[u8],
+) -> Result<u64, VerifyError> {
+    if !public_inputs_bytes.len().is_multiple_of(32) {
+        return Err(VerifyError::PublicInputsNotAligned {
+            len: public_inputs_bytes.len(),
+        });
     }
+    let provided = (public_inputs_bytes.len() / 32) as u64;
+    let expected = vk
+        .public_inputs_size
+        .checked_sub(PAIRING_POINTS_SIZE as u64)
+        .ok_or(VerifyError::InvalidVkHeader)?;
+    if expected != provided {
+        return Err(VerifyError::PublicInputsCount {
+            expected,
+            actual: provided,
+        });
+    }
+    Ok(provided)
+}
 
-    fn compute_public_input_delta(
-        public_inputs: &Bytes,
-        pairing_point_object: &[Fr],
-        beta: Fr,
-        gamma: Fr,
-        offset: u64,
-        n: u64,
-    ) -> Result<Fr, &'static str> {
-        let mut numerator = Fr::one();
-        let mut denominator = Fr::one();
-
-        let mut numerator_acc = gamma + beta * Fr::from_u64(n + offset);
-        let mut denominator_acc = gamma - beta * Fr::from_u64(offset + 1);
-
-        let mut idx = 0u32;
-        while idx < public_inputs.len() {
-            let mut arr = [0u8; 32];
-            public_inputs.slice(idx..idx + 32).copy_into_slice(&mut arr);
-            let public_input = Fr::from_bytes(&arr);
-            numerator = numerator * (numerator_acc + public_input);
-            denominator = denominator * (denominator_acc + public_input);
-            numerator_acc = numerator_acc + beta;
-            denominator_acc = denominator_acc - beta;
-            idx += 32;
-        }
-        for public_input in pairing_point_object {
-            numerator = numerator * (numerator_acc + *public_input);
-            denominator = denominator * (denominator_acc + *public_input);
-            numerator_acc = numerator_acc + beta;
-            denominator_acc = denominator_acc - beta;
-        }
-        let denominator_inv = denominator
-            .inverse()
-            .ok_or("public input delta denom is zero")?;
-        Ok(numerator * denominator_inv)
+fn compute_public_input_delta(
+    public_inputs: &[u8],
+    pairing_point_object: &[Fr],
+    beta: Fr,
+    gamma: Fr,
+    offset: u64,
+    n: u64,
+) -> Result<Fr, VerifyError> {
+    let mut numerator = Fr::one();
+    let mut denominator = Fr::one();
+
+    let mut numerator_acc = gamma + beta * Fr::from_u64(n + offset);
+    let mut denominator_acc = gamma - beta * Fr::from_u64(offset + 1);
+
+    for chunk in public_inputs.chunks_exact(32) {
+        let mut arr = [0u8; 32];
+        arr.copy_from_slice(chunk);
+        let public_input = Fr::from_bytes(&arr);
+        numerator = numerator * (numerator_acc + public_input);
+        denominator = denominator * (denominator_acc + public_input);
+        numerator_acc = numerator_acc + beta;
+        denominator_acc = denominator_acc - beta;
+    }
+    for public_input in pairing_point_object {
+        numerator = numerator * (numerator_acc + *public_input);
+        denominator = denominator * (denominator_acc + *public_input);
+        numerator_acc = numerator_acc + beta;
+        denominator_acc = denominator_acc - beta;
     }
+    let denominator_inv = denominator
+        .inverse()
+        .ok_or(VerifyError::PublicInputDeltaDenominatorZero)?;
+    Ok(numerator * denominator_inv)
 }
@@ -0,0 +1,203 @@
+//! CPU instruction budgets for the host-heavy Shplemini MSM.
+//!
+//! Run with `cargo test --test budget_test -- --nocapture` to print the counts.
+
+use soroban_sdk::{
+    crypto::bn254::{Bn254G1Affine, Fr as Bn254Fr},
+    testutils::Ledger,
+    Bytes, BytesN, Env,
+};
+use ultrahonk_soroban_verifier::{
+    ec::{g1_msm, helpers::to_affine, pairing_check},
+    field::Fr,
+    shplemini::verify_shplemini,
+    types::{
+        G1Point, RelationParameters, Transcript, CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ALPHAS,
+        NUMBER_OF_ENTITIES, NUMBER_UNSHIFTED,
+    },
+    utils::{load_proof, load_vk_from_bytes},
+    VerifyError, PROOF_BYTES,
+};
+
+const LOG_N: usize = 5;
+/// VK selectors left at infinity, as in circuits without lookups, elliptic
+/// curve or Poseidon2 gates.
+const EMPTY_SELECTORS: [usize; 4] = [6, 9, 11, 12];
+
+fn env() -> Env {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    env
+}
+
+/// CPU instructions charged while running `f`.
+fn measure<T>(env: &Env, f: impl FnOnce() -> T) -> (T, u64) {
+    let mut budget = env.cost_estimate().budget();
+    budget.reset_unlimited();
+    let out = f();
+    (out, env.cost_estimate().budget().cpu_instruction_cost())
+}
+
+/// k·G
+fn point(env: &Env, k: u64) -> G1Point {
+    let g = to_affine(env, &G1Point::generator());
+    let scalar = Bn254Fr::from_bytes(BytesN::from_array(env, &Fr::from_u64(k).to_bytes()));
+    G1Point::from_bytes(env.crypto().bn254().g1_mul(&g, &scalar).to_array())
+}
+
+/// The per-term MSM used before batching: one `g1_mul` and one `g1_add` for
+/// every non-zero scalar.
+fn naive_msm(env: &Env, coms: &[G1Point], scalars: &[Fr]) -> Bn254G1Affine {
+    let bn = env.crypto().bn254();
+    let mut acc = to_affine(env, &G1Point::infinity());
+    for (c, s) in coms.iter().zip(scalars) {
+        if s.is_zero() {
+            continue;
+        }
+        let scalar = Bn254Fr::from_bytes(BytesN::from_array(env, &s.to_bytes()));
+        acc = bn.g1_add(&acc, &bn.g1_mul(&to_affine(env, c), &scalar));
+    }
+    acc
+}
+
+/// VK and witness commitments in evaluation order, shifted entities included.
+fn entity_points(env: &Env) -> Vec<G1Point> {
+    let mut points: Vec<G1Point> = (0..NUMBER_UNSHIFTED as u64)
+        .map(|k| point(env, 100 + k))
+        .collect();
+    for i in EMPTY_SELECTORS {
+        points[i] = G1Point::infinity();
+    }
+    let shifted = points[NUMBER_UNSHIFTED - 8..NUMBER_UNSHIFTED - 3].to_vec();
+    points.extend(shifted);
+    points
+}
+
+#[test]
+fn msm_matches_per_term_msm() {
+    let env = env();
+    let mut coms: Vec<G1Point> = (1..=12).map(|k| point(&env, k)).collect();
+    coms[3] = G1Point::infinity();
+    let mut scalars: Vec<Fr> = (1..=12).map(|k| Fr::from_u64(k * 7919)).collect();
+    scalars[0] = Fr::one();
+    scalars[5] = Fr::zero();
+
+    let (expected, before) = measure(&env, || naive_msm(&env, &coms, &scalars));
+    let (actual, after) = measure(&env, || g1_msm(&env, &coms, &scalars).unwrap());
+    assert_eq!(actual.to_array(), expected.to_array());
+    println!("12-term MSM: {before} -> {after} CPU instructions");
+    assert!(after < before);
+
+    // Nothing to add up
+    let empty = g1_msm(&env, &coms[3..4], &scalars[3..4]).unwrap();
+    assert_eq!(empty.to_array(), [0u8; 64]);
+    assert_eq!(
+        g1_msm(&env, &coms, &scalars[1..]).err(),
+        Some(VerifyError::MsmLength)
+    );
+}
+
+#[test]
+fn shplemini_msm_budget() {
+    let env = env();
+    let entities = entity_points(&env);
+    let folds: Vec<G1Point> = (0..LOG_N as u64 - 1).map(|k| point(&env, 200 + k)).collect();
+    let [shplonk_q, kzg_quotient] = [point(&env, 300), point(&env, 301)];
+
+    // Before: Q, 40 entities (shifted repeated, empty selectors multiplied),
+    // the circuit's fold rounds, the generator and the KZG quotient
+    let mut coms = vec![shplonk_q];
+    coms.extend(&entities);
+    coms.extend(&folds);
+    coms.extend([G1Point::generator(), kzg_quotient]);
+    let mut scalars: Vec<Fr> = (0..coms.len() as u64).map(|k| Fr::from_u64(k + 2)).collect();
+    scalars[0] = Fr::one();
+    let (expected, before) = measure(&env, || naive_msm(&env, &coms, &scalars));
+
+    // After: shifted scalars folded into their unshifted commitments
+    let mut folded = scalars.clone();
+    for k in 0..NUMBER_OF_ENTITIES - NUMBER_UNSHIFTED {
+        let shifted = 1 + NUMBER_UNSHIFTED + k;
+        let unshifted = 1 + NUMBER_UNSHIFTED - 8 + k;
+        folded[unshifted] = folded[unshifted] + folded[shifted];
+        folded[shifted] = Fr::zero();
+    }
+    let (actual, after) = measure(&env, || g1_msm(&env, &coms, &folded).unwrap());
+    assert_eq!(actual.to_array(), expected.to_array());
+    println!("Shplemini MSM (log n = {LOG_N}): {before} -> {after} CPU instructions");
+    assert!(after * 10 < before * 9, "expected at least a 10% saving");
+}
+
+#[test]
+fn verify_shplemini_budget() {
+    let env = env();
+    let entities = entity_points(&env);
+
+    // VK: header then 27 commitments
+    let mut vk_bytes = Vec::new();
+    for value in [1u64 << LOG_N, LOG_N as u64, 18, 1] {
+        vk_bytes.extend_from_slice(&value.to_be_bytes());
+    }
+    for p in &entities[..27] {
+        vk_bytes.extend_from_slice(&p.to_bytes());
+    }
+    let vk = load_vk_from_bytes(&Bytes::from_slice(&env, &vk_bytes)).expect("vk parses");
+
+    // Proof: witness commitments and the circuit's fold rounds on the curve
+    let zeros = Bytes::from_slice(&env, &vec![0u8; PROOF_BYTES]);
+    let mut proof = load_proof(&zeros).expect("proof parses");
+    let w = &entities[27..35];
+    (proof.w1, proof.w2, proof.w3, proof.w4) = (w[0], w[1], w[2], w[3]);
+    (proof.z_perm, proof.lookup_inverses) = (w[4], w[5]);
+    (proof.lookup_read_counts, proof.lookup_read_tags) = (w[6], w[7]);
+    for (i, comm) in proof.gemini_fold_comms.iter_mut().take(LOG_N - 1).enumerate() {
+        *comm = point(&env, 200 + i as u64);
+    }
+    proof.shplonk_q = point(&env, 300);
+    proof.kzg_quotient = point(&env, 301);
+    for (i, eval) in proof.sumcheck_evaluations.iter_mut().enumerate() {
+        *eval = Fr::from_u64(1000 + i as u64);
+    }
+    for (i, eval) in proof.gemini_a_evaluations.iter_mut().take(LOG_N).enumerate() {
+        *eval = Fr::from_u64(2000 + i as u64);
+    }
+
+    let mut sumcheck_u_challenges = [Fr::zero(); CONST_PROOF_SIZE_LOG_N];
+    for (i, u) in sumcheck_u_challenges.iter_mut().take(LOG_N).enumerate() {
+        *u = Fr::from_u64(2 + i as u64);
+    }
+    let transcript = Transcript {
+        rel_params: RelationParameters {
+            eta: Fr::zero(),
+            eta_two: Fr::zero(),
+            eta_three: Fr::zero(),
+            beta: Fr::zero(),
+            gamma: Fr::zero(),
+            public_inputs_delta: Fr::zero(),
+        },
+        alphas: [Fr::zero(); NUMBER_OF_ALPHAS],
+        gate_challenges: [Fr::zero(); CONST_PROOF_SIZE_LOG_N],
+        sumcheck_u_challenges,
+        rho: Fr::from_u64(3),
+        gemini_r: Fr::from_u64(5),
+        shplonk_nu: Fr::from_u64(7),
+        shplonk_z: Fr::from_u64(11),
+    };
+
+    // Arbitrary evaluations do not open the commitments
+    let (result, total) = measure(&env, || verify_shplemini(&env, &proof, &vk, &transcript));
+    assert_eq!(result, Err(VerifyError::PairingFailed));
+
+    // The same work with the per-term MSM over the unfolded layout
+    let g = to_affine(&env, &G1Point::generator());
+    let (_, pairing) = measure(&env, || pairing_check(&env, &g, &g));
+    let mut coms = vec![proof.shplonk_q];
+    coms.extend(&entities);
+    coms.extend(&proof.gemini_fold_comms[..LOG_N - 1]);
+    coms.extend([G1Point::generator(), proof.kzg_quotient]);
+    let scalars: Vec<Fr> = (0..coms.len() as u64).map(|k| Fr::from_u64(k + 2)).collect();
+    let (_, msm) = measure(&env, || naive_msm(&env, &coms, &scalars));
+
+    println!("verify_shplemini (log n = {LOG_N}): {} -> {total} CPU instructions", msm + pairing);
+    assert!(total < msm + pairing);
+}
@@ -1,6 +1,7 @@
 #!/usr/bin/env bash
 set -euo pipefail
 
+# Fixtures are written to tests/fixtures/<circuit>.
 NOIR_VERSION="1.0.0-beta.9"
 BB_VERSION="v0.87.0"
 
@@ -47,6 +48,7 @@ install_bb
 for dir in circuits/* ; do
   [ -d "$dir" ] || continue
   name=$(basename "$dir")
+  out="$PWD/tests/fixtures/$name"
   echo "► building $name"
   pushd "$dir" >/dev/null
 
@@ -64,5 +66,18 @@ for dir in circuits/* ; do
 
   bb write_solidity_verifier -s ultra_honk -k target/vk -o target/Verifier.sol
 
+  mkdir -p "$out"
+  cp target/proof target/vk target/public_inputs "$out/"
+
+  # ZK-mode proof (Libra masking) for simple_circuit
+  if [ "$name" = "simple_circuit" ]; then
+    mkdir -p target/zk "${out}_zk"
+    bb prove -b "$json" -w "$gz" -o target/zk --zk \
+      --scheme ultra_honk --oracle_hash keccak --output_format bytes_and_fields
+    bb write_vk -b "$json" -o target/zk \
+      --scheme ultra_honk --oracle_hash keccak --output_format bytes_and_fields
+    cp target/zk/proof target/zk/vk target/zk/public_inputs "${out}_zk/"
+  fi
+
   popd >/dev/null
 done
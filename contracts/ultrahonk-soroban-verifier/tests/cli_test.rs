@@ -0,0 +1,125 @@
+//! `ultrahonk_verify`: header and public input decoding, the failing stage,
+//! and the `--json` report.
+//!
+//! Run with `cargo test --features std --test cli_test`.
+
+#![cfg(feature = "std")]
+
+use soroban_sdk::{crypto::bn254::Fr as Bn254Fr, testutils::Ledger, BytesN, Env};
+use std::{fs, path::PathBuf, process::Command};
+use ultrahonk_soroban_verifier::{
+    ec::helpers::to_affine,
+    field::Fr,
+    format::POINT_FIELDS,
+    types::{G1Point, PAIRING_POINTS_SIZE},
+    PROOF_BYTES, ZK_PROOF_BYTES,
+};
+
+const LOG_N: u64 = 5;
+
+/// k·G, computed by the host.
+fn point(env: &Env, k: u64) -> G1Point {
+    let g = to_affine(env, &G1Point::generator());
+    let scalar = Bn254Fr::from_bytes(BytesN::from_array(env, &Fr::from_u64(k).to_bytes()));
+    G1Point::from_bytes(env.crypto().bn254().g1_mul(&g, &scalar).to_array())
+}
+
+/// bb v0.87.0 VK for a `LOG_N` circuit with two public inputs, commitments on
+/// the curve.
+fn vk_bytes() -> Vec<u8> {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    let mut out = Vec::new();
+    for value in [1u64 << LOG_N, LOG_N, PAIRING_POINTS_SIZE as u64 + 2, 1] {
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+    for k in 0..27 {
+        out.extend_from_slice(&point(&env, 100 + k).to_bytes());
+    }
+    out
+}
+
+/// Write the inputs to a scratch directory and run the tool on them.
+fn run(name: &str, proof: &[u8], vk: &[u8], public_inputs: &[u8], json: bool) -> (i32, String) {
+    let dir = std::env::temp_dir().join(format!("ultrahonk_verify_{name}"));
+    fs::create_dir_all(&dir).unwrap();
+    let paths: Vec<PathBuf> = ["proof", "vk", "public_inputs"]
+        .iter()
+        .map(|file| dir.join(file))
+        .collect();
+    for (path, bytes) in paths.iter().zip([proof, vk, public_inputs]) {
+        fs::write(path, bytes).unwrap();
+    }
+
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_ultrahonk_verify"));
+    cmd.args(&paths);
+    if json {
+        cmd.arg("--json");
+    }
+    let out = cmd.output().unwrap();
+    (out.status.code().unwrap(), String::from_utf8(out.stdout).unwrap())
+}
+
+fn public_inputs(values: &[u64]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|v| Fr::from_u64(*v).to_bytes())
+        .collect()
+}
+
+#[test]
+fn prints_header_inputs_and_failing_stage() {
+    let proof = vec![0u8; PROOF_BYTES];
+    let (code, out) = run("text", &proof, &vk_bytes(), &public_inputs(&[3, 4]), false);
+
+    assert_eq!(code, 1);
+    let lines: Vec<&str> = out.lines().collect();
+    assert_eq!(lines[0], "circuit size   32");
+    assert_eq!(lines[1], "log n          5");
+    assert_eq!(lines[2], "public inputs  18 (2 + 16 pairing point fields)");
+    assert_eq!(lines[3], format!("proof          {} bytes, plain", proof.len()));
+    assert!(lines[4].starts_with("input 0        0x00") && lines[4].ends_with("03 = 3"));
+    assert!(lines[5].ends_with("04 = 4"));
+    assert_eq!(lines[6], "failed at pairing: PairingFailed");
+}
+
+#[test]
+fn json_report() {
+    let vk = vk_bytes();
+
+    // First sumcheck univariate does not sum to zero
+    let mut proof = vec![0u8; PROOF_BYTES];
+    proof[(PAIRING_POINTS_SIZE + 8 * POINT_FIELDS) * 32 + 31] = 1;
+    let (code, out) = run("json", &proof, &vk, &public_inputs(&[3, 4]), true);
+    assert_eq!(code, 1);
+    assert!(out.starts_with(
+        "{\"vk\":{\"circuit_size\":32,\"log_circuit_size\":5,\"public_inputs_size\":18},\
+         \"proof\":{\"bytes\":14592,\"zk\":false},\"public_inputs\":[{\"hex\":\"0x00"
+    ));
+    assert!(out.contains("\"value\":\"3\",\"canonical\":true}"));
+    assert!(out.ends_with(
+        "\"verified\":false,\"stage\":\"sumcheck\",\"error\":\"SumcheckRound { round: 0 }\"}\n"
+    ));
+
+    // ZK proofs are told apart by length
+    let proof = vec![0u8; ZK_PROOF_BYTES];
+    let (_, out) = run("json_zk", &proof, &vk, &public_inputs(&[3]), true);
+    assert!(out.contains("\"zk\":true"));
+    assert!(out.contains("\"stage\":\"public inputs\""));
+
+    // Inputs are decoded mod r
+    let (_, out) = run("json_unreduced", &proof, &vk, &[0xff; 32], true);
+    assert!(out.contains("\"canonical\":false"));
+}
+
+#[test]
+fn reports_bad_vk() {
+    let (code, out) = run("bad_vk", &[0u8; 32], &[0u8; 64], &[], true);
+    assert_eq!(code, 1);
+    assert_eq!(
+        out,
+        "{\"vk\":null,\"proof\":{\"bytes\":32,\"zk\":false},\
+         \"public_inputs\":[],\"verified\":false,\"stage\":\"vk\",\
+         \"error\":\"VkLength { actual: 64 }\"}\n"
+    );
+}
@@ -0,0 +1,103 @@
+//! `vk_codegen` output: the checked-in module must match the generator, embed
+//! the VK `load_vk_from_bytes` would produce, and work as a contract.
+
+use soroban_sdk::{testutils::Ledger, Bytes, Env};
+use ultrahonk_soroban_verifier::{
+    codegen::generate_verifier_module, types::PAIRING_POINTS_SIZE, utils::load_vk_from_bytes,
+    VerifyError, PROOF_BYTES,
+};
+
+#[path = "generated/synthetic_verifier.rs"]
+mod generated;
+
+use generated::{Error, SyntheticVerifier, SyntheticVerifierClient};
+
+const LOG_N: usize = 3;
+const PUBLIC_INPUTS: usize = 2;
+
+/// v0.87 VK for a circuit of size 8 with two public inputs. The commitments
+/// are placeholders: nothing here reaches the pairing.
+fn synthetic_vk() -> Vec<u8> {
+    let mut out = Vec::new();
+    let header = [1u64 << LOG_N, LOG_N as u64, (PAIRING_POINTS_SIZE + PUBLIC_INPUTS) as u64, 1];
+    for value in header {
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+    for i in 0..27u8 {
+        let mut x = [0u8; 32];
+        let mut y = [0u8; 32];
+        (x[0], x[31]) = (0x11, i);
+        (y[0], y[31]) = (0x22, i);
+        out.extend_from_slice(&x);
+        out.extend_from_slice(&y);
+    }
+    out
+}
+
+fn env() -> Env {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    env
+}
+
+#[test]
+fn generated_module_is_up_to_date() {
+    let module =
+        generate_verifier_module(&synthetic_vk(), "SyntheticVerifier").expect("vk is valid");
+    assert_eq!(
+        module,
+        include_str!("generated/synthetic_verifier.rs"),
+        "regenerate with `cargo run --features std --bin vk_codegen -- <vk> \
+         --name SyntheticVerifier -o tests/generated/synthetic_verifier.rs`"
+    );
+}
+
+#[test]
+fn generated_vk_matches_parser() {
+    let env = env();
+    assert_eq!(generated::VK_BYTES.as_slice(), synthetic_vk().as_slice());
+    let parsed = load_vk_from_bytes(&Bytes::from_slice(&env, &generated::VK_BYTES));
+    assert_eq!(parsed, Ok(generated::VK));
+}
+
+#[test]
+fn generated_contract_reports_failing_stage() {
+    let env = env();
+    let client = SyntheticVerifierClient::new(&env, &env.register(SyntheticVerifier, ()));
+    let inputs = Bytes::from_slice(&env, &[0u8; 32 * PUBLIC_INPUTS]);
+
+    let short = Bytes::from_slice(&env, &[0u8; 32]);
+    assert_eq!(client.try_verify(&short, &inputs), Err(Ok(Error::InvalidProof)));
+
+    let mut proof = vec![0u8; PROOF_BYTES];
+    let one_input = Bytes::from_slice(&env, &[0u8; 32]);
+    assert_eq!(
+        client.try_verify(&Bytes::from_slice(&env, &proof), &one_input),
+        Err(Ok(Error::PublicInputsMismatch))
+    );
+
+    // First coefficient of the first sumcheck univariate, after the pairing
+    // point limbs and the eight witness commitments (four limbs each)
+    proof[(PAIRING_POINTS_SIZE + 8 * 4) * 32 + 31] = 1;
+    assert_eq!(
+        client.try_verify(&Bytes::from_slice(&env, &proof), &inputs),
+        Err(Ok(Error::SumcheckFailed))
+    );
+}
+
+#[test]
+fn generator_rejects_invalid_vk() {
+    let vk = synthetic_vk();
+    assert_eq!(
+        generate_verifier_module(&vk[..100], "V").err(),
+        Some(VerifyError::VkLength { actual: 100 })
+    );
+
+    // Fewer public inputs than the pairing point limbs
+    let mut few_inputs = vk.clone();
+    few_inputs[23] = 2;
+    assert_eq!(
+        generate_verifier_module(&few_inputs, "V").err(),
+        Some(VerifyError::InvalidVkHeader)
+    );
+}
@@ -0,0 +1,287 @@
+//! Differential tests: the `std` native backend must agree with the Soroban
+//! host functions used on chain, primitive by primitive and end to end.
+//!
+//! Run with `cargo test --features std --test native_test`.
+
+#![cfg(feature = "std")]
+
+use ark_ec::AffineRepr;
+use ark_ff::{BigInteger, PrimeField};
+use soroban_sdk::{
+    crypto::bn254::{Bn254G1Affine, Fr as Bn254Fr},
+    testutils::Ledger,
+    Bytes, BytesN, Env,
+};
+use std::{fs, path::Path};
+use ultrahonk_soroban_verifier::{
+    backend::Backend,
+    ec::helpers::to_affine,
+    field::Fr,
+    format::POINT_FIELDS,
+    native::NativeBackend,
+    transcript::generate_transcript,
+    types::{G1Point, BATCHED_RELATION_PARTIAL_LENGTH, PAIRING_POINTS_SIZE},
+    utils::{parse_proof, parse_vk},
+    NativeVerifier, UltraHonkVerifier, VerifyError, PROOF_BYTES, ZK_PROOF_BYTES,
+};
+
+const LOG_N: u64 = 5;
+
+fn env() -> Env {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    env
+}
+
+/// k·G, computed by the host.
+fn point(env: &Env, k: u64) -> G1Point {
+    let g = to_affine(env, &G1Point::generator());
+    let scalar = Bn254Fr::from_bytes(BytesN::from_array(env, &Fr::from_u64(k).to_bytes()));
+    G1Point::from_bytes(env.crypto().bn254().g1_mul(&g, &scalar).to_array())
+}
+
+/// Host encoding of a native point: big-endian x || y, identity as zeros.
+fn native_bytes(p: &ark_bn254::G1Affine) -> [u8; 64] {
+    let mut out = [0u8; 64];
+    if let Some((x, y)) = p.xy() {
+        out[..32].copy_from_slice(&x.into_bigint().to_bytes_be());
+        out[32..].copy_from_slice(&y.into_bigint().to_bytes_be());
+    }
+    out
+}
+
+fn host_bytes(p: &Bn254G1Affine) -> [u8; 64] {
+    p.to_array()
+}
+
+/// bb v0.87.0 VK for a `LOG_N` circuit with two public inputs, commitments on
+/// the curve.
+fn vk_bytes(env: &Env) -> Vec<u8> {
+    let mut out = Vec::new();
+    for value in [1u64 << LOG_N, LOG_N, PAIRING_POINTS_SIZE as u64 + 2, 1] {
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+    for k in 0..27 {
+        out.extend_from_slice(&point(env, 100 + k).to_bytes());
+    }
+    out
+}
+
+fn public_inputs(values: &[u64]) -> Vec<u8> {
+    values
+        .iter()
+        .flat_map(|v| Fr::from_u64(*v).to_bytes())
+        .collect()
+}
+
+/// Run a proof through both verifiers, directly and through `verify_auto`,
+/// and require the same outcome.
+fn assert_agree(
+    env: &Env,
+    vk: &[u8],
+    proof: &[u8],
+    inputs: &[u8],
+    zk: bool,
+) -> Result<(), VerifyError> {
+    let onchain = UltraHonkVerifier::new(env, &Bytes::from_slice(env, vk)).expect("vk parses");
+    let native = NativeVerifier::new(vk).expect("vk parses");
+    let (proof_b, inputs_b) = (Bytes::from_slice(env, proof), Bytes::from_slice(env, inputs));
+    // Only agreement matters here; budget_test covers the cost
+    env.cost_estimate().budget().reset_unlimited();
+    let (expected, actual) = if zk {
+        (onchain.verify_zk(&proof_b, &inputs_b), native.verify_zk(proof, inputs))
+    } else {
+        (onchain.verify(&proof_b, &inputs_b), native.verify(proof, inputs))
+    };
+    assert_eq!(actual, expected);
+
+    // Length-based dispatch picks the same path on both
+    assert_eq!(native.is_zk_proof(proof.len()), zk);
+    assert_eq!(onchain.is_zk_proof(proof.len()), zk);
+    assert_eq!(native.verify_auto(proof, inputs), actual);
+    assert_eq!(onchain.verify_auto(&proof_b, &inputs_b), actual);
+    actual
+}
+
+// ---------------------------------------------------------------------------
+// Primitives
+// ---------------------------------------------------------------------------
+
+#[test]
+fn keccak_matches_host() {
+    let env = env();
+    for len in [0usize, 1, 31, 32, 33, 136, 1000] {
+        let data: Vec<u8> = (0..len).map(|i| i as u8).collect();
+        assert_eq!(NativeBackend.keccak256(&data), env.keccak256(&data), "len {len}");
+    }
+}
+
+#[test]
+fn msm_matches_host() {
+    let env = env();
+    let mut coms: Vec<G1Point> = (1..=12).map(|k| point(&env, k)).collect();
+    coms[3] = G1Point::infinity();
+    let mut scalars: Vec<Fr> = (1..=12).map(|k| Fr::from_u64(k * 7919)).collect();
+    scalars[5] = Fr::zero();
+    scalars[7] = -Fr::one();
+
+    let expected = env.g1_msm(&coms, &scalars).unwrap();
+    let actual = NativeBackend.g1_msm(&coms, &scalars).unwrap();
+    assert_eq!(native_bytes(&actual), host_bytes(&expected));
+
+    // Cancelling terms sum to the identity
+    let cancel = [Fr::one(), -Fr::one()];
+    let pair = [coms[0], coms[0]];
+    let expected = env.g1_msm(&pair, &cancel).unwrap();
+    let actual = NativeBackend.g1_msm(&pair, &cancel).unwrap();
+    assert_eq!(native_bytes(&actual), [0u8; 64]);
+    assert_eq!(host_bytes(&expected), [0u8; 64]);
+
+    assert_eq!(
+        NativeBackend.g1_msm(&coms, &scalars[1..]).err(),
+        Some(VerifyError::MsmLength)
+    );
+}
+
+#[test]
+fn negate_and_pairing_match_host() {
+    let env = env();
+    let p = point(&env, 42);
+    assert_eq!(
+        native_bytes(&NativeBackend.g1_negate(&p).unwrap()),
+        host_bytes(&env.g1_negate(&p).unwrap())
+    );
+
+    let g = G1Point::generator();
+    let cases = [
+        (G1Point::infinity(), G1Point::infinity()),
+        (g, G1Point::infinity()),
+        (G1Point::infinity(), g),
+        (g, point(&env, 7)),
+    ];
+    for (p0, p1) in cases {
+        let one = [Fr::one()];
+        let (h0, h1) = (env.g1_msm(&[p0], &one).unwrap(), env.g1_msm(&[p1], &one).unwrap());
+        let (n0, n1) = (
+            NativeBackend.g1_msm(&[p0], &one).unwrap(),
+            NativeBackend.g1_msm(&[p1], &one).unwrap(),
+        );
+        assert_eq!(NativeBackend.pairing_check(&n0, &n1), env.pairing_check(&h0, &h1));
+    }
+    let inf = G1Point::infinity();
+    let id = NativeBackend.g1_msm(&[inf], &[Fr::one()]).unwrap();
+    assert!(NativeBackend.pairing_check(&id, &id));
+}
+
+#[test]
+fn invalid_points_are_reported() {
+    // On chain the host traps on these; natively they surface as errors
+    let off_curve = G1Point::from_xy(Fr::from_u64(1).to_bytes(), Fr::from_u64(1).to_bytes());
+    assert_eq!(NativeBackend.g1_negate(&off_curve).err(), Some(VerifyError::InvalidPoint));
+    assert_eq!(
+        NativeBackend.g1_msm(&[off_curve], &[Fr::one()]).err(),
+        Some(VerifyError::InvalidPoint)
+    );
+
+    // Coordinates must be reduced
+    let mut unreduced = G1Point::generator();
+    unreduced.y = [0xff; 32];
+    assert_eq!(NativeBackend.g1_negate(&unreduced).err(), Some(VerifyError::InvalidPoint));
+
+    // Terms the MSM skips are not decoded
+    assert!(NativeBackend.g1_msm(&[off_curve], &[Fr::zero()]).is_ok());
+}
+
+#[test]
+fn transcript_matches_host() {
+    let env = env();
+    let vk = parse_vk(&vk_bytes(&env)).expect("vk parses");
+    let mut bytes = vec![0u8; PROOF_BYTES];
+    for (i, b) in bytes.iter_mut().enumerate() {
+        *b = (i % 251) as u8;
+    }
+    let proof = parse_proof(&bytes).expect("proof parses");
+    let inputs = public_inputs(&[3, 4]);
+
+    let (n, pis) = (vk.circuit_size, vk.public_inputs_size);
+    let host = generate_transcript(&env, &proof, &inputs, n, pis, 1);
+    let native = generate_transcript(&NativeBackend, &proof, &inputs, n, pis, 1);
+    assert_eq!(format!("{native:?}"), format!("{host:?}"));
+}
+
+// ---------------------------------------------------------------------------
+// End to end
+// ---------------------------------------------------------------------------
+
+#[test]
+fn verify_matches_onchain_verifier() {
+    let env = env();
+    let vk = vk_bytes(&env);
+    let inputs = public_inputs(&[3, 4]);
+
+    let zeros = vec![0u8; PROOF_BYTES];
+
+    // Public input count and alignment
+    let result = assert_agree(&env, &vk, &zeros, &public_inputs(&[3]), false);
+    assert_eq!(result, Err(VerifyError::PublicInputsCount { expected: 2, actual: 1 }));
+    let result = assert_agree(&env, &vk, &zeros, &inputs[..33], false);
+    assert_eq!(result, Err(VerifyError::PublicInputsNotAligned { len: 33 }));
+
+    // First univariate does not sum to zero
+    let mut bad = zeros.clone();
+    let first_univariate = (PAIRING_POINTS_SIZE + 8 * POINT_FIELDS) * 32;
+    bad[first_univariate + 31] = 1;
+    let result = assert_agree(&env, &vk, &bad, &inputs, false);
+    assert_eq!(result, Err(VerifyError::SumcheckRound { round: 0 }));
+
+    // A later univariate, reached only with the same challenges
+    let round = first_univariate + 2 * BATCHED_RELATION_PARTIAL_LENGTH * 32;
+    let mut bad = zeros.clone();
+    bad[round + 31] = 1;
+    bad[round + 32 + 31] = 2;
+    assert!(matches!(
+        assert_agree(&env, &vk, &bad, &inputs, false),
+        Err(VerifyError::SumcheckRound { .. })
+    ));
+
+    // Zero claims pass sumcheck and reach the pairing with the VK on the curve
+    let result = assert_agree(&env, &vk, &zeros, &inputs, false);
+    assert_eq!(result, Err(VerifyError::PairingFailed));
+
+    let result = assert_agree(&env, &vk, &[0u8; 32], &inputs, false);
+    assert!(matches!(result, Err(VerifyError::ProofLength { actual: 32, .. })));
+
+    let zeros = vec![0u8; ZK_PROOF_BYTES];
+    let result = assert_agree(&env, &vk, &zeros, &public_inputs(&[3]), true);
+    assert_eq!(result, Err(VerifyError::PublicInputsCount { expected: 2, actual: 1 }));
+    let result = assert_agree(&env, &vk, &zeros, &inputs, true);
+    assert_eq!(result, Err(VerifyError::PairingFailed));
+}
+
+/// bb proofs from `tests/build_circuits.sh` must verify with both backends.
+#[test]
+fn fixtures_verify_natively() {
+    let env = env();
+    let circuits = [
+        ("simple_circuit", false),
+        ("fib_chain", false),
+        ("simple_circuit_zk", true),
+    ];
+    for (circuit, zk) in circuits {
+        let dir = format!("tests/fixtures/{circuit}");
+        let path = Path::new(&dir);
+        let [proof, vk, inputs] = ["proof", "vk", "public_inputs"].map(|file| {
+            fs::read(path.join(file)).unwrap_or_else(|e| {
+                panic!("{dir}/{file}: {e}; run tests/build_circuits.sh first")
+            })
+        });
+        assert_eq!(assert_agree(&env, &vk, &proof, &inputs, zk), Ok(()), "{dir}");
+
+        // Any change to the public inputs breaks the proof on both
+        let mut tampered = inputs.clone();
+        if let Some(last) = tampered.last_mut() {
+            *last ^= 1;
+            assert!(assert_agree(&env, &vk, &proof, &tampered, zk).is_err(), "{dir}");
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use soroban_sdk::{BytesN, Env};
+use ultrahonk_soroban_verifier::{
+    field::Fr,
+    public_inputs::PublicInputError,
+    utils::bytes_to_vec,
+    PublicInputsBuilder, PublicInputsReader,
+};
+
+/// BN254 scalar field modulus r, big-endian.
+const R: [u8; 32] = [
+    0x30, 0x64, 0x4e, 0x72, 0xe1, 0x31, 0xa0, 0x29, 0xb8, 0x50, 0x45, 0xb6, 0x81, 0x81, 0x58, 0x5d,
+    0x28, 0x33, 0xe8, 0x48, 0x79, 0xb9, 0x70, 0x91, 0x43, 0xe1, 0xf5, 0x93, 0xf0, 0x00, 0x00, 0x01,
+];
+
+fn word(value: u64) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out[24..].copy_from_slice(&value.to_be_bytes());
+    out
+}
+
+#[test]
+fn builder_encodes_one_field_per_value() {
+    let env = Env::default();
+    let commitment = BytesN::from_array(&env, &[0x0a; 32]);
+    let builder = PublicInputsBuilder::new(&env);
+    assert!(builder.is_empty());
+
+    let builder = builder
+        .push_u32(42)
+        .push_u64(u64::MAX)
+        .push_bool(true)
+        .push_bool(false)
+        .push_fr(-Fr::one())
+        .push_bytes32(&commitment);
+    assert_eq!(builder.len(), 6);
+
+    let mut r_minus_one = R;
+    r_minus_one[31] = 0;
+    let expected = [
+        word(42),
+        word(u64::MAX),
+        word(1),
+        word(0),
+        r_minus_one,
+        [0x0a; 32],
+    ]
+    .concat();
+    assert_eq!(bytes_to_vec(&builder.build()), expected);
+}
+
+#[test]
+fn builder_reduces_bytes_mod_r() {
+    let env = Env::default();
+    let mut r_plus_five = R;
+    r_plus_five[31] += 5;
+    let mut long = [0u8; 40];
+    long[8..].copy_from_slice(&R);
+
+    let inputs = PublicInputsBuilder::new(&env)
+        .push_reduced(&R)
+        .push_reduced(&r_plus_five)
+        .push_reduced(&long)
+        .push_reduced(&[1, 0])
+        .build();
+    let expected = [word(0), word(5), word(0), word(256)].concat();
+    assert_eq!(bytes_to_vec(&inputs), expected);
+}
+
+#[test]
+fn reader_round_trips_builder() {
+    let env = Env::default();
+    let commitment = BytesN::from_array(&env, &[0x0a; 32]);
+    let inputs = PublicInputsBuilder::new(&env)
+        .push_u32(7)
+        .push_u64(1 << 40)
+        .push_bool(true)
+        .push_fr(Fr::from_u64(99))
+        .push_bytes32(&commitment)
+        .build();
+    let bytes = bytes_to_vec(&inputs);
+
+    let mut reader = PublicInputsReader::new(&bytes).unwrap();
+    assert_eq!(reader.len(), 5);
+    assert_eq!(reader.next_u32(), Ok(7));
+    assert_eq!(reader.next_u64(), Ok(1 << 40));
+    assert_eq!(reader.next_bool(), Ok(true));
+    assert_eq!(reader.next_fr(), Ok(Fr::from_u64(99)));
+    assert_eq!(reader.next_bytes32(&env), Ok(commitment));
+    assert!(reader.is_empty());
+    assert_eq!(reader.finish(), Ok(()));
+}
+
+#[test]
+fn reader_splits_return_values() {
+    // fn main(a: pub u32, b: pub bool) -> pub (Field, u64)
+    let bytes = [word(3), word(1), word(1000), word(5)].concat();
+    let (mut params, mut returns) = PublicInputsReader::split_return_values(&bytes, 2).unwrap();
+    assert_eq!((params.len(), returns.len()), (2, 2));
+    assert_eq!(params.next_u32(), Ok(3));
+    assert_eq!(params.next_bool(), Ok(true));
+    assert_eq!(params.next_u32(), Err(PublicInputError::Missing { index: 2 }));
+    assert_eq!(returns.next_fr(), Ok(Fr::from_u64(1000)));
+    assert_eq!(returns.next_u64(), Ok(5));
+    assert_eq!(returns.finish(), Ok(()));
+
+    // Errors index into the whole file
+    let (_, mut returns) = PublicInputsReader::split_return_values(&bytes, 1).unwrap();
+    assert_eq!(returns.next_bool(), Err(PublicInputError::OutOfRange { index: 3 }));
+
+    assert_eq!(
+        PublicInputsReader::split_return_values(&bytes, 5).err(),
+        Some(PublicInputError::Missing { index: 4 })
+    );
+}
+
+#[test]
+fn reader_rejects_malformed_fields() {
+    assert_eq!(
+        PublicInputsReader::new(&[0u8; 33]).err(),
+        Some(PublicInputError::NotAligned { len: 33 })
+    );
+
+    let mut too_big = word(0);
+    too_big[27] = 1;
+    let bytes = [too_big, too_big, word(2), R, word(0), word(0)].concat();
+    let mut reader = PublicInputsReader::new(&bytes).unwrap();
+    assert_eq!(reader.next_u32(), Err(PublicInputError::OutOfRange { index: 0 }));
+    assert_eq!(reader.next_u64(), Ok(1 << 32));
+    assert_eq!(reader.next_bool(), Err(PublicInputError::OutOfRange { index: 2 }));
+    assert_eq!(reader.next_fr(), Err(PublicInputError::NotCanonical { index: 3 }));
+    assert_eq!(reader.clone().finish(), Err(PublicInputError::Trailing { count: 2 }));
+    assert_eq!(reader.next_field_bytes(), Ok(word(0)));
+}
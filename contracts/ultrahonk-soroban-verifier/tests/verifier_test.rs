@@ -1,9 +1,28 @@
 use soroban_sdk::{testutils::Ledger, Bytes, Env};
 use std::{fs, path::Path};
-use ultrahonk_soroban_verifier::UltraHonkVerifier;
+use ultrahonk_soroban_verifier::{
+    format::{is_proof_len, POINT_FIELDS},
+    types::{
+        BATCHED_RELATION_PARTIAL_LENGTH, CONST_PROOF_SIZE_LOG_N, NUMBER_OF_ENTITIES,
+        NUM_LIBRA_EVALUATIONS, PAIRING_POINTS_SIZE, ZK_BATCHED_RELATION_PARTIAL_LENGTH,
+    },
+    utils::{coord_to_halves_be, load_proof, load_vk_from_bytes, load_zk_proof},
+    UltraHonkVerifier, VerifyError, PROOF_BYTES, PROOF_FIELDS, VK_BYTES, ZK_PROOF_BYTES,
+};
 
-fn run(dir: &str) -> Result<(), String> {
-    let path = Path::new(dir);
+/// Fixtures are written by `tests/build_circuits.sh` to `tests/fixtures/<circuit>`.
+fn run(circuit: &str) -> Result<(), String> {
+    run_with(circuit, false)
+}
+
+/// ZK proofs (`bb prove --zk`) live next to the plain ones as `<circuit>_zk`.
+fn run_zk(circuit: &str) -> Result<(), String> {
+    run_with(&format!("{circuit}_zk"), true)
+}
+
+fn run_with(circuit: &str, zk: bool) -> Result<(), String> {
+    let dir = format!("tests/fixtures/{circuit}");
+    let path = Path::new(&dir);
     let env = Env::default();
     env.ledger().set_protocol_version(25);
 
@@ -19,18 +38,270 @@ fn run(dir: &str) -> Result<(), String> {
     // Public inputs bytes
     let public_inputs = fs::read(path.join("public_inputs")).map_err(|e| e.to_string())?;
     let public_inputs = Bytes::from_slice(&env, &public_inputs);
-    verifier
-        .verify(&proof, &public_inputs)
-        .map_err(|e| format!("{e:?}"))?;
+    if zk {
+        verifier.verify_zk(&proof, &public_inputs)
+    } else {
+        verifier.verify(&proof, &public_inputs)
+    }
+    .map_err(|e| format!("{e:?}"))?;
     Ok(())
 }
 
 #[test]
 fn simple_circuit_proof_verifies() -> Result<(), String> {
-    run("circuits/simple_circuit/target")
+    run("simple_circuit")
 }
 
 #[test]
 fn fib_chain_proof_verifies() -> Result<(), String> {
-    run("circuits/fib_chain/target")
+    run("fib_chain")
+}
+
+#[test]
+fn simple_circuit_zk_proof_verifies() -> Result<(), String> {
+    run_zk("simple_circuit")
+}
+
+// ---------------------------------------------------------------------------
+// Proof and VK layouts (synthetic data, no fixtures needed)
+// ---------------------------------------------------------------------------
+
+/// Deterministic 32-byte word; `tag` fills the upper bytes so point
+/// coordinates exercise both (lo136, hi) limbs.
+fn word(tag: u8, n: usize) -> [u8; 32] {
+    let mut out = [0u8; 32];
+    out[1] = tag;
+    out[24..].copy_from_slice(&(n as u64).to_be_bytes());
+    out
+}
+
+fn push_point(out: &mut Vec<u8>, n: usize) {
+    for coord in [word(0x11, n), word(0x22, n)] {
+        let (lo, hi) = coord_to_halves_be(&coord);
+        out.extend_from_slice(&lo);
+        out.extend_from_slice(&hi);
+    }
+}
+
+fn push_fr(out: &mut Vec<u8>, count: usize, n: &mut usize) {
+    for _ in 0..count {
+        out.extend_from_slice(&word(0, *n));
+        *n += 1;
+    }
+}
+
+/// Encode a synthetic padded proof.
+fn synthetic_proof() -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut n = 0;
+    push_fr(&mut out, PAIRING_POINTS_SIZE, &mut n);
+    for _ in 0..8 {
+        push_point(&mut out, n);
+        n += 1;
+    }
+    push_fr(
+        &mut out,
+        CONST_PROOF_SIZE_LOG_N * BATCHED_RELATION_PARTIAL_LENGTH + NUMBER_OF_ENTITIES,
+        &mut n,
+    );
+    for _ in 0..CONST_PROOF_SIZE_LOG_N - 1 {
+        push_point(&mut out, n);
+        n += 1;
+    }
+    push_fr(&mut out, CONST_PROOF_SIZE_LOG_N, &mut n);
+    for _ in 0..2 {
+        push_point(&mut out, n);
+        n += 1;
+    }
+    out
+}
+
+/// Encode a synthetic padded ZK proof, in transcript order.
+fn synthetic_zk_proof() -> Vec<u8> {
+    let mut out = Vec::new();
+    let mut n = 0;
+    push_fr(&mut out, PAIRING_POINTS_SIZE, &mut n);
+    // w1..w4, lookups, z_perm, then the Libra concatenation commitment
+    for _ in 0..9 {
+        push_point(&mut out, n);
+        n += 1;
+    }
+    // Libra sum, univariates, evaluations, Libra evaluation
+    push_fr(
+        &mut out,
+        1 + CONST_PROOF_SIZE_LOG_N * ZK_BATCHED_RELATION_PARTIAL_LENGTH + NUMBER_OF_ENTITIES + 1,
+        &mut n,
+    );
+    // Libra grand sum and quotient, Gemini masking polynomial
+    for _ in 0..3 {
+        push_point(&mut out, n);
+        n += 1;
+    }
+    push_fr(&mut out, 1, &mut n);
+    for _ in 0..CONST_PROOF_SIZE_LOG_N - 1 {
+        push_point(&mut out, n);
+        n += 1;
+    }
+    // Gemini evaluations, Libra polynomial evaluations
+    push_fr(&mut out, CONST_PROOF_SIZE_LOG_N + NUM_LIBRA_EVALUATIONS, &mut n);
+    for _ in 0..2 {
+        push_point(&mut out, n);
+        n += 1;
+    }
+    out
+}
+
+/// VK header followed by 27 synthetic commitments.
+fn synthetic_vk(log_n: u64, public_inputs_size: u64) -> Vec<u8> {
+    let mut out = Vec::new();
+    for value in [1u64 << log_n, log_n, public_inputs_size, 1] {
+        out.extend_from_slice(&value.to_be_bytes());
+    }
+    for i in 0..27 {
+        out.extend_from_slice(&word(0x11, i));
+        out.extend_from_slice(&word(0x22, i));
+    }
+    out
+}
+
+#[test]
+fn layout_sizes() {
+    assert_eq!(PROOF_FIELDS, 456);
+    assert_eq!(PROOF_BYTES, 14_592);
+    assert_eq!(ZK_PROOF_BYTES, 507 * 32);
+    assert_eq!(VK_BYTES, 1760);
+    assert_eq!(synthetic_proof().len(), PROOF_BYTES);
+    assert_eq!(synthetic_zk_proof().len(), ZK_PROOF_BYTES);
+    assert_eq!(synthetic_vk(5, 18).len(), VK_BYTES);
+
+    assert!(is_proof_len(PROOF_BYTES));
+    assert!(is_proof_len(ZK_PROOF_BYTES));
+    assert!(!is_proof_len(32));
+}
+
+#[test]
+fn proof_layout_recombines_limbs() {
+    let env = Env::default();
+    let proof =
+        load_proof(&Bytes::from_slice(&env, &synthetic_proof())).expect("proof parses");
+
+    // Coordinates survive the limb split intact
+    let n = PAIRING_POINTS_SIZE;
+    assert_eq!(proof.w1.x, word(0x11, n));
+    assert_eq!(proof.w1.y, word(0x22, n));
+    assert_eq!(proof.z_perm.y, word(0x22, n + 7));
+    assert_eq!(proof.sumcheck_univariates[0][0].to_bytes(), word(0, n + 8));
+}
+
+#[test]
+fn zk_proof_layout() {
+    let env = Env::default();
+    let bytes = Bytes::from_slice(&env, &synthetic_zk_proof());
+    let proof = load_zk_proof(&bytes).expect("proof parses");
+
+    // Elements are numbered in transcript order
+    let mut n = PAIRING_POINTS_SIZE;
+    assert_eq!(proof.w1.x, word(0x11, n));
+    assert_eq!(proof.z_perm.x, word(0x11, n + 7));
+    assert_eq!(proof.libra_commitments[0].y, word(0x22, n + 8));
+    n += 9;
+    assert_eq!(proof.libra_sum.to_bytes(), word(0, n));
+    assert_eq!(proof.sumcheck_univariates[0][0].to_bytes(), word(0, n + 1));
+    assert_eq!(
+        proof.sumcheck_univariates[CONST_PROOF_SIZE_LOG_N - 1]
+            [ZK_BATCHED_RELATION_PARTIAL_LENGTH - 1]
+            .to_bytes(),
+        word(0, n + CONST_PROOF_SIZE_LOG_N * ZK_BATCHED_RELATION_PARTIAL_LENGTH)
+    );
+    n += 1 + CONST_PROOF_SIZE_LOG_N * ZK_BATCHED_RELATION_PARTIAL_LENGTH;
+    assert_eq!(proof.sumcheck_evaluations[0].to_bytes(), word(0, n));
+    n += NUMBER_OF_ENTITIES;
+    assert_eq!(proof.libra_evaluation.to_bytes(), word(0, n));
+    assert_eq!(proof.libra_commitments[1].x, word(0x11, n + 1));
+    assert_eq!(proof.libra_commitments[2].x, word(0x11, n + 2));
+    assert_eq!(proof.gemini_masking_poly.x, word(0x11, n + 3));
+    assert_eq!(proof.gemini_masking_eval.to_bytes(), word(0, n + 4));
+    n += 5;
+    assert_eq!(proof.gemini_fold_comms[0].x, word(0x11, n));
+    n += CONST_PROOF_SIZE_LOG_N - 1;
+    assert_eq!(proof.gemini_a_evaluations[0].to_bytes(), word(0, n));
+    n += CONST_PROOF_SIZE_LOG_N;
+    assert_eq!(proof.libra_poly_evals[0].to_bytes(), word(0, n));
+    assert_eq!(proof.libra_poly_evals[3].to_bytes(), word(0, n + 3));
+    n += NUM_LIBRA_EVALUATIONS;
+    assert_eq!(proof.shplonk_q.x, word(0x11, n));
+    assert_eq!(proof.kzg_quotient.y, word(0x22, n + 1));
+}
+
+#[test]
+fn vk_header_layout() {
+    let env = Env::default();
+    let bytes = Bytes::from_slice(&env, &synthetic_vk(5, 18));
+    let vk = load_vk_from_bytes(&bytes).expect("vk parses");
+    assert_eq!(vk.circuit_size, 32);
+    assert_eq!(vk.log_circuit_size, 5);
+    assert_eq!(vk.public_inputs_size, 18);
+    assert_eq!(vk.qm.x, word(0x11, 0));
+    assert_eq!(vk.lagrange_last.y, word(0x22, 26));
+    let verifier = UltraHonkVerifier::new(&env, &bytes).expect("vk parses");
+    assert_eq!(verifier.get_vk(), &vk);
+
+    let truncated = Bytes::from_slice(&env, &synthetic_vk(5, 18)[..1728]);
+    assert_eq!(
+        load_vk_from_bytes(&truncated).err(),
+        Some(VerifyError::VkLength { actual: 1728 })
+    );
+    assert_eq!(
+        load_vk_from_bytes(&Bytes::from_slice(&env, &[0u8; 64])).err(),
+        Some(VerifyError::VkLength { actual: 64 })
+    );
+
+    // Header words must describe a circuit the padded proof can hold
+    let too_big = Bytes::from_slice(&env, &synthetic_vk(29, 18));
+    assert_eq!(load_vk_from_bytes(&too_big).err(), Some(VerifyError::InvalidVkHeader));
+    let empty = Bytes::from_slice(&env, &synthetic_vk(0, 18));
+    assert_eq!(load_vk_from_bytes(&empty).err(), Some(VerifyError::InvalidVkHeader));
+}
+
+#[test]
+fn verify_reports_failing_stage() {
+    let env = Env::default();
+    env.ledger().set_protocol_version(25);
+    let vk = Bytes::from_slice(&env, &synthetic_vk(5, 18));
+    let verifier = UltraHonkVerifier::new(&env, &vk).expect("vk parses");
+    let proof = Bytes::from_slice(&env, &synthetic_proof());
+    let public_inputs = Bytes::from_slice(&env, &[&word(0, 1)[..], &word(0, 2)[..]].concat());
+
+    assert_eq!(
+        verifier.verify(&Bytes::from_slice(&env, &[0u8; 32]), &public_inputs),
+        Err(VerifyError::ProofLength { expected: PROOF_BYTES, actual: 32 })
+    );
+    assert_eq!(
+        verifier.verify_zk(&proof, &public_inputs),
+        Err(VerifyError::ProofLength { expected: ZK_PROOF_BYTES, actual: PROOF_BYTES })
+    );
+    assert_eq!(
+        verifier.verify(&proof, &Bytes::from_slice(&env, &[0u8; 33])),
+        Err(VerifyError::PublicInputsNotAligned { len: 33 })
+    );
+    assert_eq!(
+        verifier.verify(&proof, &Bytes::from_slice(&env, &word(0, 1))),
+        Err(VerifyError::PublicInputsCount { expected: 2, actual: 1 })
+    );
+
+    // Synthetic univariates do not sum to the initial target of zero
+    let mut bytes = synthetic_proof();
+    assert_eq!(
+        verifier.verify(&Bytes::from_slice(&env, &bytes), &public_inputs),
+        Err(VerifyError::SumcheckRound { round: 0 })
+    );
+
+    // Zero univariates pass every round but not the relation check
+    let start = (PAIRING_POINTS_SIZE + 8 * POINT_FIELDS) * 32;
+    let len = CONST_PROOF_SIZE_LOG_N * BATCHED_RELATION_PARTIAL_LENGTH * 32;
+    bytes[start..start + len].fill(0);
+    assert_eq!(
+        verifier.verify(&Bytes::from_slice(&env, &bytes), &public_inputs),
+        Err(VerifyError::SumcheckFinalMismatch)
+    );
 }
@@ -0,0 +1,162 @@
+#![no_std]
+
+//! # ZK Verifier
+//!
+//! A deployable UltraHonk verifier shared by every game, so games check
+//! proofs with a cross-contract call instead of linking the verifier into
+//! their own WASM.
+//!
+//! **Registry:**
+//! VKs are stored under the keccak256 hash of their bytes. Registration is
+//! open to anyone: a hash can only ever name one key, so a game that pins a
+//! `vk_hash` cannot have its key swapped. Each key is validated when it is
+//! registered, and registering it again or verifying a proof against it
+//! keeps it alive.
+//!
+//! **Interface:**
+//! `verify(vk_hash, proof, public_inputs)` accepts plain and ZK proofs and
+//! reports the failing stage as a `game_common::VerifierError`. `verify_zk`
+//! takes the same arguments but only accepts ZK proofs, for games whose
+//! private inputs must stay hidden. Games call both through
+//! `game_common::VerifierClient`.
+
+use game_common::VerifierError;
+use soroban_sdk::{contract, contractevent, contractimpl, contracttype, Bytes, BytesN, Env};
+use ultrahonk_soroban_verifier::UltraHonkVerifier;
+
+// ============================================================================
+// Constants
+// ============================================================================
+
+/// Ledgers a registered VK is kept for after it was last registered or
+/// verified against (about 30 days at 5 seconds per ledger)
+pub const VK_TTL_LEDGERS: u32 = 518_400;
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// VK bytes, keyed by their keccak256 hash
+    Vk(BytesN<32>),
+}
+
+/// A VK was registered (keccak256 of its bytes)
+#[contractevent]
+pub struct VkRegistered {
+    pub vk_hash: BytesN<32>,
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn extend_vk_ttl(env: &Env, key: &DataKey) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, VK_TTL_LEDGERS, VK_TTL_LEDGERS);
+}
+
+/// Load a registered VK and build a verifier for it, keeping the VK alive
+fn load_verifier(env: &Env, vk_hash: BytesN<32>) -> Result<UltraHonkVerifier, VerifierError> {
+    let key = DataKey::Vk(vk_hash);
+    let vk_bytes: Bytes = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(VerifierError::VkNotFound)?;
+    extend_vk_ttl(env, &key);
+
+    Ok(UltraHonkVerifier::new(env, &vk_bytes)?)
+}
+
+// ============================================================================
+// Contract Definition
+// ============================================================================
+
+#[contract]
+pub struct ZkVerifierContract;
+
+#[contractimpl]
+impl ZkVerifierContract {
+    /// Register a verification key (anyone may call).
+    ///
+    /// Registering a key that is already stored only extends its TTL.
+    ///
+    /// # Arguments
+    /// * `vk_bytes` - Verification key bytes, as written by `bb write_vk`
+    ///
+    /// # Returns
+    /// * `BytesN<32>` - keccak256 of `vk_bytes`, to pass to `verify`
+    pub fn register_vk(env: Env, vk_bytes: Bytes) -> Result<BytesN<32>, VerifierError> {
+        // Reject keys the verifier could never use
+        UltraHonkVerifier::new(&env, &vk_bytes)?;
+
+        let vk_hash: BytesN<32> = env.crypto().keccak256(&vk_bytes).into();
+        let key = DataKey::Vk(vk_hash.clone());
+        if !env.storage().persistent().has(&key) {
+            env.storage().persistent().set(&key, &vk_bytes);
+            VkRegistered {
+                vk_hash: vk_hash.clone(),
+            }
+            .publish(&env);
+        }
+        extend_vk_ttl(&env, &key);
+
+        Ok(vk_hash)
+    }
+
+    /// Whether a VK is registered under `vk_hash`.
+    pub fn has_vk(env: Env, vk_hash: BytesN<32>) -> bool {
+        env.storage().persistent().has(&DataKey::Vk(vk_hash))
+    }
+
+    /// Get the VK bytes registered under `vk_hash`.
+    pub fn get_vk(env: Env, vk_hash: BytesN<32>) -> Option<Bytes> {
+        env.storage().persistent().get(&DataKey::Vk(vk_hash))
+    }
+
+    /// Verify an UltraHonk proof against a registered VK.
+    ///
+    /// # Arguments
+    /// * `vk_hash` - Hash returned by `register_vk`
+    /// * `proof` - Proof bytes; ZK proofs are told apart by length
+    /// * `public_inputs` - Public inputs, one 32-byte field element each
+    pub fn verify(
+        env: Env,
+        vk_hash: BytesN<32>,
+        proof: Bytes,
+        public_inputs: Bytes,
+    ) -> Result<(), VerifierError> {
+        let verifier = load_verifier(&env, vk_hash)?;
+        verifier.verify_auto(&proof, &public_inputs)?;
+        Ok(())
+    }
+
+    /// Verify a ZK UltraHonk proof against a registered VK; plain proofs are
+    /// rejected as `InvalidProof`.
+    ///
+    /// # Arguments
+    /// * `vk_hash` - Hash returned by `register_vk`
+    /// * `proof` - ZK proof bytes (`bb prove --zk`)
+    /// * `public_inputs` - Public inputs, one 32-byte field element each
+    pub fn verify_zk(
+        env: Env,
+        vk_hash: BytesN<32>,
+        proof: Bytes,
+        public_inputs: Bytes,
+    ) -> Result<(), VerifierError> {
+        let verifier = load_verifier(&env, vk_hash)?;
+        verifier.verify_zk(&proof, &public_inputs)?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod test;
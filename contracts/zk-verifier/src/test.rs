@@ -0,0 +1,258 @@
+#![cfg(test)]
+
+//! Unit tests for the ZK verifier contract.
+//!
+//! There are no bb fixtures here, so proofs are synthetic: these tests cover
+//! the registry and that each verification stage surfaces its own error.
+
+use crate::{VkRegistered, ZkVerifierContract, ZkVerifierContractClient, VK_TTL_LEDGERS};
+use game_common::{VerifierClient, VerifierError};
+use soroban_sdk::testutils::{storage::Persistent as _, Events as _, Ledger as _};
+use soroban_sdk::{Bytes, BytesN, Env, Event as _};
+use ultrahonk_soroban_verifier::{format::POINT_FIELDS, PROOF_BYTES, VK_BYTES, ZK_PROOF_BYTES};
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+fn setup_test() -> (Env, ZkVerifierContractClient<'static>) {
+    let env = Env::default();
+
+    env.ledger().set(soroban_sdk::testutils::LedgerInfo {
+        timestamp: 1441065600,
+        protocol_version: 25,
+        sequence_number: 100,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_ttl: 16,
+        min_persistent_entry_ttl: 16,
+        max_entry_ttl: u32::MAX / 2,
+    });
+
+    let contract_id = env.register(ZkVerifierContract, ());
+    let client = ZkVerifierContractClient::new(&env, &contract_id);
+
+    (env, client)
+}
+
+/// Helper to create a bb v0.87.0 VK with the given header and zeroed commitments
+fn fake_vk(env: &Env, log_circuit_size: u64, public_inputs_size: u64) -> Bytes {
+    let mut bytes = [0u8; VK_BYTES];
+    bytes[0..8].copy_from_slice(&(1u64 << log_circuit_size).to_be_bytes());
+    bytes[8..16].copy_from_slice(&log_circuit_size.to_be_bytes());
+    bytes[16..24].copy_from_slice(&public_inputs_size.to_be_bytes());
+    bytes[24..32].copy_from_slice(&1u64.to_be_bytes());
+    Bytes::from_slice(env, &bytes)
+}
+
+/// Helper to create `count` zeroed public inputs
+fn zero_inputs(env: &Env, count: usize) -> Bytes {
+    let mut inputs = Bytes::new(env);
+    for _ in 0..count {
+        inputs.append(&Bytes::from_array(env, &[0u8; 32]));
+    }
+    inputs
+}
+
+/// Padded proof whose first sumcheck univariate does not sum to zero
+fn bad_sumcheck_proof(env: &Env) -> Bytes {
+    let mut proof = [0u8; PROOF_BYTES];
+    let first_univariate = (16 + 8 * POINT_FIELDS) * 32;
+    proof[first_univariate + 31] = 1;
+    Bytes::from_slice(env, &proof)
+}
+
+/// Padded ZK proof whose first sumcheck univariate does not sum to zero
+fn bad_sumcheck_zk_proof(env: &Env) -> Bytes {
+    let mut proof = [0u8; ZK_PROOF_BYTES];
+    let first_univariate = (16 + 9 * POINT_FIELDS + 1) * 32;
+    proof[first_univariate + 31] = 1;
+    Bytes::from_slice(env, &proof)
+}
+
+/// Assert that a Result contains a specific error
+fn assert_verifier_error<T, E>(
+    result: &Result<Result<T, E>, Result<VerifierError, soroban_sdk::InvokeError>>,
+    expected_error: VerifierError,
+) {
+    match result {
+        Err(Ok(actual_error)) => {
+            assert_eq!(
+                *actual_error, expected_error,
+                "Expected error {:?} (code {}), but got {:?} (code {})",
+                expected_error, expected_error as u32, actual_error, *actual_error as u32
+            );
+        }
+        Err(Err(_invoke_error)) => {
+            panic!(
+                "Expected contract error {:?} (code {}), but got invocation error",
+                expected_error, expected_error as u32
+            );
+        }
+        Ok(Err(_conv_error)) => {
+            panic!(
+                "Expected contract error {:?} (code {}), but got conversion error",
+                expected_error, expected_error as u32
+            );
+        }
+        Ok(Ok(_)) => {
+            panic!(
+                "Expected error {:?} (code {}), but operation succeeded",
+                expected_error, expected_error as u32
+            );
+        }
+    }
+}
+
+// ============================================================================
+// Registry Tests
+// ============================================================================
+
+#[test]
+fn test_register_vk() {
+    let (env, client) = setup_test();
+    let vk = fake_vk(&env, 1, 16 + 2);
+    let expected_hash: BytesN<32> = env.crypto().keccak256(&vk).into();
+
+    let vk_hash = client.register_vk(&vk);
+    assert_eq!(vk_hash, expected_hash);
+    assert_eq!(
+        env.events().all(),
+        [VkRegistered {
+            vk_hash: vk_hash.clone(),
+        }
+        .to_xdr(&env, &client.address)]
+    );
+    assert!(client.has_vk(&vk_hash));
+    assert_eq!(client.get_vk(&vk_hash), Some(vk.clone()));
+
+    // Registering again is a no-op that returns the same hash
+    assert_eq!(client.register_vk(&vk), vk_hash);
+    assert_eq!(env.events().all().events().len(), 0);
+
+    // Other keys get their own entry
+    let other = client.register_vk(&fake_vk(&env, 2, 16 + 2));
+    assert_ne!(other, vk_hash);
+    assert!(client.has_vk(&other));
+}
+
+#[test]
+fn test_register_vk_rejects_invalid_vk() {
+    let (env, client) = setup_test();
+
+    let result = client.try_register_vk(&Bytes::from_array(&env, &[0u8; 64]));
+    assert_verifier_error(&result, VerifierError::VkParseError);
+
+    // Circuit size 0
+    let result = client.try_register_vk(&fake_vk(&env, 0, 16 + 2));
+    assert_verifier_error(&result, VerifierError::VkParseError);
+
+    let vk = fake_vk(&env, 1, 16 + 2);
+    assert!(!client.has_vk(&env.crypto().keccak256(&vk).into()));
+}
+
+#[test]
+fn test_vk_ttl_extended() {
+    let (env, client) = setup_test();
+    let vk_hash = client.register_vk(&fake_vk(&env, 1, 16 + 2));
+    let ttl = || {
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .get_ttl(&crate::DataKey::Vk(vk_hash.clone()))
+        })
+    };
+    assert_eq!(ttl(), VK_TTL_LEDGERS);
+
+    // A failed verification is rolled back, TTL extension included
+    env.ledger().with_mut(|li| li.sequence_number += 1_000);
+    assert_eq!(ttl(), VK_TTL_LEDGERS - 1_000);
+    let result = client.try_verify(&vk_hash, &bad_sumcheck_proof(&env), &zero_inputs(&env, 2));
+    assert_verifier_error(&result, VerifierError::SumcheckRoundFailed);
+    assert_eq!(ttl(), VK_TTL_LEDGERS - 1_000);
+
+    // Registering the key again keeps it alive
+    assert_eq!(client.register_vk(&fake_vk(&env, 1, 16 + 2)), vk_hash);
+    assert_eq!(ttl(), VK_TTL_LEDGERS);
+}
+
+// ============================================================================
+// Verification Tests
+// ============================================================================
+
+#[test]
+fn test_verify_unknown_vk() {
+    let (env, client) = setup_test();
+    let proof = Bytes::from_slice(&env, &[0u8; PROOF_BYTES]);
+
+    let unknown = BytesN::from_array(&env, &[7u8; 32]);
+    let result = client.try_verify(&unknown, &proof, &zero_inputs(&env, 2));
+    assert_verifier_error(&result, VerifierError::VkNotFound);
+}
+
+#[test]
+fn test_verify_errors_are_distinct() {
+    let (env, client) = setup_test();
+    let vk_hash = client.register_vk(&fake_vk(&env, 1, 16 + 2));
+    let inputs = zero_inputs(&env, 2);
+
+    let short = Bytes::from_array(&env, &[0u8; 32]);
+    let result = client.try_verify(&vk_hash, &short, &inputs);
+    assert_verifier_error(&result, VerifierError::InvalidProof);
+
+    let zero_proof = Bytes::from_slice(&env, &[0u8; PROOF_BYTES]);
+    let result = client.try_verify(&vk_hash, &zero_proof, &zero_inputs(&env, 3));
+    assert_verifier_error(&result, VerifierError::PublicInputsMismatch);
+
+    let result = client.try_verify(&vk_hash, &bad_sumcheck_proof(&env), &inputs);
+    assert_verifier_error(&result, VerifierError::SumcheckRoundFailed);
+
+    // Proofs must be padded to the full round count
+    let truncated = bad_sumcheck_proof(&env).slice(..PROOF_BYTES as u32 - 32);
+    let result = client.try_verify(&vk_hash, &truncated, &inputs);
+    assert_verifier_error(&result, VerifierError::InvalidProof);
+
+    // ZK proofs go through verify_zk
+    let zk_proof = Bytes::from_slice(&env, &[0u8; ZK_PROOF_BYTES]);
+    let result = client.try_verify(&vk_hash, &zk_proof, &zero_inputs(&env, 3));
+    assert_verifier_error(&result, VerifierError::PublicInputsMismatch);
+}
+
+#[test]
+fn test_verify_zk_rejects_plain_proofs() {
+    let (env, client) = setup_test();
+    let vk_hash = client.register_vk(&fake_vk(&env, 1, 16 + 2));
+    let inputs = zero_inputs(&env, 2);
+
+    let result = client.try_verify_zk(&vk_hash, &bad_sumcheck_zk_proof(&env), &inputs);
+    assert_verifier_error(&result, VerifierError::SumcheckRoundFailed);
+
+    // Plain proofs that `verify` would accept for checking are turned away
+    let result = client.try_verify_zk(&vk_hash, &bad_sumcheck_proof(&env), &inputs);
+    assert_verifier_error(&result, VerifierError::InvalidProof);
+
+    let unknown = BytesN::from_array(&env, &[7u8; 32]);
+    let result = client.try_verify_zk(&unknown, &bad_sumcheck_zk_proof(&env), &inputs);
+    assert_verifier_error(&result, VerifierError::VkNotFound);
+}
+
+// ============================================================================
+// Client Interface Tests
+// ============================================================================
+
+#[test]
+fn test_game_common_client() {
+    let (env, client) = setup_test();
+    let vk_hash = client.register_vk(&fake_vk(&env, 1, 16 + 2));
+    let verifier = VerifierClient::new(&env, &client.address);
+
+    let unknown = BytesN::from_array(&env, &[7u8; 32]);
+    let result = verifier.try_verify(&unknown, &bad_sumcheck_proof(&env), &zero_inputs(&env, 2));
+    assert_eq!(result, Err(Ok(VerifierError::VkNotFound)));
+
+    let result = verifier.try_verify(&vk_hash, &bad_sumcheck_proof(&env), &zero_inputs(&env, 2));
+    assert_eq!(result, Err(Ok(VerifierError::SumcheckRoundFailed)));
+
+    let result = verifier.try_verify_zk(&vk_hash, &bad_sumcheck_proof(&env), &zero_inputs(&env, 2));
+    assert_eq!(result, Err(Ok(VerifierError::InvalidProof)));
+}
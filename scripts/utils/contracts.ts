@@ -53,6 +53,12 @@ export async function getWorkspaceContracts(): Promise<ContractInfo[]> {
       throw new Error(`Missing [package].name in ${manifestPath}`);
     }
 
+    // Library crates (game-common, the verifier) have no WASM to build or deploy
+    const crateTypes = (manifestToml?.lib?.["crate-type"] ?? []) as string[];
+    if (!crateTypes.includes("cdylib")) {
+      continue;
+    }
+
     const wasmName = toWasmName(packageName);
     const envKey = toEnvKey(packageName);
 
@@ -324,7 +324,18 @@ pub trait GameHub {
         player2_points: i128,
     );
 
-    fn end_game(env: Env, session_id: u32, player1_won: bool);
+    fn end_game(env: Env, session_id: u32, outcome: GameOutcome);
+
+    fn cancel_session(env: Env, session_id: u32);
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum GameOutcome {
+    Player1,
+    Player2,
+    Draw,
+    Cancelled,
 }`}</code>
             </pre>
           </div>